@@ -1,6 +1,7 @@
 using Xunit;
 using Cognite.Kuiper;
 using System;
+using System.Threading.Tasks;
 
 namespace KuiperNet.Test;
 
@@ -42,4 +43,95 @@ public class UnitTest1
         var expr = new KuiperExpression("my_func(5, 10)", new CompilerConfig().AddCustomFunction("my_func", func));
         Assert.Equal("50", expr.Run());
     }
+
+    [Fact]
+    public void TestKuiperErrorKind()
+    {
+        var ex = Assert.Throws<KuiperException>(() => new KuiperExpression("\"test\".notafunc()", []));
+        Assert.Equal(KuiperErrorKind.Build, ex.Kind);
+
+        var expr = new KuiperExpression("1 / input", "input");
+        ex = Assert.Throws<KuiperException>(() => expr.Run("0"));
+        Assert.Equal(KuiperErrorKind.DivideByZero, ex.Kind);
+        Assert.Equal(2ul, ex.Start);
+        Assert.Equal(3ul, ex.End);
+    }
+
+    [Fact]
+    public void TestKuiperRunBatch()
+    {
+        var expr = new KuiperExpression("in1 + in2", "in1", "in2");
+        var results = expr.RunBatch(new[] { new[] { "1", "2" }, new[] { "3", "{" }, new[] { "5", "6" } });
+        Assert.Equal(3, results.Length);
+        Assert.Equal("3", results[0].Result);
+        Assert.True(results[1].IsError);
+        Assert.Equal(KuiperErrorKind.InvalidJson, results[1].Error.Kind);
+        Assert.Equal("11", results[2].Result);
+    }
+
+    [Fact]
+    public void TestKuiperConfigOptions()
+    {
+        var config = new CompilerConfig().DenyFunction("now");
+        var ex = Assert.Throws<KuiperException>(() => new KuiperExpression("now()", config));
+        Assert.Contains("Function now is not allowed", ex.Message);
+
+        var typed = new CompilerConfig().SetTypeCheckerMode(TypeCheckerMode.Early);
+        ex = Assert.Throws<KuiperException>(() => new KuiperExpression("1 + \"test\"", typed));
+        Assert.Equal(KuiperErrorKind.Type, ex.Kind);
+    }
+
+    [Fact]
+    public void TestKuiperRunLimited()
+    {
+        var expr = new KuiperExpression("[1, 2, 3].map(x => x + in1)", "in1");
+        expr.RunLimited(-1, out long opcount, "1");
+        Assert.True(opcount > 0);
+
+        var ex = Assert.Throws<KuiperException>(() => expr.RunLimited(opcount - 1, out _, "1"));
+        Assert.Equal(KuiperErrorKind.OperationLimitExceeded, ex.Kind);
+        Assert.Equal("[2,3,4]", expr.RunLimited(opcount, out _, "1"));
+    }
+
+    [Fact]
+    public void TestKuiperSharedExpression()
+    {
+        var expr = new KuiperExpression("in1 * 2", "in1");
+        var clones = new KuiperExpression[4];
+        for (int i = 0; i < clones.Length; i++)
+        {
+            clones[i] = expr.Clone();
+        }
+        // The clones keep the native expression alive.
+        expr.Dispose();
+
+        Parallel.For(0, clones.Length, i =>
+        {
+            using var handle = clones[i];
+            for (int j = 0; j < 100; j++)
+            {
+                Assert.Equal((j * 2).ToString(), handle.Run(j.ToString()));
+            }
+        });
+    }
+
+    [Fact]
+    public void TestKuiperUtf8()
+    {
+        var expr = new KuiperExpression("concat(\"blåbær: \", enhet.navn)", "enhet");
+        Assert.Equal("\"blåbær: Måler ÆØÅ ✓\"", expr.Run("{\"navn\": \"Måler ÆØÅ ✓\"}"));
+
+        // Spans are byte offsets, "æ" is two bytes in UTF-8.
+        var ex = Assert.Throws<KuiperException>(() => new KuiperExpression("\"æ\" + 1", []));
+        Assert.Equal(5ul, ex.Start);
+        Assert.Equal(6ul, ex.End);
+    }
+
+    [Fact]
+    public void TestKuiperDocs()
+    {
+        Assert.Contains("\"signature\":\"concat(", KuiperDocs.GetMethodDocs("concat"));
+        Assert.Null(KuiperDocs.GetMethodDocs("not_a_function"));
+        Assert.StartsWith("[", KuiperDocs.ListFunctions());
+    }
 }
@@ -7,6 +7,52 @@ using System.Text;
 
 namespace Cognite.Kuiper
 {
+    /// <summary>
+    /// Category of an error raised by Kuiper.
+    /// </summary>
+    public enum KuiperErrorKind
+    {
+        /// <summary>No error.</summary>
+        None = 0,
+        /// <summary>The expression could not be parsed.</summary>
+        Parser = 1,
+        /// <summary>The expression is invalid, for example calling a function with the wrong number of arguments.</summary>
+        Build = 2,
+        /// <summary>The expression failed during constant evaluation in the optimizer.</summary>
+        Optimizer = 3,
+        /// <summary>The type checker found an error in the expression.</summary>
+        Type = 4,
+        /// <summary>A source was missing at runtime.</summary>
+        SourceMissing = 5,
+        /// <summary>A value had the wrong type at runtime.</summary>
+        IncorrectType = 6,
+        /// <summary>Failed to convert a value at runtime.</summary>
+        ConversionFailed = 7,
+        /// <summary>An operation was invalid at runtime.</summary>
+        InvalidOperation = 8,
+        /// <summary>Division by zero at runtime.</summary>
+        DivideByZero = 9,
+        /// <summary>The expression exceeded the maximum number of operations.</summary>
+        OperationLimitExceeded = 10,
+        /// <summary>An input was not valid JSON.</summary>
+        InvalidJson = 11,
+        /// <summary>A string passed to Kuiper was not valid UTF-8.</summary>
+        InvalidString = 12,
+    }
+
+    /// <summary>
+    /// When to run the type checker during compilation.
+    /// </summary>
+    public enum TypeCheckerMode
+    {
+        /// <summary>Run the type checker before optimization.</summary>
+        Early = 0,
+        /// <summary>Run the type checker after optimization.</summary>
+        Late = 1,
+        /// <summary>Disable the type checker. This is the default.</summary>
+        Off = 2,
+    }
+
     /// <summary>
     /// Exception thrown by the Kuiper mapping language.
     /// </summary>
@@ -24,11 +70,20 @@ namespace Cognite.Kuiper
         /// Start and end may both be 0 if there is no known range.
         /// </summary>
         public ulong End { get; }
+        /// <summary>
+        /// The category of the error.
+        /// </summary>
+        public KuiperErrorKind Kind { get; }
 
-        public KuiperException(string message, ulong start, ulong end) : base(message)
+        public KuiperException(string message, ulong start, ulong end) : this(message, start, end, KuiperErrorKind.None)
+        {
+        }
+
+        public KuiperException(string message, ulong start, ulong end, KuiperErrorKind kind) : base(message)
         {
             Start = start;
             End = end;
+            Kind = kind;
         }
     }
 
@@ -39,6 +94,7 @@ namespace Cognite.Kuiper
         public bool is_error;
         public ulong start;
         public ulong end;
+        public KuiperErrorKind kind;
 #pragma warning restore CS0649
     }
 
@@ -55,6 +111,15 @@ namespace Cognite.Kuiper
 #pragma warning disable CS0649 // These fields are assigned in external code.
         public KuiperError error;
         public unsafe byte* result;
+        public long opcount;
+#pragma warning restore CS0649
+    }
+
+    internal struct BatchTransformResult
+    {
+#pragma warning disable CS0649 // These fields are assigned in external code.
+        public unsafe TransformResult* results;
+        public UIntPtr len;
 #pragma warning restore CS0649
     }
 
@@ -95,15 +160,33 @@ namespace Cognite.Kuiper
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "run_expression")]
         public unsafe static extern TransformResult* run_expression(byte** data, UIntPtr len, RawKuiperExpression* expression);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "run_expression_limited")]
+        public unsafe static extern TransformResult* run_expression_limited(byte** data, UIntPtr len, RawKuiperExpression* expression, long maxOperationCount);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "run_expression_batch")]
+        public unsafe static extern BatchTransformResult* run_expression_batch(byte** data, UIntPtr* counts, UIntPtr batchSize, RawKuiperExpression* expression);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "destroy_batch_transform_result")]
+        public unsafe static extern void destroy_batch_transform_result(BatchTransformResult* result);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "destroy_expression")]
         public unsafe static extern void destroy_expression(RawKuiperExpression* data);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "clone_expression")]
+        public unsafe static extern RawKuiperExpression* clone_expression(RawKuiperExpression* data);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "expression_to_string")]
         public unsafe static extern byte* expression_to_string(RawKuiperExpression* expression);
 
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "destroy_string")]
         public unsafe static extern void destroy_string(byte* data);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "get_method_docs")]
+        public unsafe static extern byte* get_method_docs(byte* name);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "list_functions")]
+        public unsafe static extern byte* list_functions();
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "new_compiler_config")]
         public unsafe static extern RawCompilerConfig* new_compiler_config();
 
@@ -116,6 +199,12 @@ namespace Cognite.Kuiper
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "config_set_max_macro_expansions")]
         public unsafe static extern void config_set_max_macro_expansions(RawCompilerConfig* config, int limit);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "config_set_type_checker_mode")]
+        public unsafe static extern void config_set_type_checker_mode(RawCompilerConfig* config, TypeCheckerMode mode);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "config_deny_function")]
+        public unsafe static extern int config_deny_function(RawCompilerConfig* config, byte* name);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "config_add_custom_function")]
         public unsafe static extern int config_add_custom_function(RawCompilerConfig* config, byte* name, IntPtr callback);
 
@@ -134,6 +223,85 @@ namespace Cognite.Kuiper
         }
     }
 
+    /// <summary>
+    /// Documentation for the built-in functions in Kuiper.
+    /// </summary>
+    public static class KuiperDocs
+    {
+        /// <summary>
+        /// Get the documentation for a built-in function, as a JSON object with the `name`,
+        /// `signature`, `description`, and `examples` of the function.
+        /// </summary>
+        /// <param name="name">Name of the function.</param>
+        /// <returns>JSON string, or null if there is no built-in function with the given name.</returns>
+        public static string GetMethodDocs(string name)
+        {
+            unsafe
+            {
+                var rawName = Encoding.UTF8.GetBytes(name + char.MinValue);
+                byte* result;
+                fixed (byte* namePtr = &rawName[0])
+                {
+                    result = KuiperInterop.get_method_docs(namePtr);
+                }
+                if (result == null) return null;
+                string resString = Utils.PointerToStringUTF8(result);
+                KuiperInterop.destroy_string(result);
+                return resString;
+            }
+        }
+
+        /// <summary>
+        /// List the documentation for all built-in functions, sorted by name.
+        /// </summary>
+        /// <returns>JSON array of objects in the same format as returned by `GetMethodDocs`.</returns>
+        public static string ListFunctions()
+        {
+            unsafe
+            {
+                var result = KuiperInterop.list_functions();
+                string resString = Utils.PointerToStringUTF8(result);
+                KuiperInterop.destroy_string(result);
+                return resString;
+            }
+        }
+    }
+
+    /// <summary>
+    /// The result of a single run in a batch, either a JSON string or an error.
+    /// </summary>
+    public sealed class KuiperResult
+    {
+        /// <summary>
+        /// JSON string result, or null if the run failed.
+        /// </summary>
+        public string Result { get; }
+        /// <summary>
+        /// The error, or null if the run succeeded.
+        /// </summary>
+        public KuiperException Error { get; }
+        /// <summary>
+        /// Whether the run failed.
+        /// </summary>
+        public bool IsError => Error != null;
+        /// <summary>
+        /// The number of operations performed, or 0 if the run failed.
+        /// </summary>
+        public long Opcount { get; }
+
+        internal KuiperResult(string result, KuiperException error, long opcount)
+        {
+            Result = result;
+            Error = error;
+            Opcount = opcount;
+        }
+    }
+
+    /// <summary>
+    /// A compiled Kuiper expression.
+    ///
+    /// Expressions are immutable, and may be run from several threads at once.
+    /// </summary>
     public sealed class KuiperExpression : IDisposable
     {
         private unsafe RawKuiperExpression* _expression;
@@ -152,6 +320,11 @@ namespace Cognite.Kuiper
         {
         }
 
+        private KuiperExpression(CompilerConfig config)
+        {
+            _config = config;
+        }
+
         public KuiperExpression(string expression, CompilerConfig config, params string[] inputs)
         {
             _config = config;
@@ -194,7 +367,7 @@ namespace Cognite.Kuiper
             }
         }
 
-        private unsafe KuiperException ExceptionFromError(KuiperError error)
+        private static unsafe KuiperException ExceptionFromError(KuiperError error)
         {
             if (!error.is_error)
             {
@@ -206,7 +379,7 @@ namespace Cognite.Kuiper
             {
                 msg = Utils.PointerToStringUTF8(error.error);
             }
-            return new KuiperException(msg, error.start, error.end);
+            return new KuiperException(msg, error.start, error.end, error.kind);
         }
 
         private unsafe KuiperException InitExpression(byte* expressionPtr, byte** inputsToRust, nuint inputsLength, RawCompilerConfig* config)
@@ -229,6 +402,19 @@ namespace Cognite.Kuiper
         /// to the `inputs` array passed to the constructor.</param>
         /// <returns>JSON string result.</returns>
         public string Run(params string[] inputs)
+        {
+            return RunLimited(-1, out _, inputs);
+        }
+
+        /// <summary>
+        /// Run a Kuiper expression, failing if it performs more than `maxOperationCount` operations.
+        /// </summary>
+        /// <param name="maxOperationCount">Maximum number of operations, or -1 for no limit.</param>
+        /// <param name="opcount">The number of operations performed by the expression.</param>
+        /// <param name="inputs">JSON strings passed as arguments, the number must be equal
+        /// to the `inputs` array passed to the constructor.</param>
+        /// <returns>JSON string result.</returns>
+        public string RunLimited(long maxOperationCount, out long opcount, params string[] inputs)
         {
             unsafe
             {
@@ -249,7 +435,7 @@ namespace Cognite.Kuiper
                 {
                     fixed (byte** inputsToRust = &inputPtrs[0])
                     {
-                        exc = RunInternal(inputsToRust, (nuint)rawInputs.Length, out transformedData);
+                        exc = RunInternal(inputsToRust, (nuint)rawInputs.Length, maxOperationCount, out transformedData, out opcount);
                         for (int i = 0; i < pinnedInputs.Length; i++)
                         {
                             pinnedInputs[i].Free();
@@ -258,19 +444,20 @@ namespace Cognite.Kuiper
                 }
                 else
                 {
-                    exc = RunInternal(null, 0, out transformedData);
+                    exc = RunInternal(null, 0, maxOperationCount, out transformedData, out opcount);
                 }
                 if (exc != null) ExceptionDispatchInfo.Capture(exc).Throw();
                 return transformedData;
             }
         }
 
-        private unsafe KuiperException RunInternal(byte** inputsToRust, nuint inputsLength, out string transformedData)
+        private unsafe KuiperException RunInternal(byte** inputsToRust, nuint inputsLength, long maxOperationCount, out string transformedData, out long opcount)
         {
             KuiperException exc = null;
             transformedData = null;
-            var result = KuiperInterop.run_expression(inputsToRust, inputsLength, _expression);
+            var result = KuiperInterop.run_expression_limited(inputsToRust, inputsLength, _expression, maxOperationCount);
             exc = ExceptionFromError((*result).error);
+            opcount = (*result).opcount;
             if (exc == null)
             {
                 transformedData = Utils.PointerToStringUTF8((*result).result);
@@ -279,6 +466,73 @@ namespace Cognite.Kuiper
             return exc;
         }
 
+        /// <summary>
+        /// Run a Kuiper expression once for each set of inputs, in a single native call.
+        /// This is much faster than calling `Run` repeatedly for small inputs.
+        /// </summary>
+        /// <param name="inputs">A list of input sets. Each set contains JSON strings passed as arguments,
+        /// the number must be equal to the `inputs` array passed to the constructor.</param>
+        /// <returns>One result per input set, in the same order. Failed runs do not affect the others.</returns>
+        public KuiperResult[] RunBatch(IReadOnlyList<string[]> inputs)
+        {
+            unsafe
+            {
+                var rawInputs = inputs.SelectMany(set => set).Select(inp => Encoding.UTF8.GetBytes(inp + char.MinValue)).ToArray();
+                var counts = inputs.Select(set => (UIntPtr)set.Length).ToArray();
+
+                GCHandle[] pinnedInputs = new GCHandle[rawInputs.Length];
+                byte*[] inputPtrs = new byte*[rawInputs.Length + 1];
+                UIntPtr[] rawCounts = new UIntPtr[counts.Length + 1];
+                counts.CopyTo(rawCounts, 0);
+
+                for (int i = 0; i < rawInputs.Length; i++)
+                {
+                    pinnedInputs[i] = GCHandle.Alloc(rawInputs[i], GCHandleType.Pinned);
+                    inputPtrs[i] = (byte*)pinnedInputs[i].AddrOfPinnedObject();
+                }
+
+                BatchTransformResult* result;
+                // The arrays have an extra element so that they are never empty.
+                fixed (byte** inputsToRust = &inputPtrs[0])
+                fixed (UIntPtr* countsToRust = &rawCounts[0])
+                {
+                    result = KuiperInterop.run_expression_batch(inputsToRust, countsToRust, (UIntPtr)counts.Length, _expression);
+                }
+                for (int i = 0; i < pinnedInputs.Length; i++)
+                {
+                    pinnedInputs[i].Free();
+                }
+
+                var results = new KuiperResult[(int)(*result).len];
+                for (int i = 0; i < results.Length; i++)
+                {
+                    var item = (*result).results[i];
+                    var exc = ExceptionFromError(item.error);
+                    results[i] = exc != null
+                        ? new KuiperResult(null, exc, 0)
+                        : new KuiperResult(Utils.PointerToStringUTF8(item.result), null, item.opcount);
+                }
+                KuiperInterop.destroy_batch_transform_result(result);
+                return results;
+            }
+        }
+
+        /// <summary>
+        /// Create a new handle to the same compiled expression. The native expression is shared,
+        /// and freed once every handle has been disposed. Use this to give each owner, for example
+        /// a worker thread, its own handle to dispose.
+        /// </summary>
+        /// <returns>A new handle to this expression.</returns>
+        public KuiperExpression Clone()
+        {
+            var expr = new KuiperExpression(_config);
+            unsafe
+            {
+                expr._expression = KuiperInterop.clone_expression(_expression);
+            }
+            return expr;
+        }
+
         /// <inheritdoc />
         public override string ToString()
         {
@@ -373,6 +627,42 @@ namespace Cognite.Kuiper
             return this;
         }
 
+        /// <summary>
+        /// Set when to run the type checker. The type checker is disabled by default.
+        /// </summary>
+        /// <param name="mode">Type checker mode.</param>
+        public CompilerConfig SetTypeCheckerMode(TypeCheckerMode mode)
+        {
+            unsafe
+            {
+                KuiperInterop.config_set_type_checker_mode(_config, mode);
+            }
+            return this;
+        }
+
+        /// <summary>
+        /// Deny a function, expressions calling it will fail to compile.
+        /// </summary>
+        /// <param name="name">The name of the function.</param>
+        /// <exception cref="InvalidOperationException">If denying the function fails for some reason.</exception>
+        public CompilerConfig DenyFunction(string name)
+        {
+            unsafe
+            {
+                var rawName = Encoding.UTF8.GetBytes(name + char.MinValue);
+                int res;
+                fixed (byte* namePtr = &rawName[0])
+                {
+                    res = KuiperInterop.config_deny_function(_config, namePtr);
+                }
+                if (res != 0)
+                {
+                    throw new InvalidOperationException($"Failed to deny function {name} in compiler config");
+                }
+            }
+            return this;
+        }
+
         unsafe static FreeDataCallback freeDataDelegate = new FreeDataCallback(data =>
         {
             var handle = GCHandle.FromIntPtr((IntPtr)data);
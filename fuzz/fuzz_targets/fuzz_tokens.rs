@@ -105,7 +105,7 @@ impl<'a> libfuzzer_sys::arbitrary::Arbitrary<'a> for TokenWrap {
     fn arbitrary(
         u: &mut libfuzzer_sys::arbitrary::Unstructured<'a>,
     ) -> libfuzzer_sys::arbitrary::Result<Self> {
-        let b: u8 = u.int_in_range(0..=25)?;
+        let b: u8 = u.int_in_range(0..=28)?;
         Ok(TokenWrap(match b {
             0 => Token::Period,
             1 => Token::OpenParenthesis,
@@ -159,6 +159,9 @@ impl<'a> libfuzzer_sys::arbitrary::Arbitrary<'a> for TokenWrap {
             23 => Token::DefineSym,
             24 => Token::CombinedArrow,
             25 => Token::Comment,
+            26 => Token::QuestionMark,
+            27 => Token::Try,
+            28 => Token::Catch,
             _ => unreachable!(),
         }))
     }
@@ -1,22 +1,85 @@
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
+use flate2::read::MultiGzDecoder;
+use kuiper_cli::docs::{docs_json, docs_markdown};
 use kuiper_cli::errors::KuiperCliError;
 use kuiper_cli::repl::repl;
+use kuiper_cli::serve::{serve, ServeConfig};
+use kuiper_cli::test_runner::run_tests;
 use kuiper_lang::compile_expression;
 use serde_json::Value;
-use std::fs::{self, read_to_string};
+use std::fs::{self, read_to_string, File};
 use std::io;
 use std::io::Read;
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum MessageEnd {
     Eof,
     LF,
+    /// Records are separated by NUL characters, both in the input and the output
+    Nul,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DocsFormat {
+    Markdown,
+    /// A JSON array with the name, signature, description, arguments, and examples of each function
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run all expression test files (`.kp`) in a directory and report the results.
+    ///
+    /// Each test file starts with a JSON comment declaring the input names and a list of
+    /// cases, each with `inputs` and either an `expected` output or an `expected_error`.
+    /// Cases with neither are compared against a snapshot stored next to the test file.
+    Test {
+        /// Directory or file containing the tests
+        path: PathBuf,
+
+        /// Overwrite snapshot files with the current outputs instead of comparing against them
+        #[arg(long)]
+        update_snapshots: bool,
+    },
+    /// Print the reference documentation for all built-in functions.
+    Docs {
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: DocsFormat,
+    },
+    /// Run an HTTP server that transforms the JSON body of each POST request with an expression,
+    /// and responds with the result.
+    Serve {
+        /// File to load kuiper expression to run from
+        #[arg(short = 'f', long)]
+        expression_file: PathBuf,
+
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Maximum number of requests to handle at the same time. Further requests are rejected
+        #[arg(long, default_value_t = 16)]
+        max_concurrency: usize,
+
+        /// Maximum number of operations the expression may use per request, 0 for no limit
+        #[arg(long, default_value_t = 0)]
+        max_operations: i64,
+    },
 }
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Kuiper expression to run
     #[arg(short, long)]
     expression: Option<String>,
@@ -25,9 +88,13 @@ struct Args {
     #[arg(short = 'f', long)]
     expression_file: Option<PathBuf>,
 
-    /// Input data, uses STDIN if omitted
+    /// Input data, uses STDIN if omitted. Files ending with `.gz` are decompressed automatically
     input: Option<PathBuf>,
 
+    /// Decompress gzipped input data
+    #[arg(long)]
+    gzip: bool,
+
     /// Message separator
     #[arg(short, long, value_enum, default_value = "eof")]
     separator: MessageEnd,
@@ -43,23 +110,60 @@ struct Args {
     /// Output results to a file instead of STDOUT
     #[arg(short, long)]
     output: Option<PathBuf>,
+
+    /// Set the exit code based on the last output: 0 if it is truthy, 1 if it is false or null.
+    /// Errors always result in exit code 2.
+    #[arg(long)]
+    exit_status: bool,
+
+    /// Print string results without quotes. Arrays of strings are printed with one string per line.
+    #[arg(short, long)]
+    raw_output: bool,
+
+    /// Print JSON output indented over multiple lines
+    #[arg(long, conflicts_with = "compact")]
+    pretty: bool,
+
+    /// Print JSON output on a single line. This is the default.
+    /// Object keys are always sorted in the output
+    #[arg(long)]
+    compact: bool,
+
+    /// Make a string available to the expression as an input with the given name
+    #[arg(long, num_args = 2, value_names = ["NAME", "VALUE"])]
+    arg: Vec<String>,
+
+    /// Make a JSON value available to the expression as an input with the given name
+    #[arg(long, num_args = 2, value_names = ["NAME", "JSON"])]
+    argjson: Vec<String>,
 }
 
 impl Args {
     pub fn launch_repl(&self) -> bool {
-        self.expression.is_none() && self.expression_file.is_none() && self.input.is_none()
+        self.command.is_none()
+            && self.expression.is_none()
+            && self.expression_file.is_none()
+            && self.input.is_none()
     }
 }
 
 fn load_input_data(args: &Args) -> Result<Vec<Value>, KuiperCliError> {
-    let string_data = match &args.input {
-        Some(path) => read_to_string(path)?,
-        None => {
-            let mut buffer = Vec::new();
-            io::stdin().read_to_end(&mut buffer)?;
-            String::from_utf8(buffer)?
-        }
+    let (reader, gzip): (Box<dyn Read>, bool) = match &args.input {
+        Some(path) => (
+            Box::new(File::open(path)?),
+            args.gzip || path.extension().is_some_and(|ext| ext == "gz"),
+        ),
+        None => (Box::new(io::stdin()), args.gzip),
     };
+    let mut reader = if gzip {
+        Box::new(MultiGzDecoder::new(reader))
+    } else {
+        reader
+    };
+
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+    let string_data = String::from_utf8(buffer)?;
 
     let data = match &args.separator {
         MessageEnd::LF => string_data
@@ -67,12 +171,44 @@ fn load_input_data(args: &Args) -> Result<Vec<Value>, KuiperCliError> {
             .split('\n')
             .map(serde_json::from_str::<Value>)
             .collect::<Result<Vec<Value>, serde_json::Error>>()?,
+        MessageEnd::Nul => string_data
+            .split('\0')
+            .filter(|record| !record.trim().is_empty())
+            .map(serde_json::from_str::<Value>)
+            .collect::<Result<Vec<Value>, serde_json::Error>>()?,
         MessageEnd::Eof => vec![serde_json::from_str(&string_data)?],
     };
 
     Ok(data)
 }
 
+/// Collect named inputs given with `--arg` and `--argjson`.
+fn load_named_args(args: &Args) -> Result<(Vec<String>, Vec<Value>), KuiperCliError> {
+    let mut names = Vec::new();
+    let mut values = Vec::new();
+
+    for [name, value] in args.arg.as_chunks::<2>().0 {
+        names.push(name.clone());
+        values.push(Value::String(value.clone()));
+    }
+    for [name, value] in args.argjson.as_chunks::<2>().0 {
+        names.push(name.clone());
+        values.push(serde_json::from_str(value).map_err(|e| {
+            KuiperCliError::ErrorMessage(format!("Invalid JSON for argument {name}: {e}"))
+        })?);
+    }
+
+    for (i, name) in names.iter().enumerate() {
+        if name == "input" || names[..i].contains(name) {
+            return Err(KuiperCliError::ErrorMessage(format!(
+                "Argument {name} is defined more than once"
+            )));
+        }
+    }
+
+    Ok((names, values))
+}
+
 fn load_expression(args: &Args) -> Result<String, KuiperCliError> {
     match (&args.expression, &args.expression_file) {
         (None, None) => Err("Either expression or expression file needs to be provided!")?,
@@ -82,23 +218,51 @@ fn load_expression(args: &Args) -> Result<String, KuiperCliError> {
     }
 }
 
+fn serialize_output(args: &Args, value: &Value) -> Result<String, KuiperCliError> {
+    if args.raw_output {
+        match value {
+            Value::String(s) => return Ok(s.clone()),
+            Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_string) => {
+                return Ok(items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join("\n"));
+            }
+            _ => (),
+        }
+    }
+
+    if args.pretty {
+        Ok(serde_json::to_string_pretty(value)?)
+    } else {
+        Ok(serde_json::to_string(value)?)
+    }
+}
+
 fn run_formatter(args: &Args) -> Result<String, KuiperCliError> {
     let expression = load_expression(args)?;
     let formatted_expression = kuiper_lang::format_expression(&expression)?;
     Ok(formatted_expression)
 }
 
-fn inner_run(args: &Args) -> Result<Vec<String>, KuiperCliError> {
+fn inner_run(args: &Args) -> Result<Vec<Value>, KuiperCliError> {
     let expression = load_expression(args)?;
 
-    let expression = compile_expression(&expression, &["input"])?;
+    let (arg_names, arg_values) = load_named_args(args)?;
+
+    let inputs = std::iter::once("input")
+        .chain(arg_names.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+    let expression = compile_expression(&expression, &inputs)?;
 
     let data = load_input_data(args)?;
 
     let mut res = Vec::new();
     for input in data {
-        let (result, opcount) = expression.run_get_opcount([&input])?;
-        res.push(serde_json::to_string(&*result)?);
+        let (result, opcount) =
+            expression.run_get_opcount(std::iter::once(&input).chain(arg_values.iter()))?;
+        res.push(result.into_owned());
 
         if args.verbose {
             println!("Expression executed with {opcount} operations");
@@ -108,12 +272,69 @@ fn inner_run(args: &Args) -> Result<Vec<String>, KuiperCliError> {
     Ok(res)
 }
 
-pub fn main() {
+pub fn main() -> ExitCode {
     let args = Args::parse();
 
     if args.launch_repl() {
         repl(args.verbose);
-        return;
+        return ExitCode::SUCCESS;
+    }
+
+    match &args.command {
+        Some(Command::Test {
+            path,
+            update_snapshots,
+        }) => {
+            return match run_tests(path, *update_snapshots) {
+                Ok(report) if report.success() => ExitCode::SUCCESS,
+                Ok(_) => ExitCode::from(1),
+                Err(error) => {
+                    eprintln!("\x1b[91mError:\x1b[0m {error}");
+                    ExitCode::from(2)
+                }
+            };
+        }
+        Some(Command::Docs { format }) => {
+            let docs = match format {
+                DocsFormat::Markdown => Ok(docs_markdown()),
+                DocsFormat::Json => docs_json(),
+            };
+            return match docs {
+                Ok(docs) => {
+                    println!("{}", docs.trim_end());
+                    ExitCode::SUCCESS
+                }
+                Err(error) => {
+                    eprintln!("\x1b[91mError:\x1b[0m {error}");
+                    ExitCode::from(2)
+                }
+            };
+        }
+        Some(Command::Serve {
+            expression_file,
+            port,
+            host,
+            max_concurrency,
+            max_operations,
+        }) => {
+            let config = ServeConfig {
+                address: format!("{host}:{port}"),
+                max_concurrency: *max_concurrency,
+                max_operations: *max_operations,
+            };
+            let res = read_to_string(expression_file)
+                .map_err(KuiperCliError::from)
+                .and_then(|expression| Ok(compile_expression(&expression, &["input"])?))
+                .and_then(|expression| serve(expression, &config));
+            return match res {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(error) => {
+                    eprintln!("\x1b[91mError:\x1b[0m {error}");
+                    ExitCode::from(2)
+                }
+            };
+        }
+        None => (),
     }
 
     match args.format {
@@ -125,6 +346,7 @@ pub fn main() {
                             "\x1b[91mError writing to file {}:\x1b[0m {e}",
                             path.display()
                         );
+                        return ExitCode::from(2);
                     }
                 } else if let Some(path) = &args.expression_file {
                     if let Err(e) = fs::write(path, formatted) {
@@ -132,28 +354,55 @@ pub fn main() {
                             "\x1b[91mError writing to file {}:\x1b[0m {e}",
                             path.display()
                         );
+                        return ExitCode::from(2);
                     }
                 } else {
                     println!("{formatted}");
                 }
+                ExitCode::SUCCESS
+            }
+            Err(error) => {
+                eprintln!("\x1b[91mError:\x1b[0m {error}");
+                ExitCode::from(2)
             }
-            Err(error) => eprintln!("\x1b[91mError:\x1b[0m {error}"),
         },
 
         false => match inner_run(&args) {
-            Ok(strings) => strings.into_iter().for_each(|s| {
-                if let Some(path) = &args.output {
-                    fs::write(path, s).unwrap_or_else(|e| {
-                        eprintln!(
-                            "\x1b[91mError writing to file {}:\x1b[0m {e}",
-                            path.display()
-                        )
-                    });
-                } else {
-                    println!("{s}");
+            Ok(values) => {
+                for value in &values {
+                    let s = match serialize_output(&args, value) {
+                        Ok(s) => s,
+                        Err(error) => {
+                            eprintln!("\x1b[91mError:\x1b[0m {error}");
+                            return ExitCode::from(2);
+                        }
+                    };
+                    if let Some(path) = &args.output {
+                        if let Err(e) = fs::write(path, s) {
+                            eprintln!(
+                                "\x1b[91mError writing to file {}:\x1b[0m {e}",
+                                path.display()
+                            );
+                            return ExitCode::from(2);
+                        }
+                    } else if args.separator == MessageEnd::Nul {
+                        print!("{s}\0");
+                    } else {
+                        println!("{s}");
+                    }
+                }
+
+                match values.last() {
+                    Some(Value::Null | Value::Bool(false)) | None if args.exit_status => {
+                        ExitCode::from(1)
+                    }
+                    _ => ExitCode::SUCCESS,
                 }
-            }),
-            Err(error) => eprintln!("\x1b[91mError:\x1b[0m {error}"),
+            }
+            Err(error) => {
+                eprintln!("\x1b[91mError:\x1b[0m {error}");
+                ExitCode::from(2)
+            }
         },
     }
 }
@@ -0,0 +1,251 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use kuiper_lang::docs::{FunctionDef, FUNCTIONS};
+
+/// A single argument of a builtin function, as read from its signature.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct ArgumentDoc {
+    /// Name of the argument. Lambda arguments are given as written in the signature,
+    /// for example `(acc, val) => ...`.
+    pub name: String,
+    /// Whether the argument may be omitted
+    pub optional: bool,
+    /// Whether the argument may be repeated any number of times
+    pub variadic: bool,
+    /// Whether the argument is a lambda
+    pub lambda: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExampleDoc {
+    pub input: &'static str,
+    pub output: Option<&'static str>,
+}
+
+/// Structured documentation for a builtin function.
+#[derive(Debug, Serialize)]
+pub struct FunctionDoc {
+    pub name: &'static str,
+    pub signature: &'static str,
+    pub description: &'static str,
+    pub args: Vec<ArgumentDoc>,
+    pub examples: Vec<ExampleDoc>,
+}
+
+/// Iterate over the documented builtins, sorted by name.
+fn documented_functions() -> impl Iterator<Item = (&'static str, &'static FunctionDef)> {
+    FUNCTIONS.iter().map(|def| (def.name, def))
+}
+
+/// Get the documentation of all builtin functions, sorted by name.
+pub fn function_docs() -> Vec<FunctionDoc> {
+    documented_functions()
+        .map(|(name, def)| FunctionDoc {
+            name,
+            signature: def.signature,
+            description: def.description,
+            args: parse_signature_args(def.signature),
+            examples: def
+                .examples
+                .iter()
+                .map(|e| ExampleDoc {
+                    input: e.input,
+                    output: e.output,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Render the builtin function reference as a JSON array.
+pub fn docs_json() -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&function_docs())
+}
+
+/// Render the builtin function reference as Markdown, in the same format as
+/// `kuiper_documentation/built_in_functions.md`.
+pub fn docs_markdown() -> String {
+    let mut res = String::from(
+        "---\npagination_next: null\npagination_prev: null\ntitle: Functions\n---\n\n# Functions\n",
+    );
+    // Writing to a string cannot fail.
+    for (name, def) in documented_functions() {
+        let _ = write!(
+            res,
+            "\n## {name}\n\n`{}`\n\n{}\n\n",
+            def.signature, def.description
+        );
+        let plural = if def.examples.len() > 1 { "s" } else { "" };
+        let _ = writeln!(res, "**Code example{plural}**\n");
+        for (i, example) in def.examples.iter().enumerate() {
+            if i > 0 {
+                res.push('\n');
+            }
+            if example.output.is_some() {
+                res.push_str("**Input**\n");
+            }
+            let _ = writeln!(res, "```kuiper\n{}\n```", example.input);
+            if let Some(output) = example.output {
+                let _ = writeln!(res, "**Output**\n```\n{output}\n```");
+            }
+        }
+    }
+    res
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SignatureToken<'a> {
+    Open,
+    Close,
+    Comma,
+    Arrow,
+    Ellipsis,
+    Ident(&'a str),
+}
+
+fn tokenize_signature(sig: &str) -> Vec<SignatureToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = sig;
+    while let Some(c) = rest.chars().next() {
+        let (token, len) = match c {
+            '(' => (Some(SignatureToken::Open), 1),
+            ')' => (Some(SignatureToken::Close), 1),
+            ',' => (Some(SignatureToken::Comma), 1),
+            _ if rest.starts_with("=>") => (Some(SignatureToken::Arrow), 2),
+            _ if rest.starts_with("...") => (Some(SignatureToken::Ellipsis), 3),
+            c if c.is_alphanumeric() || c == '_' => {
+                let len = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                (Some(SignatureToken::Ident(&rest[..len])), len)
+            }
+            c => (None, c.len_utf8()),
+        };
+        tokens.extend(token);
+        rest = &rest[len..];
+    }
+    tokens
+}
+
+/// Parse the argument list of a signature like `get(x, path(, default))`.
+///
+/// Optional arguments are written in parentheses, `...` marks the previous argument as
+/// repeatable, and lambdas are written as `it => ...` or `(a, b) => ...`. If the signature
+/// lists several alternative forms, only the first is used.
+pub fn parse_signature_args(signature: &str) -> Vec<ArgumentDoc> {
+    let first = signature.split('`').next().unwrap_or_default();
+    let Some(start) = first.find('(') else {
+        return Vec::new();
+    };
+    let tokens = tokenize_signature(&first[start..]);
+    let mut args = Vec::new();
+    // Skip the opening parenthesis of the argument list.
+    let mut pos = 1;
+    parse_args(&tokens, &mut pos, false, &mut args);
+    args
+}
+
+/// Find the index of the parenthesis closing the one at `open`.
+fn matching_close(tokens: &[SignatureToken<'_>], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, token) in tokens.iter().enumerate().skip(open) {
+        match token {
+            SignatureToken::Open => depth += 1,
+            SignatureToken::Close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+fn lambda_arg(params: String, optional: bool) -> ArgumentDoc {
+    ArgumentDoc {
+        name: format!("{params} => ..."),
+        optional,
+        variadic: false,
+        lambda: true,
+    }
+}
+
+/// Parse arguments until the closing parenthesis of the current group, leaving `pos` after it.
+fn parse_args(
+    tokens: &[SignatureToken<'_>],
+    pos: &mut usize,
+    optional: bool,
+    args: &mut Vec<ArgumentDoc>,
+) {
+    while let Some(token) = tokens.get(*pos) {
+        match token {
+            SignatureToken::Close => {
+                *pos += 1;
+                return;
+            }
+            SignatureToken::Comma => *pos += 1,
+            SignatureToken::Ellipsis => {
+                if let Some(last) = args.last_mut() {
+                    last.variadic = true;
+                }
+                *pos += 1;
+            }
+            // Lambdas are consumed with their bodies below, so a stray arrow can be ignored.
+            SignatureToken::Arrow => *pos += 1,
+            SignatureToken::Open => {
+                let close = matching_close(tokens, *pos).unwrap_or(tokens.len());
+                if tokens.get(close + 1) == Some(&SignatureToken::Arrow) {
+                    let params = lambda_params(&tokens[*pos + 1..close]);
+                    args.push(lambda_arg(format!("({params})"), optional));
+                    *pos = close + 2;
+                    skip_lambda_body(tokens, pos);
+                } else {
+                    *pos += 1;
+                    parse_args(tokens, pos, true, args);
+                }
+            }
+            SignatureToken::Ident(name) => {
+                if tokens.get(*pos + 1) == Some(&SignatureToken::Arrow) {
+                    args.push(lambda_arg(name.to_string(), optional));
+                    *pos += 2;
+                    skip_lambda_body(tokens, pos);
+                } else {
+                    args.push(ArgumentDoc {
+                        name: name.to_string(),
+                        optional,
+                        variadic: false,
+                        lambda: false,
+                    });
+                    *pos += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Format the parameter list of a lambda, like `acc, val` or `it(, index)`.
+fn lambda_params(tokens: &[SignatureToken<'_>]) -> String {
+    let mut res = String::new();
+    for token in tokens {
+        match token {
+            SignatureToken::Open => res.push('('),
+            SignatureToken::Close => res.push(')'),
+            SignatureToken::Comma => res.push_str(", "),
+            SignatureToken::Arrow => res.push_str(" => "),
+            SignatureToken::Ellipsis => res.push_str("..."),
+            SignatureToken::Ident(name) => res.push_str(name),
+        }
+    }
+    res
+}
+
+/// Skip the `...` body of a lambda.
+fn skip_lambda_body(tokens: &[SignatureToken<'_>], pos: &mut usize) {
+    if tokens.get(*pos) == Some(&SignatureToken::Ellipsis) {
+        *pos += 1;
+    }
+}
@@ -1,3 +1,6 @@
 pub(crate) mod builtins;
+pub mod docs;
 pub mod errors;
 pub mod repl;
+pub mod serve;
+pub mod test_runner;
@@ -1,20 +1,180 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
 use crate::builtins::BUILT_INS;
+use colored::{ColoredString, Colorize};
+use kuiper_lang::lex::Token;
+use logos::Logos;
 use rustyline::{
-    completion::Completer, validate::MatchingBracketValidator, Context, Helper, Highlighter,
-    Hinter, Validator,
+    completion::Completer,
+    highlight::{CmdKind, Highlighter},
+    validate::{ValidationContext, ValidationResult, Validator},
+    Context, Helper, Hinter,
 };
 
-#[derive(Hinter, Helper, Validator, Highlighter)]
+#[derive(Hinter, Helper)]
 pub struct KuiperHelper {
-    #[rustyline(Validator)]
-    validator: MatchingBracketValidator,
+    /// The last expression that failed to compile, and the span of the error.
+    last_error: Option<(String, Range<usize>)>,
 }
 
 impl KuiperHelper {
     pub fn new() -> Self {
-        KuiperHelper {
-            validator: MatchingBracketValidator::new(),
+        KuiperHelper { last_error: None }
+    }
+
+    /// Store the location of a compile error, so that it can be highlighted
+    /// if the expression is edited again.
+    pub fn set_error(&mut self, expression: &str, span: Range<usize>) {
+        self.last_error = Some((expression.to_string(), span));
+    }
+
+    pub fn clear_error(&mut self) {
+        self.last_error = None;
+    }
+}
+
+fn is_builtin(name: &str) -> bool {
+    BUILT_INS
+        .iter()
+        .any(|f| f.strip_suffix('(').is_some_and(|f| f == name))
+}
+
+fn color_token(token: &Token, raw: &str, next: Option<&char>) -> ColoredString {
+    match token {
+        Token::String(_) | Token::RawTemplateString(_) => raw.green(),
+        Token::Float(_) | Token::Integer(_) | Token::Boolean(_) => raw.yellow(),
+        Token::TypeLiteral(_) => raw.yellow(),
+        Token::Operator(_)
+        | Token::UnaryOperator(_)
+        | Token::Arrow
+        | Token::DefineEqual
+        | Token::DefineSym
+        | Token::DotDot
+        | Token::QuestionMark => raw.magenta(),
+        Token::If | Token::Else | Token::Try | Token::Catch | Token::Not => raw.magenta().bold(),
+        Token::Identifier(name) if next == Some(&'(') && is_builtin(name) => raw.blue(),
+        Token::Comment => raw.dimmed(),
+        _ => raw.normal(),
+    }
+}
+
+/// Produce a syntax highlighted version of the expression. Tokens overlapping
+/// `error_span` are marked as errors.
+fn highlight_expression(line: &str, error_span: Option<&Range<usize>>) -> String {
+    let mut res = String::with_capacity(line.len() * 2);
+    let mut last = 0;
+    let mut last_token = None;
+
+    for (token, span) in Token::lexer(line).spanned() {
+        res.push_str(&line[last..span.start]);
+        let raw = &line[span.clone()];
+        let token = token.map(|t| t.contextual(last_token.as_ref()));
+        if let Ok(t) = &token {
+            last_token = Some(t.clone());
+        }
+
+        let colored = match (&token, error_span) {
+            (_, Some(err)) if span.start < err.end.max(err.start + 1) && err.start < span.end => {
+                raw.red().underline()
+            }
+            (Ok(token), _) => {
+                let next = line[span.end..].trim_start().chars().next();
+                color_token(token, raw, next.as_ref())
+            }
+            (Err(_), _) => raw.red(),
+        };
+        res.push_str(&colored.to_string());
+        last = span.end;
+    }
+    res.push_str(&line[last..]);
+
+    res
+}
+
+impl Highlighter for KuiperHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        // Magic functions are not kuiper expressions
+        if line.starts_with('/') && !(line.starts_with("//") || line.starts_with("/*")) {
+            return Cow::Borrowed(line);
         }
+
+        let error_span = match &self.last_error {
+            Some((expression, span)) if expression == line => Some(span),
+            _ => None,
+        };
+
+        Cow::Owned(highlight_expression(line, error_span))
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize, kind: CmdKind) -> bool {
+        kind != CmdKind::MoveCursor
+    }
+}
+
+/// Check whether the input is complete, or if we should keep reading lines.
+/// Input is incomplete if it ends with a `\`, or if it has unclosed brackets,
+/// ignoring any brackets inside strings and comments.
+fn validate_input(input: &str) -> ValidationResult {
+    if input.trim_end().ends_with('\\') {
+        return ValidationResult::Incomplete;
+    }
+
+    let mut stack = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' | '`' => {
+                // Skip to the end of the string, an unterminated string is left to the compiler.
+                while let Some(n) = chars.next() {
+                    if n == '\\' {
+                        chars.next();
+                    } else if n == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for n in chars.by_ref() {
+                    if n == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut closed = false;
+                while let Some(n) = chars.next() {
+                    if n == '*' && chars.peek() == Some(&'/') {
+                        chars.next();
+                        closed = true;
+                        break;
+                    }
+                }
+                if !closed {
+                    return ValidationResult::Incomplete;
+                }
+            }
+            '(' | '[' | '{' => stack.push(c),
+            ')' | ']' | '}' => match (stack.pop(), c) {
+                (Some('('), ')') | (Some('['), ']') | (Some('{'), '}') => {}
+                // Leave mismatched brackets to the compiler, which gives a better error message.
+                _ => return ValidationResult::Valid(None),
+            },
+            _ => {}
+        }
+    }
+
+    if stack.is_empty() {
+        ValidationResult::Valid(None)
+    } else {
+        ValidationResult::Incomplete
+    }
+}
+
+impl Validator for KuiperHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        Ok(validate_input(ctx.input()))
     }
 }
 
@@ -49,11 +209,59 @@ impl Completer for KuiperHelper {
 
         let word: String = line.chars().skip(low).take(high - low).collect();
         let candidates = BUILT_INS
-            .into_iter()
+            .iter()
             .filter(|s| s.starts_with(&word))
-            .map(String::from)
+            .cloned()
             .collect();
 
         Ok((low, candidates))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rustyline::validate::ValidationResult;
+
+    use super::validate_input;
+
+    fn is_complete(input: &str) -> bool {
+        match validate_input(input) {
+            ValidationResult::Valid(_) => true,
+            ValidationResult::Incomplete => false,
+            _ => panic!("Unexpected validation result for {input:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_brackets() {
+        assert!(is_complete("1 + 1"));
+        assert!(is_complete("[1, {\"a\": (2)}]"));
+        assert!(!is_complete("[1, 2"));
+        assert!(!is_complete("{\"a\": [1,\n2]"));
+        assert!(!is_complete("map(input, x =>"));
+        assert!(!is_complete("1 +\\"));
+        // Mismatched brackets are left to the compiler.
+        assert!(is_complete("[1, 2)"));
+        assert!(is_complete("]"));
+    }
+
+    #[test]
+    fn test_validate_strings() {
+        assert!(is_complete(r#""[(""#));
+        assert!(is_complete(r#"'{' + `[`"#));
+        assert!(is_complete(r#"["\"]", "\\"]"#));
+        assert!(!is_complete(r#"["]""#));
+        // Unclosed strings are left to the compiler.
+        assert!(is_complete(r#""abc"#));
+    }
+
+    #[test]
+    fn test_validate_comments() {
+        assert!(is_complete("1 // [("));
+        assert!(is_complete("1 /* [( */"));
+        assert!(!is_complete("1 /* [("));
+        assert!(!is_complete("[1, // ]\n2"));
+        assert!(is_complete("[1, /* ] */ 2]"));
+        assert!(is_complete("4 / 2"));
+    }
+}
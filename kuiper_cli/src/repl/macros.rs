@@ -11,7 +11,7 @@ pub struct Macro {
 impl Macro {
     pub fn from_expression(expr: &str) -> Result<Macro, &str> {
         let name_pattern = Regex::new(r"#(\w+)\s*:=").unwrap();
-        let def_pattern = Regex::new(r":=\s*(.+)\s*;").unwrap();
+        let def_pattern = Regex::new(r"(?s):=\s*(.+)\s*;").unwrap();
 
         let name = name_pattern
             .captures(expr)
@@ -2,11 +2,16 @@ use std::{cmp::max, collections::HashMap, vec};
 
 use colored::Colorize;
 use kuiper_lang::types::Type;
+use rustyline::history::DefaultHistory;
 use serde_json::Value;
 
 use crate::builtins::{BUILT_INS, HELP};
 
-use super::{io::printerr, macros::Macro};
+use super::{
+    io::printerr,
+    macros::Macro,
+    session::{restore_session, save_session},
+};
 
 fn help(command: Option<&str>) {
     match command {
@@ -51,8 +56,21 @@ fn help(command: Option<&str>) {
                     "/help (<function>)",
                     "Display this help page or help for specific functions",
                 ),
+                (
+                    "/doc <function>",
+                    "Display documentation and examples for a function",
+                ),
                 ("/store <name>", "Store the last result as a named variable"),
+                (
+                    "/load <name> <path>",
+                    "Load a JSON file and store it as a named variable",
+                ),
                 ("/macros", "List all stored macros and their definitions"),
+                (
+                    "/save <path>",
+                    "Save stored values, macros and history to a session file",
+                ),
+                ("/restore <path>", "Restore a session saved with /save"),
                 (
                     "/type <expression>",
                     "Determine the resulting type of an expression",
@@ -65,6 +83,38 @@ fn help(command: Option<&str>) {
     }
 }
 
+fn doc(function_name: &str) {
+    let Some(function) = HELP.get(function_name) else {
+        printerr!(format!("No function named {function_name}"), "");
+        return;
+    };
+
+    println!(
+        "{}",
+        format!("Documentation for {function_name}")
+            .bold()
+            .underline()
+    );
+    println!("Signature:  {}\n", function.signature);
+    println!("{}", function.description.trim());
+
+    if !function.examples.is_empty() {
+        println!("\n{}", "Examples".bold());
+    }
+    for example in function.examples {
+        for line in example.input.lines() {
+            println!("  {}", line.cyan());
+        }
+        if let Some(output) = example.output {
+            for (i, line) in output.lines().enumerate() {
+                let prefix = if i == 0 { "=>" } else { "  " };
+                println!("  {} {line}", prefix.dimmed());
+            }
+        }
+        println!();
+    }
+}
+
 pub enum ReplResult {
     Continue,
     Stop,
@@ -76,6 +126,7 @@ pub fn apply_magic_function(
     inputs: &mut Vec<String>,
     index: &mut usize,
     macro_defs: &mut HashMap<String, Macro>,
+    history: &mut DefaultHistory,
 ) -> ReplResult {
     let parsed_line: Vec<&str> = line.split_whitespace().collect();
 
@@ -85,6 +136,14 @@ pub fn apply_magic_function(
             ReplResult::Continue
         }
 
+        Some(&"/doc") => {
+            match parsed_line.get(1) {
+                Some(name) => doc(name.trim_end_matches('(')),
+                None => printerr!("Missing name of function to document", ""),
+            };
+            ReplResult::Continue
+        }
+
         Some(&"/clear") => {
             println!("Clearing stored values and macros");
             *index = 0;
@@ -111,6 +170,51 @@ pub fn apply_magic_function(
             ReplResult::Continue
         }
 
+        Some(&"/load") => {
+            match (parsed_line.get(1), parsed_line.get(2)) {
+                (Some(name), Some(path)) => match std::fs::read_to_string(path) {
+                    Ok(raw) => match serde_json::from_str::<Value>(&raw) {
+                        Ok(value) => {
+                            println!("Loaded {path} as {name}");
+                            inputs.push(name.to_string());
+                            data.push(value);
+                        }
+                        Err(e) => printerr!(format!("Failed to parse {path}:"), e),
+                    },
+                    Err(e) => printerr!(format!("Failed to read {path}:"), e),
+                },
+                _ => printerr!("Usage: /load <name> <path>", ""),
+            };
+
+            ReplResult::Continue
+        }
+
+        Some(&"/save") => {
+            match parsed_line.get(1) {
+                Some(path) => match save_session(path, data, inputs, *index, macro_defs, history) {
+                    Ok(()) => println!("Saved session to {path}"),
+                    Err(e) => printerr!(format!("Failed to save session to {path}:"), e),
+                },
+                None => printerr!("Missing path to save session to", ""),
+            };
+
+            ReplResult::Continue
+        }
+
+        Some(&"/restore") => {
+            match parsed_line.get(1) {
+                Some(path) => {
+                    match restore_session(path, data, inputs, index, macro_defs, history) {
+                        Ok(()) => println!("Restored session from {path}"),
+                        Err(e) => printerr!(format!("Failed to restore session from {path}:"), e),
+                    }
+                }
+                None => printerr!("Missing path to restore session from", ""),
+            };
+
+            ReplResult::Continue
+        }
+
         Some(&"/macros") => {
             if macro_defs.is_empty() {
                 println!("No macros stored");
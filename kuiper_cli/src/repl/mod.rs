@@ -2,6 +2,7 @@ mod cmd_helper;
 mod io;
 mod macros;
 mod magic;
+mod session;
 
 use std::collections::HashMap;
 use std::time::Instant;
@@ -36,7 +37,7 @@ pub fn repl(verbose_log: bool) {
     let mut history_path = dirs::home_dir().unwrap();
     history_path.push(".kuiper_history");
 
-    let macro_pattern = Regex::new(r"#.*?;").unwrap();
+    let macro_pattern = Regex::new(r"(?s)#.*?;").unwrap();
     let mut macro_defs = HashMap::new();
 
     let _ = readlines.load_history(&history_path);
@@ -52,12 +53,14 @@ pub fn repl(verbose_log: bool) {
 
         match line {
             Ok(mut expression) => {
-                expression = expression.trim().to_string();
+                // Remove line continuations, lines are kept to make error messages readable.
+                expression = expression.replace("\\\n", "\n").trim().to_string();
                 if expression.is_empty() {
                     continue;
                 }
 
                 let _ = readlines.add_history_entry(expression.as_str());
+                let entered = expression.clone();
 
                 if expression.starts_with('/')
                     && !(expression.starts_with("//") || expression.starts_with("/*"))
@@ -68,6 +71,7 @@ pub fn repl(verbose_log: bool) {
                         &mut inputs,
                         &mut index,
                         &mut macro_defs,
+                        readlines.history_mut(),
                     ) {
                         magic::ReplResult::Continue => {
                             println!();
@@ -100,7 +104,8 @@ pub fn repl(verbose_log: bool) {
                 let formatted_macro_defs = macro_defs
                     .values()
                     .fold("".to_string(), |acc, e| format!("{e} {acc}"));
-                expression = format!("{formatted_macro_defs}{expression}");
+                let user_expression = expression;
+                expression = format!("{formatted_macro_defs}{user_expression}");
 
                 let chunk_id = format!("out{index}");
                 let compile_start = Instant::now();
@@ -120,9 +125,29 @@ pub fn repl(verbose_log: bool) {
                     Err(e) => {
                         print_compile_error(&expression, &e);
                         println!();
+
+                        // Highlight the error if the user edits the same expression again.
+                        // Skip this if the error is in a macro, or the input had macro definitions.
+                        let prefix_len = formatted_macro_defs.len();
+                        if let Some(helper) = readlines.helper_mut() {
+                            match e.span() {
+                                Some(span)
+                                    if span.start >= prefix_len && user_expression == entered =>
+                                {
+                                    helper.set_error(
+                                        &entered,
+                                        span.start - prefix_len..span.end - prefix_len,
+                                    )
+                                }
+                                _ => helper.clear_error(),
+                            }
+                        }
                         continue;
                     }
                 };
+                if let Some(helper) = readlines.helper_mut() {
+                    helper.clear_error();
+                }
 
                 let run_start = Instant::now();
                 let res = expr.run(data.iter());
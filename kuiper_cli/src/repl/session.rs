@@ -0,0 +1,85 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use rustyline::history::{DefaultHistory, History};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::KuiperCliError;
+
+use super::macros::Macro;
+
+/// A REPL session, stored as JSON so that it can be restored later.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Session {
+    /// Index of the next output
+    index: usize,
+    /// Names of stored values, in the same order as `data`
+    inputs: Vec<String>,
+    /// Stored values
+    data: Vec<Value>,
+    /// Macro definitions, keyed by macro name
+    macros: BTreeMap<String, String>,
+    /// Command history, oldest first
+    history: Vec<String>,
+}
+
+/// Save the current state of the REPL to a session file.
+pub fn save_session(
+    path: &str,
+    data: &[Value],
+    inputs: &[String],
+    index: usize,
+    macro_defs: &HashMap<String, Macro>,
+    history: &DefaultHistory,
+) -> Result<(), KuiperCliError> {
+    let session = Session {
+        index,
+        inputs: inputs.to_vec(),
+        data: data.to_vec(),
+        macros: macro_defs
+            .iter()
+            .map(|(name, mac)| (name.clone(), mac.def.clone()))
+            .collect(),
+        history: history.iter().cloned().collect(),
+    };
+
+    fs::write(path, serde_json::to_string_pretty(&session)?)?;
+    Ok(())
+}
+
+/// Restore a session from file, replacing the current state of the REPL.
+pub fn restore_session(
+    path: &str,
+    data: &mut Vec<Value>,
+    inputs: &mut Vec<String>,
+    index: &mut usize,
+    macro_defs: &mut HashMap<String, Macro>,
+    history: &mut DefaultHistory,
+) -> Result<(), KuiperCliError> {
+    let session: Session = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+    if session.inputs.len() != session.data.len() {
+        Err("Invalid session file, number of inputs and values do not match")?;
+    }
+
+    *index = session.index;
+    *inputs = session.inputs;
+    *data = session.data;
+    *macro_defs = session
+        .macros
+        .into_iter()
+        .map(|(name, def)| (name.clone(), Macro { def, name }))
+        .collect();
+
+    history
+        .clear()
+        .map_err(|e| KuiperCliError::ErrorMessage(e.to_string()))?;
+    for line in session.history {
+        history
+            .add_owned(line)
+            .map_err(|e| KuiperCliError::ErrorMessage(e.to_string()))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,355 @@
+use std::io::{BufRead, BufReader, Read, Take, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use kuiper_lang::{ExpressionType, TransformError};
+use serde_json::{json, Value};
+
+use crate::errors::KuiperCliError;
+
+/// Largest request body the server accepts, in bytes.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Largest request line and headers the server accepts, in bytes.
+const MAX_HEADER_BYTES: u64 = 64 * 1024;
+
+/// Largest number of headers the server accepts in a single request.
+const MAX_HEADERS: usize = 100;
+
+/// How long to wait for a client to send its request before giving up.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Configuration for `kuiper serve`.
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    /// Address to listen on, like `127.0.0.1:8080`
+    pub address: String,
+    /// Maximum number of requests handled at the same time. Requests beyond this
+    /// are rejected with `503 Service Unavailable`.
+    pub max_concurrency: usize,
+    /// Maximum number of operations per request, or 0 for no limit
+    pub max_operations: i64,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> std::io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        reason_phrase(response.status),
+        body.len(),
+    )?;
+    stream.flush()
+}
+
+/// Read a single line of the request head, failing if the header limit is reached first.
+fn read_header_line(reader: &mut Take<impl BufRead>, line: &mut String) -> Result<(), Response> {
+    line.clear();
+    reader
+        .read_line(line)
+        .map_err(|_| Response::error(400, "Failed to read request"))?;
+    if !line.ends_with('\n') {
+        if reader.limit() > 0 {
+            return Err(Response::error(400, "Request ended before the headers"));
+        }
+        return Err(Response::error(
+            431,
+            format!("Request headers are larger than the limit of {MAX_HEADER_BYTES} bytes"),
+        ));
+    }
+    Ok(())
+}
+
+/// Read a request from the stream, returning its method and body.
+fn read_request(stream: impl Read) -> Result<(String, Vec<u8>), Response> {
+    let mut reader = BufReader::new(stream).take(MAX_HEADER_BYTES);
+    let mut line = String::new();
+    read_header_line(&mut reader, &mut line)?;
+    let method = line
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+
+    let mut content_length = None;
+    let mut header_count = 0;
+    loop {
+        read_header_line(&mut reader, &mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        header_count += 1;
+        if header_count > MAX_HEADERS {
+            return Err(Response::error(
+                431,
+                format!("Requests may have at most {MAX_HEADERS} headers"),
+            ));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| Response::error(400, "Invalid Content-Length"))?,
+                );
+            }
+        }
+    }
+
+    if method != "POST" {
+        return Ok((method, Vec::new()));
+    }
+    let Some(content_length) = content_length else {
+        return Err(Response::error(
+            411,
+            "Requests must have a Content-Length header",
+        ));
+    };
+    if content_length > MAX_BODY_SIZE {
+        return Err(Response::error(
+            413,
+            format!("Request body is larger than the limit of {MAX_BODY_SIZE} bytes"),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.set_limit(content_length as u64);
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| Response::error(400, "Failed to read request"))?;
+    Ok((method, body))
+}
+
+fn handle_request(
+    stream: &TcpStream,
+    expression: &ExpressionType,
+    max_operations: i64,
+) -> Response {
+    let (method, body) = match read_request(stream) {
+        Ok(r) => r,
+        Err(response) => return response,
+    };
+    if method != "POST" {
+        return Response::error(405, "Only POST requests are supported");
+    }
+    let input: Value = match serde_json::from_slice(&body) {
+        Ok(input) => input,
+        Err(e) => return Response::error(400, format!("Request body is not valid JSON: {e}")),
+    };
+    let limit = if max_operations > 0 {
+        max_operations
+    } else {
+        -1
+    };
+    match expression.run_limited([&input], limit) {
+        Ok(res) => Response::ok(res.into_owned()),
+        Err(TransformError::OperationLimitExceeded) => Response::error(
+            422,
+            format!("Expression exceeded the limit of {max_operations} operations"),
+        ),
+        Err(e) => Response::error(422, e.to_string()),
+    }
+}
+
+/// Run an HTTP server that transforms the JSON body of each POST request with `expression`,
+/// and responds with the result. Errors are returned as a JSON object with an `error` field.
+///
+/// This runs until the process is stopped.
+pub fn serve(expression: ExpressionType, config: &ServeConfig) -> Result<(), KuiperCliError> {
+    let listener = TcpListener::bind(&config.address)?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+    accept_connections(listener, expression, config);
+    Ok(())
+}
+
+fn accept_connections(listener: TcpListener, expression: ExpressionType, config: &ServeConfig) {
+    let expression = Arc::new(expression);
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("\x1b[91mError accepting connection:\x1b[0m {e}");
+                continue;
+            }
+        };
+        if active.fetch_add(1, Ordering::SeqCst) >= config.max_concurrency {
+            active.fetch_sub(1, Ordering::SeqCst);
+            let response = Response::error(503, "Too many concurrent requests");
+            let _ = write_response(&mut stream, &response);
+            continue;
+        }
+
+        let expression = expression.clone();
+        let active = active.clone();
+        let max_operations = config.max_operations;
+        std::thread::spawn(move || {
+            let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+            let response = handle_request(&stream, &expression, max_operations);
+            if let Err(e) = write_response(&mut stream, &response) {
+                eprintln!("\x1b[91mError writing response:\x1b[0m {e}");
+            }
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpListener, TcpStream};
+
+    use kuiper_lang::compile_expression;
+    use serde_json::{json, Value};
+
+    use super::{accept_connections, read_request, ServeConfig, MAX_HEADERS, MAX_HEADER_BYTES};
+
+    fn start_server(expression: &str, max_concurrency: usize, max_operations: i64) -> SocketAddr {
+        let expression = compile_expression(expression, &["input"]).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = ServeConfig {
+            address: addr.to_string(),
+            max_concurrency,
+            max_operations,
+        };
+        std::thread::spawn(move || accept_connections(listener, expression, &config));
+        addr
+    }
+
+    fn send(addr: SocketAddr, request: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn post(addr: SocketAddr, body: &str) -> (u16, Value) {
+        send(
+            addr,
+            &format!(
+                "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ),
+        )
+    }
+
+    #[test]
+    fn test_serve_responses() {
+        let addr = start_server("input.map(x => x * 2)", 4, 0);
+
+        assert_eq!((200, json!([2, 4, 6])), post(addr, "[1, 2, 3]"));
+
+        let (status, body) = post(addr, "[1, 2");
+        assert_eq!(400, status);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Request body is not valid JSON"));
+
+        let (status, _) = send(addr, "GET / HTTP/1.1\r\n\r\n");
+        assert_eq!(405, status);
+
+        let (status, _) = send(addr, "POST / HTTP/1.1\r\n\r\n");
+        assert_eq!(411, status);
+
+        let (status, _) = send(
+            addr,
+            "POST / HTTP/1.1\r\nContent-Length: 1000000000\r\n\r\n",
+        );
+        assert_eq!(413, status);
+    }
+
+    #[test]
+    fn test_serve_operation_limit() {
+        let addr = start_server("input.map(x => x * 2)", 4, 5);
+
+        assert_eq!((200, json!([2])), post(addr, "[1]"));
+        assert_eq!(
+            (
+                422,
+                json!({ "error": "Expression exceeded the limit of 5 operations" })
+            ),
+            post(addr, "[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]")
+        );
+    }
+
+    #[test]
+    fn test_serve_max_concurrency() {
+        let addr = start_server("input", 1, 0);
+
+        // This connection never sends a request, so it holds the only slot until it is closed.
+        let idle = TcpStream::connect(addr).unwrap();
+        // The server rejects the connection without reading the request, so send nothing.
+        assert_eq!(
+            (503, json!({ "error": "Too many concurrent requests" })),
+            send(addr, "")
+        );
+        drop(idle);
+    }
+
+    #[test]
+    fn test_header_limits() {
+        let long_header = format!(
+            "POST / HTTP/1.1\r\nX-Long: {}\r\n\r\n",
+            "a".repeat(MAX_HEADER_BYTES as usize)
+        );
+        let err = read_request(long_header.as_bytes()).unwrap_err();
+        assert_eq!(431, err.status);
+
+        let many_headers = format!(
+            "POST / HTTP/1.1\r\n{}\r\n",
+            "X-Header: a\r\n".repeat(MAX_HEADERS + 1)
+        );
+        let err = read_request(many_headers.as_bytes()).unwrap_err();
+        assert_eq!(431, err.status);
+
+        let err = read_request("POST / HTTP/1.1\r\nContent-Le".as_bytes()).unwrap_err();
+        assert_eq!(400, err.status);
+
+        let (method, body) =
+            read_request("POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\ntrue".as_bytes()).unwrap();
+        assert_eq!("POST", method);
+        assert_eq!(b"true", body.as_slice());
+    }
+}
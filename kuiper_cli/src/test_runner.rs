@@ -0,0 +1,370 @@
+use std::fs::{self, read_to_string};
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use kuiper_lang::compile_expression;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::errors::KuiperCliError;
+
+/// A single run of a test expression.
+#[derive(Debug, Deserialize)]
+struct TestRunConfig {
+    /// List of input values for this test run
+    inputs: Vec<Value>,
+    /// The expected output
+    expected: Option<Value>,
+    /// Expected error message, or part of it. Mutually exclusive with `expected`
+    expected_error: Option<String>,
+}
+
+/// Test configuration, given as a JSON comment at the start of the expression file.
+#[derive(Debug, Deserialize, Default)]
+struct TestCaseConfig {
+    /// List of input variable names
+    #[serde(default)]
+    inputs: Vec<String>,
+    /// List of input/output pairs to test with
+    #[serde(default)]
+    cases: Vec<TestRunConfig>,
+}
+
+/// Summary of a test run.
+#[derive(Debug, Default)]
+pub struct TestReport {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+impl TestReport {
+    pub fn success(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Find all `.kp` files in the given path. If the path is a file, it is returned as is.
+fn discover_test_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), KuiperCliError> {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            discover_test_files(&entry, files)?;
+        } else if entry.extension().is_some_and(|ext| ext == "kp") {
+            files.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract the test config from the leading comment of the expression, if any.
+fn parse_config(expression: &str) -> Result<TestCaseConfig, KuiperCliError> {
+    let trimmed = expression.trim_start();
+
+    let raw_config = if let Some(rest) = trimmed.strip_prefix("/*") {
+        match rest.find("*/") {
+            Some(end) => &rest[..end],
+            None => return Err("Unterminated comment at start of test file")?,
+        }
+    } else if let Some(rest) = trimmed.strip_prefix("//") {
+        rest.lines().next().unwrap_or_default()
+    } else {
+        return Ok(TestCaseConfig::default());
+    };
+
+    Ok(serde_json::from_str(raw_config)?)
+}
+
+/// Path of the snapshot file belonging to a test file, `foo.kp` is stored in `foo.snap.json`.
+fn snapshot_path(path: &Path) -> PathBuf {
+    path.with_extension("snap.json")
+}
+
+/// Produce a line based diff between two strings, using the longest common subsequence.
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut res = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            res.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            res.push(format!("- {}", old[i]).red().to_string());
+            i += 1;
+        } else {
+            res.push(format!("+ {}", new[j]).green().to_string());
+            j += 1;
+        }
+    }
+
+    res.join("\n")
+}
+
+/// Run a single test file, returning a list of failure messages.
+///
+/// Cases without `expected` or `expected_error` are compared against the snapshot file
+/// stored next to the test file. If `update_snapshots` is set, the snapshot file is
+/// overwritten with the current outputs instead.
+fn run_test_file(path: &Path, update_snapshots: bool) -> Result<Vec<String>, KuiperCliError> {
+    let raw_expression = read_to_string(path)?;
+    let config = parse_config(&raw_expression)?;
+
+    let inputs: Vec<&str> = config.inputs.iter().map(String::as_str).collect();
+    let expression = compile_expression(&raw_expression, &inputs)?;
+
+    let mut failures = Vec::new();
+    let mut outputs = Vec::with_capacity(config.cases.len());
+    let mut snapshot_cases = Vec::new();
+    for (i, case) in config.cases.iter().enumerate() {
+        let result = expression.run(case.inputs.iter());
+
+        match (result, &case.expected, &case.expected_error) {
+            (_, Some(_), Some(_)) => failures.push(format!(
+                "Case {i}: only one of expected and expected_error can be set"
+            )),
+            (Ok(result), Some(expected), None) => {
+                if result.as_ref() != expected {
+                    failures.push(format!(
+                        "Case {i}: expected {expected}, got {}",
+                        result.as_ref()
+                    ));
+                }
+                outputs.push(result.into_owned());
+                continue;
+            }
+            (Ok(result), None, Some(expected_error)) => failures.push(format!(
+                "Case {i}: expected error \"{expected_error}\", got {}",
+                result.as_ref()
+            )),
+            (Ok(result), None, None) => {
+                snapshot_cases.push(i);
+                outputs.push(result.into_owned());
+                continue;
+            }
+            (Err(e), None, Some(expected_error)) => {
+                if !e.to_string().contains(expected_error.as_str()) {
+                    failures.push(format!(
+                        "Case {i}: expected error \"{expected_error}\", got \"{e}\""
+                    ));
+                }
+            }
+            (Err(e), _, None) => failures.push(format!("Case {i}: {e}")),
+        }
+        outputs.push(Value::Null);
+    }
+
+    if snapshot_cases.is_empty() {
+        return Ok(failures);
+    }
+
+    let snapshot_path = snapshot_path(path);
+    if update_snapshots {
+        let mut serialized = serde_json::to_string_pretty(&outputs)?;
+        serialized.push('\n');
+        fs::write(&snapshot_path, serialized)?;
+        return Ok(failures);
+    }
+
+    if !snapshot_path.exists() {
+        failures.push(format!(
+            "Missing snapshot file {}, run with --update-snapshots to create it",
+            snapshot_path.display()
+        ));
+        return Ok(failures);
+    }
+    let snapshot: Vec<Value> = serde_json::from_str(&read_to_string(&snapshot_path)?)?;
+
+    for i in snapshot_cases {
+        let expected = snapshot.get(i).unwrap_or(&Value::Null);
+        if expected != &outputs[i] {
+            failures.push(format!(
+                "Case {i}: output does not match snapshot\n{}",
+                diff_lines(
+                    &serde_json::to_string_pretty(expected)?,
+                    &serde_json::to_string_pretty(&outputs[i])?
+                )
+            ));
+        }
+    }
+
+    Ok(failures)
+}
+
+/// Discover and run all test files in `path`, printing a pass/fail report.
+pub fn run_tests(path: &Path, update_snapshots: bool) -> Result<TestReport, KuiperCliError> {
+    let mut files = Vec::new();
+    discover_test_files(path, &mut files)?;
+
+    let mut report = TestReport::default();
+
+    for file in files {
+        let failures = match run_test_file(&file, update_snapshots) {
+            Ok(failures) => failures,
+            Err(e) => vec![e.to_string()],
+        };
+
+        if failures.is_empty() {
+            println!("{} {}", "PASS".green().bold(), file.display());
+            report.passed += 1;
+        } else {
+            println!("{} {}", "FAIL".red().bold(), file.display());
+            for failure in failures {
+                for line in failure.lines() {
+                    println!("    {line}");
+                }
+            }
+            report.failed += 1;
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed", report.passed, report.failed);
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::{diff_lines, parse_config, run_test_file, snapshot_path};
+
+    /// Create an empty directory for a test, removing any leftovers from earlier runs.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("kuiper_test_runner_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config(
+            r#"/* {"inputs": ["a"], "cases": [{"inputs": [1], "expected": 2}]} */
+            a + 1"#,
+        )
+        .unwrap();
+        assert_eq!(config.inputs, vec!["a"]);
+        assert_eq!(config.cases.len(), 1);
+        assert_eq!(config.cases[0].inputs, vec![json!(1)]);
+        assert_eq!(config.cases[0].expected, Some(json!(2)));
+        assert_eq!(config.cases[0].expected_error, None);
+
+        let config = parse_config("  // {\"inputs\": [\"b\"]}\nb").unwrap();
+        assert_eq!(config.inputs, vec!["b"]);
+        assert!(config.cases.is_empty());
+
+        let config = parse_config("1 + 1").unwrap();
+        assert!(config.inputs.is_empty());
+
+        assert!(parse_config("/* {\"inputs\": []} 1 + 1").is_err());
+        assert!(parse_config("/* not json */ 1 + 1").is_err());
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        colored::control::set_override(false);
+        assert_eq!(diff_lines("a\nb\nc", "a\nb\nc"), "  a\n  b\n  c");
+        assert_eq!(diff_lines("a\nb\nc", "a\nx\nc"), "  a\n- b\n+ x\n  c");
+        assert_eq!(diff_lines("a\nc", "a\nb\nc"), "  a\n+ b\n  c");
+        assert_eq!(diff_lines("a\nb", "b"), "- a\n  b");
+    }
+
+    #[test]
+    fn test_expected_output_and_error() {
+        let dir = test_dir("expected");
+        let path = dir.join("add.kp");
+        fs::write(
+            &path,
+            r#"/* {"inputs": ["input"], "cases": [
+                {"inputs": [1], "expected": 2},
+                {"inputs": [1], "expected": 3},
+                {"inputs": ["a"], "expected_error": "not applicable"},
+                {"inputs": [1], "expected_error": "not applicable"}
+            ]} */
+            input + 1"#,
+        )
+        .unwrap();
+
+        let failures = run_test_file(&path, false).unwrap();
+        assert_eq!(
+            failures,
+            vec![
+                "Case 1: expected 3, got 2".to_owned(),
+                "Case 3: expected error \"not applicable\", got 2".to_owned(),
+            ]
+        );
+        // No cases are compared against a snapshot, so none is written.
+        assert!(!snapshot_path(&path).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_snapshots() {
+        colored::control::set_override(false);
+        let dir = test_dir("snapshots");
+        let path = dir.join("snap.kp");
+        let write_test = |input: i64| {
+            fs::write(
+                &path,
+                format!(
+                    r#"/* {{"inputs": ["input"], "cases": [{{"inputs": [{input}]}}]}} */
+                    {{ "a": input, "b": "c" }}"#
+                ),
+            )
+            .unwrap();
+        };
+        write_test(1);
+
+        let failures = run_test_file(&path, false).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("Missing snapshot file"));
+
+        // Updating creates the missing snapshot.
+        assert!(run_test_file(&path, true).unwrap().is_empty());
+        let snapshot: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(snapshot_path(&path)).unwrap()).unwrap();
+        assert_eq!(snapshot, json!([{ "a": 1, "b": "c" }]));
+        assert!(run_test_file(&path, false).unwrap().is_empty());
+
+        write_test(2);
+        let failures = run_test_file(&path, false).unwrap();
+        assert_eq!(
+            failures,
+            vec![
+                "Case 0: output does not match snapshot\n  {\n-   \"a\": 1,\n+   \"a\": 2,\n    \"b\": \"c\"\n  }"
+                    .to_owned()
+            ]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 #! /bin/env python
+import json
 import sys
 from pathlib import Path
 from typing import TextIO, Any
@@ -6,6 +7,11 @@ from typing import TextIO, Any
 import yaml
 
 
+def rust_string(value: str) -> str:
+    """Format a string as a Rust string literal."""
+    return json.dumps(value, ensure_ascii=False)
+
+
 def generate_docs(functions: list[dict[str, Any]], file: TextIO):
     file.write(
         """---
@@ -53,71 +59,91 @@ def generate_warning_header(file: TextIO, comment_tag="//"):
     )
 
 
-def generate_repl_list(functions: list[dict[str, Any]], file: TextIO):
+def generate_js_list(functions: list[dict[str, Any]], file: TextIO):
     generate_warning_header(file)
 
     file.write(
-        """use lazy_static::lazy_static;
-use std::collections::HashMap;
-
-"""
+        """export type KuiperInput = {
+    label: string,
+    description: string,
+};\n\n"""
     )
 
-    file.write(f"pub const BUILT_INS: [&str; {len(functions)}] = [\n")
+    file.write("export const builtIns: KuiperInput[] = [\n")
+
     for function in functions:
-        file.write(f'    "{function["name"].strip()}(",\n')
+        short_desc = function["description"].split("\n")[0].strip()
+        file.write(
+            f'    {{ label: "{function["name"].strip()}", description: "{function["signature"].strip()}: {short_desc}" }},\n'
+        )
+
     file.write("];\n")
 
+
+def generate_rust_docs(functions: list[dict[str, Any]], file: TextIO):
+    generate_warning_header(file)
+
     file.write(
-        """
+        """use serde::Serialize;
+
+/// An example of how to use a built-in function.
+#[derive(Serialize, Debug)]
+pub struct FunctionExample {
+    /// Example expression.
+    pub input: &'static str,
+    /// Result of the expression, if it is deterministic.
+    pub output: Option<&'static str>,
+}
+
+/// Documentation for a built-in function.
+#[derive(Serialize, Debug)]
 pub struct FunctionDef {
+    /// Name of the function.
+    pub name: &'static str,
+    /// Signature of the function, like `pow(x, y)`.
     pub signature: &'static str,
+    /// Description of the function, in markdown.
     pub description: &'static str,
+    /// Examples of how to use the function.
+    pub examples: &'static [FunctionExample],
 }
 
-lazy_static! {
-    pub static ref HELP: HashMap<&'static str, FunctionDef> = HashMap::from([
 """
     )
 
+    file.write("/// All built-in functions, sorted by name.\n")
+    file.write("pub static FUNCTIONS: &[FunctionDef] = &[")
     for function in functions:
         file.write(
             f"""
-        (
-            "{function["name"]}",
-            FunctionDef {{
-                signature: "{function["signature"].strip("`")}",
-                description: "{function["description"].strip()}",
-            }}
-        ),"""
+    FunctionDef {{
+        name: {rust_string(function["name"].strip())},
+        signature: {rust_string(function["signature"].strip("`"))},
+        description: {rust_string(function["description"].strip())},
+        examples: &["""
         )
-
-    file.write(
-        """
-    ]);
-}\n"""
-    )
-
-
-def generate_js_list(functions: list[dict[str, Any]], file: TextIO):
-    generate_warning_header(file)
-
-    file.write(
-        """export type KuiperInput = {
-    label: string,
-    description: string,
-};\n\n"""
-    )
-
-    file.write("export const builtIns: KuiperInput[] = [\n")
-
-    for function in functions:
-        short_desc = function["description"].split("\n")[0].strip()
+        for example in function["examples"]:
+            if not isinstance(example, dict):
+                continue
+            output = (
+                f"Some({rust_string(str(example['output']).strip())})"
+                if "output" in example
+                else "None"
+            )
+            file.write(
+                f"""
+            FunctionExample {{
+                input: {rust_string(example["input"].strip())},
+                output: {output},
+            }},"""
+            )
         file.write(
-            f'    {{ label: "{function["name"].strip()}", description: "{function["signature"].strip()}: {short_desc}" }},\n'
+            """
+        ],
+    },"""
         )
 
-    file.write("];\n")
+    file.write("\n];\n")
 
 
 def find_function_defs(file: TextIO) -> set[str]:
@@ -184,8 +210,8 @@ def main():
         project_base / "kuiper_documentation" / "built_in_functions.md", "w"
     ) as f:
         generate_docs(functions, f)
-    with open(project_base / "kuiper_cli" / "src" / "builtins.rs", "w") as f:
-        generate_repl_list(functions, f)
+    with open(project_base / "kuiper_lang" / "src" / "docs.rs", "w") as f:
+        generate_rust_docs(functions, f)
     with open(project_base / "kuiper_lezer" / "src" / "builtins.ts", "w") as f:
         generate_js_list(functions, f)
 
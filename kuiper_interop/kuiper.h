@@ -10,14 +10,37 @@
 // This is allocated by the `compile_expression` function and should be
 // freed by the `destroy_expression` function.
 // Internally this is a complex rust type that is not exposed here.
+// Expressions are immutable and reference counted, and may be used from several threads at once.
 typedef struct ExpressionType ExpressionType;
 
+// The category of an error returned by kuiper functions.
+typedef enum KuiperErrorKind {
+    KUIPER_ERROR_NONE = 0,
+    // Compile errors
+    KUIPER_ERROR_PARSER = 1,
+    KUIPER_ERROR_BUILD = 2,
+    KUIPER_ERROR_OPTIMIZER = 3,
+    KUIPER_ERROR_TYPE = 4,
+    // Runtime errors
+    KUIPER_ERROR_SOURCE_MISSING = 5,
+    KUIPER_ERROR_INCORRECT_TYPE = 6,
+    KUIPER_ERROR_CONVERSION_FAILED = 7,
+    KUIPER_ERROR_INVALID_OPERATION = 8,
+    KUIPER_ERROR_DIVIDE_BY_ZERO = 9,
+    KUIPER_ERROR_OPERATION_LIMIT_EXCEEDED = 10,
+    // Invalid input from the caller
+    KUIPER_ERROR_INVALID_JSON = 11,
+    KUIPER_ERROR_INVALID_STRING = 12,
+} KuiperErrorKind;
+
 // An error returned by kuiper functions.
+// `start` and `end` are byte offsets into the expression, both are 0 if there is no known range.
 typedef struct KuiperError {
     char *error;
     bool is_error;
     unsigned long start;
     unsigned long end;
+    KuiperErrorKind kind;
 } KuiperError;
 
 // The result of compiling a kuiper expression. Either `error` is set, or `result` is set.
@@ -30,14 +53,36 @@ typedef struct CompileResult {
 CompileResult *compile_expression(const char *expression, const char **inputs, size_t input_count);
 
 // The result of running a kuiper expression. Either `error` is set, or `result` is set.
+// `opcount` is the number of operations performed by a successful run, or 0 if the run failed.
 typedef struct TransformResult {
     KuiperError error;
     char *result;
+    long opcount;
 } TransformResult;
 
 // Run a compiled kuiper expression with the given input data.
 TransformResult *run_expression(const char **data, size_t input_count, ExpressionType *expr);
 
+// Run a compiled kuiper expression with the given input data, failing if it performs more than
+// `max_operation_count` operations. If `max_operation_count` is -1, no limit is enforced.
+TransformResult *run_expression_limited(const char **data, size_t input_count, ExpressionType *expr,
+                                        long max_operation_count);
+
+// The result of running a kuiper expression on a batch of inputs. `results` is an array of length `len`.
+typedef struct BatchTransformResult {
+    TransformResult *results;
+    size_t len;
+} BatchTransformResult;
+
+// Run a compiled kuiper expression once for each of `batch_size` input sets, in a single call.
+// `data` contains the inputs for every run one after another, run `i` uses the next `counts[i]` entries.
+// Failed runs do not affect the others.
+BatchTransformResult *run_expression_batch(const char **data, const size_t *counts, size_t batch_size,
+                                           ExpressionType *expr);
+
+// Destroy a batch transform result allocated by `run_expression_batch`, including all the results it contains.
+void destroy_batch_transform_result(BatchTransformResult *result);
+
 // Free a string allocated by rust.
 void destroy_string(char *data);
 
@@ -52,9 +97,14 @@ void destroy_transform_result(TransformResult *result);
 // This does not check whether `result` is null and may return a null pointer.
 ExpressionType *get_expression_from_compile_result(CompileResult *result);
 
-// Destroy an expression allocated by `compile_expression`.
+// Destroy an expression allocated by `compile_expression`. If the expression has been shared with
+// `clone_expression`, this releases one reference, and the expression is freed once every reference is released.
 void destroy_expression(ExpressionType *expr);
 
+// Create a new reference to an expression, which must be released separately with `destroy_expression`.
+// Use this to share one compiled expression between threads without external locking.
+ExpressionType *clone_expression(ExpressionType *expr);
+
 // Destroy a compile result allocated by `compile_expression`.
 void destroy_compile_result(CompileResult *result);
 
@@ -73,6 +123,23 @@ void config_set_optimizer_operation_limit(CompilerConfig *config, long limit);
 // Set the maximum number of macro expansions for a compiler configuration.
 void config_set_max_macro_expansions(CompilerConfig *config, int limit);
 
+// When to run the type checker.
+typedef enum TypeCheckerMode {
+    // Run the type checker before optimization.
+    TYPE_CHECKER_EARLY = 0,
+    // Run the type checker after optimization.
+    TYPE_CHECKER_LATE = 1,
+    // Disable the type checker. This is the default.
+    TYPE_CHECKER_OFF = 2,
+} TypeCheckerMode;
+
+// Set when to run the type checker for a compiler configuration.
+void config_set_type_checker_mode(CompilerConfig *config, TypeCheckerMode mode);
+
+// Deny a function in a compiler configuration. Expressions calling the function will fail to compile.
+// Returns -1 if `name` is not valid UTF-8.
+int config_deny_function(CompilerConfig *config, const char *name);
+
 // The result of a custom function.
 // If `is_error` is true, then `data` contains an error message.
 // Otherwise, `data` contains the result of the function as a JSON string.
@@ -99,4 +166,14 @@ int config_add_custom_function(CompilerConfig *config, const char *name,
 CompileResult *compile_expression_with_config(const char *expression, const char **inputs, size_t input_count,
                                               CompilerConfig *config);
 
+// Get the documentation for a built-in function as a JSON object with the `name`, `signature`, `description`, and
+// `examples` of the function. Returns null if there is no built-in function with the given name.
+// The returned string must be freed with `destroy_string`.
+char *get_method_docs(const char *name);
+
+// List the documentation for all built-in functions as a JSON array, sorted by name.
+// The returned string must be freed with `destroy_string`.
+char *list_functions();
+
 #endif
+
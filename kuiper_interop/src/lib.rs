@@ -6,12 +6,18 @@ use std::{
 };
 
 use kuiper_lang::{
-    CompileError, DynamicFunctionBuilder, Expression, ExpressionMeta, ExpressionType, Span,
-    TransformError,
+    docs::FUNCTIONS, CompileError, DynamicFunctionBuilder, Expression, ExpressionMeta,
+    ExpressionType, Span, TransformError, TransformErrorKind,
 };
 use serde_json::Value;
 use thiserror::Error;
 
+// Expressions are shared across threads by FFI hosts, so this must stay thread safe.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ExpressionType>();
+};
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct CompileResult {
@@ -19,6 +25,21 @@ pub struct CompileResult {
     pub result: *mut ExpressionType,
 }
 
+impl From<Result<ExpressionType, InteropError>> for CompileResult {
+    fn from(value: Result<ExpressionType, InteropError>) -> Self {
+        match value {
+            Ok(expr) => CompileResult {
+                error: KuiperError::none(),
+                result: Arc::into_raw(Arc::new(expr)) as *mut _,
+            },
+            Err(e) => CompileResult {
+                error: e.into(),
+                result: std::ptr::null_mut(),
+            },
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct KuiperError {
@@ -26,6 +47,64 @@ pub struct KuiperError {
     pub is_error: bool,
     pub start: u64,
     pub end: u64,
+    pub kind: KuiperErrorKind,
+}
+
+impl KuiperError {
+    fn none() -> Self {
+        KuiperError {
+            error: std::ptr::null_mut(),
+            is_error: false,
+            start: 0,
+            end: 0,
+            kind: KuiperErrorKind::None,
+        }
+    }
+}
+
+/// The category of an error returned over FFI.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KuiperErrorKind {
+    None = 0,
+    Parser = 1,
+    Build = 2,
+    Optimizer = 3,
+    Type = 4,
+    SourceMissing = 5,
+    IncorrectType = 6,
+    ConversionFailed = 7,
+    InvalidOperation = 8,
+    DivideByZero = 9,
+    OperationLimitExceeded = 10,
+    InvalidJson = 11,
+    InvalidString = 12,
+}
+
+impl From<&CompileError> for KuiperErrorKind {
+    fn from(value: &CompileError) -> Self {
+        match value {
+            CompileError::Parser(_) => KuiperErrorKind::Parser,
+            CompileError::Build(_) => KuiperErrorKind::Build,
+            CompileError::Optimizer(_) => KuiperErrorKind::Optimizer,
+            CompileError::TypeChecker(_) => KuiperErrorKind::Type,
+        }
+    }
+}
+
+impl From<&TransformError> for KuiperErrorKind {
+    fn from(value: &TransformError) -> Self {
+        match value.kind() {
+            TransformErrorKind::SourceMissing => KuiperErrorKind::SourceMissing,
+            TransformErrorKind::IncorrectType => KuiperErrorKind::IncorrectType,
+            TransformErrorKind::ConversionFailed => KuiperErrorKind::ConversionFailed,
+            TransformErrorKind::InvalidOperation => KuiperErrorKind::InvalidOperation,
+            TransformErrorKind::DivideByZero => KuiperErrorKind::DivideByZero,
+            TransformErrorKind::OperationLimitExceeded => KuiperErrorKind::OperationLimitExceeded,
+            // Kinds added to kuiper_lang later are reported as invalid operations until they get their own kind here.
+            _ => KuiperErrorKind::InvalidOperation,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -76,11 +155,12 @@ pub unsafe extern "C" fn destroy_compile_result(data: *mut CompileResult) {
         unsafe { std::mem::drop(CString::from_raw(data.error.error)) };
     }
     if !data.result.is_null() {
-        unsafe { drop(Box::from_raw(data.result)) };
+        unsafe { drop(Arc::from_raw(data.result)) };
     }
 }
 
-/// Destroy an expression type
+/// Destroy an expression type. If the expression has been shared with `clone_expression`,
+/// this only releases one reference, and the expression is freed once every reference is released.
 ///
 /// # Safety
 ///
@@ -88,7 +168,21 @@ pub unsafe extern "C" fn destroy_compile_result(data: *mut CompileResult) {
 /// `compile_expression` and `get_expression_from_compile_result`.
 #[no_mangle]
 pub unsafe extern "C" fn destroy_expression(data: *mut ExpressionType) {
-    unsafe { drop(Box::from_raw(data)) };
+    unsafe { drop(Arc::from_raw(data)) };
+}
+
+/// Create a new reference to an expression, which must be released separately with
+/// `destroy_expression`. Compiled expressions are immutable and thread safe, so each thread
+/// can hold its own reference without any external locking.
+///
+/// # Safety
+///
+/// `data` must be a valid, non-null rust pointer to an `ExpressionType`, typically produced by
+/// `compile_expression` and `get_expression_from_compile_result`.
+#[no_mangle]
+pub unsafe extern "C" fn clone_expression(data: *mut ExpressionType) -> *mut ExpressionType {
+    unsafe { Arc::increment_strong_count(data) };
+    data
 }
 
 /// Destroy a `CompileResult` and return the `ExpressionType` it contains.
@@ -111,25 +205,18 @@ pub unsafe extern "C" fn get_expression_from_compile_result(
 
 impl From<InteropError> for KuiperError {
     fn from(value: InteropError) -> Self {
-        match value {
-            InteropError::Compile(c) => KuiperError {
-                is_error: true,
-                error: CString::new(c.to_string()).unwrap().into_raw(),
-                start: c.span().map(|s| s.start as u64).unwrap_or_default(),
-                end: c.span().map(|s| s.end as u64).unwrap_or_default(),
-            },
-            InteropError::Execute(c) => KuiperError {
-                is_error: true,
-                error: CString::new(c.to_string()).unwrap().into_raw(),
-                start: c.span().map(|s| s.start as u64).unwrap_or_default(),
-                end: c.span().map(|s| s.end as u64).unwrap_or_default(),
-            },
-            c => KuiperError {
-                is_error: true,
-                error: CString::new(c.to_string()).unwrap().into_raw(),
-                start: 0,
-                end: 0,
-            },
+        let (span, kind) = match &value {
+            InteropError::Compile(c) => (c.span(), c.into()),
+            InteropError::Execute(c) => (c.span(), c.into()),
+            InteropError::Serde(_) => (None, KuiperErrorKind::InvalidJson),
+            InteropError::Utf8(_) => (None, KuiperErrorKind::InvalidString),
+        };
+        KuiperError {
+            is_error: true,
+            error: CString::new(value.to_string()).unwrap().into_raw(),
+            start: span.as_ref().map(|s| s.start as u64).unwrap_or_default(),
+            end: span.as_ref().map(|s| s.end as u64).unwrap_or_default(),
+            kind,
         }
     }
 }
@@ -148,27 +235,8 @@ pub unsafe extern "C" fn compile_expression(
     inputs: *const *const c_char,
     len: usize,
 ) -> *mut CompileResult {
-    let res = match compile_expression_internal(
-        data,
-        inputs,
-        len,
-        &kuiper_lang::CompilerConfig::default(),
-    ) {
-        Ok(expr) => CompileResult {
-            error: KuiperError {
-                error: std::ptr::null_mut(),
-                is_error: false,
-                start: 0,
-                end: 0,
-            },
-            result: Box::into_raw(Box::new(expr)),
-        },
-        Err(e) => CompileResult {
-            error: e.into(),
-            result: std::ptr::null_mut(),
-        },
-    };
-    Box::into_raw(Box::new(res))
+    let r = compile_expression_internal(data, inputs, len, &kuiper_lang::CompilerConfig::default());
+    Box::into_raw(Box::new(r.into()))
 }
 
 #[derive(Default)]
@@ -234,6 +302,67 @@ pub unsafe extern "C" fn config_set_max_macro_expansions(config: *mut CompilerCo
     );
 }
 
+/// When to run the type checker, mirrors `kuiper_lang::TypeCheckerMode`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeCheckerMode {
+    Early = 0,
+    Late = 1,
+    Off = 2,
+}
+
+impl From<TypeCheckerMode> for kuiper_lang::TypeCheckerMode {
+    fn from(value: TypeCheckerMode) -> Self {
+        match value {
+            TypeCheckerMode::Early => kuiper_lang::TypeCheckerMode::Early,
+            TypeCheckerMode::Late => kuiper_lang::TypeCheckerMode::Late,
+            TypeCheckerMode::Off => kuiper_lang::TypeCheckerMode::Off,
+        }
+    }
+}
+
+#[no_mangle]
+/// Set when to run the type checker for a compiler configuration.
+///
+/// # Safety
+///
+/// `config` must be a valid, non-null pointer to a `CompilerConfig`,
+/// typically obtained from `new_compiler_config`. `mode` must be a valid `TypeCheckerMode`.
+pub unsafe extern "C" fn config_set_type_checker_mode(
+    config: *mut CompilerConfig,
+    mode: TypeCheckerMode,
+) {
+    let config = unsafe { &mut *config };
+    config.inner = Some(
+        config
+            .inner
+            .take()
+            .unwrap_or_default()
+            .type_checker_mode(mode.into()),
+    );
+}
+
+#[no_mangle]
+/// Deny a function in a compiler configuration. Expressions calling the function will fail to compile.
+///
+/// Returns -1 if `name` is not valid UTF-8.
+///
+/// # Safety
+///
+/// `config` must be a valid, non-null pointer to a `CompilerConfig`,
+/// typically obtained from `new_compiler_config`. `name` must be a valid, null terminated string.
+pub unsafe extern "C" fn config_deny_function(
+    config: *mut CompilerConfig,
+    name: *const c_char,
+) -> i32 {
+    let Ok(name) = (unsafe { CStr::from_ptr(name).to_str() }) else {
+        return -1;
+    };
+    let config = unsafe { &mut *config };
+    config.inner = Some(config.inner.take().unwrap_or_default().deny_function(name));
+    0
+}
+
 #[no_mangle]
 /// Add a custom function to a compiler configuration. The `implementation` function will be called
 /// when the custom function is invoked in a kuiper expression. The `implementation` function should
@@ -297,22 +426,7 @@ pub unsafe extern "C" fn compile_expression_with_config(
             compile_expression_internal(data, inputs, len, &kuiper_lang::CompilerConfig::default())
         }
     };
-    let res = match r {
-        Ok(expr) => CompileResult {
-            error: KuiperError {
-                error: std::ptr::null_mut(),
-                is_error: false,
-                start: 0,
-                end: 0,
-            },
-            result: Box::into_raw(Box::new(expr)),
-        },
-        Err(e) => CompileResult {
-            error: e.into(),
-            result: std::ptr::null_mut(),
-        },
-    };
-    Box::into_raw(Box::new(res))
+    Box::into_raw(Box::new(r.into()))
 }
 
 #[repr(C)]
@@ -427,13 +541,32 @@ impl DynamicFunctionBuilder for CustomBuilder {
 pub struct TransformResult {
     pub error: KuiperError,
     pub result: *mut c_char,
+    pub opcount: i64,
+}
+
+impl From<Result<(String, i64), InteropError>> for TransformResult {
+    fn from(value: Result<(String, i64), InteropError>) -> Self {
+        match value {
+            Ok((res, opcount)) => TransformResult {
+                error: KuiperError::none(),
+                result: CString::new(res).unwrap().into_raw(),
+                opcount,
+            },
+            Err(e) => TransformResult {
+                error: e.into(),
+                result: std::ptr::null_mut(),
+                opcount: 0,
+            },
+        }
+    }
 }
 
 unsafe fn run_expression_internal(
     data: *const *const c_char,
     len: usize,
     expression: *const ExpressionType,
-) -> Result<String, InteropError> {
+    max_operation_count: i64,
+) -> Result<(String, i64), InteropError> {
     let data = if len > 0 {
         let data_raw = unsafe { &*slice_from_raw_parts(data, len) };
         data_raw
@@ -449,8 +582,12 @@ unsafe fn run_expression_internal(
             .into_iter()
             .map(serde_json::from_str)
             .collect::<Result<Vec<Value>, _>>()?;
-        let res = (*expression).run(&data_json)?;
-        Ok(res.to_string())
+        let (res, opcount) = (*expression)
+            .builder()
+            .with_values(&data_json)
+            .max_operation_count(max_operation_count)
+            .run_get_opcount()?;
+        Ok((res.to_string(), opcount))
     }
 }
 
@@ -512,20 +649,132 @@ pub unsafe extern "C" fn run_expression(
     len: usize,
     expression: *const ExpressionType,
 ) -> *mut TransformResult {
-    let res = match run_expression_internal(data, len, expression) {
-        Ok(expr) => TransformResult {
-            error: KuiperError {
-                error: std::ptr::null_mut(),
-                is_error: false,
-                start: 0,
-                end: 0,
-            },
-            result: CString::new(expr).unwrap().into_raw(),
-        },
-        Err(e) => TransformResult {
-            error: e.into(),
-            result: std::ptr::null_mut(),
-        },
-    };
+    let res = run_expression_internal(data, len, expression, -1).into();
+    Box::into_raw(Box::new(res))
+}
+
+/// Run a kuiper expression with a list of inputs, failing if it performs more than
+/// `max_operation_count` operations. If `max_operation_count` is -1, no limit is enforced.
+///
+/// Returns a result struct in which exactly one of `error` or `result` is non-null.
+///
+/// # Safety
+///
+/// `data` must be an array of valid, utf8-encoded, null-terminated strings
+/// with length `len`. If `len` is 0, `data` may be null.
+///
+/// `expression` must be a valid pointer to an `ExpressionType`, typically obtained from
+/// `compile_expression` and `get_expression_from_compile_result`
+#[no_mangle]
+pub unsafe extern "C" fn run_expression_limited(
+    data: *const *const c_char,
+    len: usize,
+    expression: *const ExpressionType,
+    max_operation_count: i64,
+) -> *mut TransformResult {
+    let res = run_expression_internal(data, len, expression, max_operation_count).into();
     Box::into_raw(Box::new(res))
 }
+
+#[repr(C)]
+pub struct BatchTransformResult {
+    pub results: *mut TransformResult,
+    pub len: usize,
+}
+
+/// Run a kuiper expression once for each of a list of input sets, in a single call.
+///
+/// `data` contains the inputs for every run, one after another. Run `i` uses the next `counts[i]`
+/// entries of `data`. Returns a batch result with one `TransformResult` per run, in the same order.
+/// Failed runs do not affect the others.
+///
+/// # Safety
+///
+/// `data` must be an array of valid, utf8-encoded, null-terminated strings, with length equal to
+/// the sum of `counts`. `counts` must be an array of length `batch_size`. If `batch_size` is 0,
+/// `data` and `counts` may be null.
+///
+/// `expression` must be a valid pointer to an `ExpressionType`, typically obtained from
+/// `compile_expression` and `get_expression_from_compile_result`
+#[no_mangle]
+pub unsafe extern "C" fn run_expression_batch(
+    data: *const *const c_char,
+    counts: *const usize,
+    batch_size: usize,
+    expression: *const ExpressionType,
+) -> *mut BatchTransformResult {
+    let counts = if batch_size > 0 {
+        unsafe { &*slice_from_raw_parts(counts, batch_size) }
+    } else {
+        &[]
+    };
+    let mut offset = 0;
+    let results: Box<[TransformResult]> = counts
+        .iter()
+        .map(|&count| {
+            let inputs = unsafe { data.add(offset) };
+            offset += count;
+            run_expression_internal(inputs, count, expression, -1).into()
+        })
+        .collect();
+
+    let len = results.len();
+    Box::into_raw(Box::new(BatchTransformResult {
+        results: Box::into_raw(results) as *mut TransformResult,
+        len,
+    }))
+}
+
+/// Destroy a batch transform result, including all the results it contains.
+///
+/// # Safety
+///
+/// `data` must be a valid, non-null pointer to a `BatchTransformResult`, typically obtained
+/// from `run_expression_batch`.
+#[no_mangle]
+pub unsafe extern "C" fn destroy_batch_transform_result(data: *mut BatchTransformResult) {
+    let data = unsafe { Box::from_raw(data) };
+    let results =
+        unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(data.results, data.len)) };
+    for res in results.iter() {
+        if !res.error.error.is_null() {
+            unsafe { drop(CString::from_raw(res.error.error)) };
+        }
+        if !res.result.is_null() {
+            unsafe { drop(CString::from_raw(res.result)) };
+        }
+    }
+}
+
+/// Get the documentation for a built-in function as a JSON object with the `name`, `signature`,
+/// `description`, and `examples` of the function.
+///
+/// Returns null if there is no built-in function with the given name. The returned string must
+/// be freed with `destroy_string`.
+///
+/// # Safety
+///
+/// `name` must be a valid, null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn get_method_docs(name: *const c_char) -> *mut c_char {
+    let Ok(name) = (unsafe { CStr::from_ptr(name) }).to_str() else {
+        return std::ptr::null_mut();
+    };
+    match FUNCTIONS.iter().find(|f| f.name == name) {
+        Some(def) => CString::new(serde_json::to_string(def).unwrap())
+            .unwrap()
+            .into_raw(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// List the documentation for all built-in functions as a JSON array, sorted by name.
+/// Each element has the same format as the output of `get_method_docs`.
+///
+/// The returned string must be freed with `destroy_string`.
+#[no_mangle]
+pub extern "C" fn list_functions() -> *mut c_char {
+    CString::new(serde_json::to_string(FUNCTIONS).unwrap())
+        .unwrap()
+        .into_raw()
+}
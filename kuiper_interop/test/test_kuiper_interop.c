@@ -1,5 +1,6 @@
 #include "../kuiper.h"
 #include <malloc.h>
+#include <pthread.h>
 #include <stdio.h>
 #include <string.h>
 
@@ -90,11 +91,307 @@ cleanup:
     return error;
 }
 
+int test_error_span() {
+    CompileResult *compile_result = compile_expression("1 / a", (const char *[]){"a"}, 1);
+
+    if (compile_result->error.is_error) {
+        fprintf(stderr, "Error compiling expression: %s\n", compile_result->error.error);
+        destroy_compile_result(compile_result);
+        return 1;
+    }
+
+    ExpressionType *expr = get_expression_from_compile_result(compile_result);
+
+    TransformResult *transform_result = run_expression((const char *[]){"0"}, 1, expr);
+
+    int error = 0;
+
+    if (!transform_result->error.is_error) {
+        fprintf(stderr, "Expected error, got '%s'\n", transform_result->result);
+        error = 1;
+        goto cleanup;
+    }
+
+    if (transform_result->error.kind != KUIPER_ERROR_DIVIDE_BY_ZERO || transform_result->error.start != 2 ||
+        transform_result->error.end != 3) {
+        fprintf(stderr, "Unexpected error kind %d at %lu..%lu\n", transform_result->error.kind,
+                transform_result->error.start, transform_result->error.end);
+        error = 1;
+        goto cleanup;
+    } else {
+        printf("Test passed: '1 / a' with a=0 gives error '%s'\n", transform_result->error.error);
+    }
+
+cleanup:
+    destroy_transform_result(transform_result);
+    destroy_expression(expr);
+    return error;
+}
+
+int test_batch() {
+    CompileResult *compile_result = compile_expression("a + b", (const char *[]){"a", "b"}, 2);
+
+    if (compile_result->error.is_error) {
+        fprintf(stderr, "Error compiling expression: %s\n", compile_result->error.error);
+        destroy_compile_result(compile_result);
+        return 1;
+    }
+
+    ExpressionType *expr = get_expression_from_compile_result(compile_result);
+
+    const char *data[] = {"1", "2", "3", "{", "5", "6"};
+    size_t counts[] = {2, 2, 2};
+    BatchTransformResult *batch_result = run_expression_batch(data, counts, 3, expr);
+
+    int error = 0;
+
+    if (batch_result->len != 3) {
+        fprintf(stderr, "Expected 3 results, got %zu\n", batch_result->len);
+        error = 1;
+        goto cleanup;
+    }
+
+    TransformResult *results = batch_result->results;
+    if (results[0].error.is_error || strcmp(results[0].result, "3") != 0 || !results[1].error.is_error ||
+        results[1].error.kind != KUIPER_ERROR_INVALID_JSON || results[2].error.is_error ||
+        strcmp(results[2].result, "11") != 0) {
+        fprintf(stderr, "Unexpected batch results\n");
+        error = 1;
+        goto cleanup;
+    } else {
+        printf("Test passed: batch of 'a + b' gives %s, error '%s', and %s\n", results[0].result,
+               results[1].error.error, results[2].result);
+    }
+
+cleanup:
+    destroy_batch_transform_result(batch_result);
+    destroy_expression(expr);
+    return error;
+}
+
+int test_config_options() {
+    CompilerConfig *config = new_compiler_config();
+    config_deny_function(config, "now");
+
+    CompileResult *compile_result = compile_expression_with_config("now()", NULL, 0, config);
+    int error = 0;
+
+    if (!compile_result->error.is_error || compile_result->error.kind != KUIPER_ERROR_BUILD) {
+        fprintf(stderr, "Expected build error when calling denied function\n");
+        error = 1;
+        goto cleanup;
+    }
+    destroy_compile_result(compile_result);
+
+    config_set_type_checker_mode(config, TYPE_CHECKER_EARLY);
+    compile_result = compile_expression_with_config("1 + 'test'", NULL, 0, config);
+
+    if (!compile_result->error.is_error || compile_result->error.kind != KUIPER_ERROR_TYPE) {
+        fprintf(stderr, "Expected type error\n");
+        error = 1;
+        goto cleanup;
+    } else {
+        printf("Test passed: config options give error '%s'\n", compile_result->error.error);
+    }
+
+cleanup:
+    destroy_compile_result(compile_result);
+    destroy_compiler_config(config);
+    return error;
+}
+
+int test_operation_limit() {
+    CompileResult *compile_result = compile_expression("[1, 2, 3].map(x => x + a)", (const char *[]){"a"}, 1);
+
+    if (compile_result->error.is_error) {
+        fprintf(stderr, "Error compiling expression: %s\n", compile_result->error.error);
+        destroy_compile_result(compile_result);
+        return 1;
+    }
+
+    ExpressionType *expr = get_expression_from_compile_result(compile_result);
+
+    TransformResult *transform_result = run_expression((const char *[]){"1"}, 1, expr);
+    int error = 0;
+
+    if (transform_result->error.is_error || transform_result->opcount <= 0) {
+        fprintf(stderr, "Expected successful run with opcount\n");
+        error = 1;
+        goto cleanup;
+    }
+    long opcount = transform_result->opcount;
+    destroy_transform_result(transform_result);
+
+    transform_result = run_expression_limited((const char *[]){"1"}, 1, expr, opcount - 1);
+    if (!transform_result->error.is_error ||
+        transform_result->error.kind != KUIPER_ERROR_OPERATION_LIMIT_EXCEEDED) {
+        fprintf(stderr, "Expected operation limit to be exceeded\n");
+        error = 1;
+        goto cleanup;
+    }
+    destroy_transform_result(transform_result);
+
+    transform_result = run_expression_limited((const char *[]){"1"}, 1, expr, opcount);
+    if (transform_result->error.is_error) {
+        fprintf(stderr, "Error running expression: %s\n", transform_result->error.error);
+        error = 1;
+        goto cleanup;
+    } else {
+        printf("Test passed: expression with %ld operations runs with limit %ld\n", transform_result->opcount,
+               opcount);
+    }
+
+cleanup:
+    destroy_transform_result(transform_result);
+    destroy_expression(expr);
+    return error;
+}
+
+void *run_shared_expression(void *arg) {
+    ExpressionType *expr = (ExpressionType *)arg;
+    long failed = 0;
+    for (int i = 0; i < 100; i++) {
+        TransformResult *transform_result = run_expression((const char *[]){"1", "2"}, 2, expr);
+        if (transform_result->error.is_error || strcmp(transform_result->result, "3") != 0) {
+            failed = 1;
+        }
+        destroy_transform_result(transform_result);
+    }
+    destroy_expression(expr);
+    return (void *)failed;
+}
+
+int test_shared_expression() {
+    CompileResult *compile_result = compile_expression("a + b", (const char *[]){"a", "b"}, 2);
+
+    if (compile_result->error.is_error) {
+        fprintf(stderr, "Error compiling expression: %s\n", compile_result->error.error);
+        destroy_compile_result(compile_result);
+        return 1;
+    }
+
+    ExpressionType *expr = get_expression_from_compile_result(compile_result);
+
+    pthread_t threads[4];
+    for (int i = 0; i < 4; i++) {
+        pthread_create(&threads[i], NULL, run_shared_expression, clone_expression(expr));
+    }
+    // Release our own reference early, the threads keep the expression alive.
+    destroy_expression(expr);
+
+    int error = 0;
+    for (int i = 0; i < 4; i++) {
+        void *failed;
+        pthread_join(threads[i], &failed);
+        if (failed) {
+            error = 1;
+        }
+    }
+
+    if (error) {
+        fprintf(stderr, "Shared expression failed on a worker thread\n");
+    } else {
+        printf("Test passed: shared expression runs on 4 threads\n");
+    }
+    return error;
+}
+
+int test_utf8() {
+    CompileResult *compile_result = compile_expression("concat(\"blåbær: \", enhet.navn)", (const char *[]){"enhet"}, 1);
+
+    if (compile_result->error.is_error) {
+        fprintf(stderr, "Error compiling expression: %s\n", compile_result->error.error);
+        destroy_compile_result(compile_result);
+        return 1;
+    }
+
+    ExpressionType *expr = get_expression_from_compile_result(compile_result);
+
+    TransformResult *transform_result = run_expression((const char *[]){"{\"navn\": \"Måler ÆØÅ ✓\"}"}, 1, expr);
+
+    int error = 0;
+
+    if (transform_result->error.is_error) {
+        fprintf(stderr, "Error running expression: %s\n", transform_result->error.error);
+        error = 1;
+        goto cleanup;
+    }
+
+    if (strcmp(transform_result->result, "\"blåbær: Måler ÆØÅ ✓\"") != 0) {
+        fprintf(stderr, "Unexpected result '%s'\n", transform_result->result);
+        error = 1;
+        goto cleanup;
+    }
+
+    // Spans are byte offsets, "æ" is two bytes in UTF-8, so the `+` is at 5..6.
+    CompileResult *error_result = compile_expression("\"æ\" + 1", NULL, 0);
+    if (!error_result->error.is_error || error_result->error.start != 5 || error_result->error.end != 6) {
+        fprintf(stderr, "Unexpected error span %lu..%lu\n", error_result->error.start, error_result->error.end);
+        error = 1;
+    } else {
+        printf("Test passed: non-ASCII strings give %s\n", transform_result->result);
+    }
+    destroy_compile_result(error_result);
+
+cleanup:
+    destroy_transform_result(transform_result);
+    destroy_expression(expr);
+    return error;
+}
+
+int test_docs() {
+    char *docs = get_method_docs("concat");
+    int error = 0;
+    if (docs == NULL || strstr(docs, "\"signature\":\"concat(") == NULL) {
+        fprintf(stderr, "Unexpected docs for concat: %s\n", docs ? docs : "null");
+        error = 1;
+    }
+    destroy_string(docs);
+
+    if (get_method_docs("not_a_function") != NULL) {
+        fprintf(stderr, "Expected no docs for unknown function\n");
+        error = 1;
+    }
+
+    char *all = list_functions();
+    if (all[0] != '[' || strstr(all, "\"name\":\"concat\"") == NULL) {
+        fprintf(stderr, "Unexpected function list\n");
+        error = 1;
+    }
+    destroy_string(all);
+
+    if (!error) {
+        printf("Test passed: function documentation is available\n");
+    }
+    return error;
+}
+
 int main() {
     int r = test_simple_expression();
     if (r != 0)
         return r;
     r = test_expression_with_custom_function();
+    if (r != 0)
+        return r;
+    r = test_error_span();
+    if (r != 0)
+        return r;
+    r = test_batch();
+    if (r != 0)
+        return r;
+    r = test_config_options();
+    if (r != 0)
+        return r;
+    r = test_operation_limit();
+    if (r != 0)
+        return r;
+    r = test_shared_expression();
+    if (r != 0)
+        return r;
+    r = test_utf8();
+    if (r != 0)
+        return r;
+    r = test_docs();
     if (r != 0)
         return r;
     return 0;
@@ -0,0 +1,90 @@
+//! Table of compiled expressions owned by Java objects.
+//!
+//! Java only ever sees an opaque handle, never a raw pointer. A handle packs the slot index
+//! in the low 32 bits and a generation counter in the high 32 bits. The generation is bumped
+//! whenever a slot is freed, so using a handle after it has been freed, or freeing it twice,
+//! is detected instead of touching freed memory.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use jni::sys::jlong;
+use kuiper_lang::ExpressionType;
+
+struct Slot {
+    generation: u32,
+    expression: Option<Arc<ExpressionType>>,
+}
+
+struct HandleTable {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+}
+
+static HANDLES: Mutex<HandleTable> = Mutex::new(HandleTable {
+    slots: Vec::new(),
+    free: Vec::new(),
+});
+
+fn table() -> MutexGuard<'static, HandleTable> {
+    // The table is never left in an inconsistent state, so a poisoned lock is safe to use.
+    HANDLES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn split(handle: jlong) -> (usize, u32) {
+    let handle = handle as u64;
+    ((handle & 0xFFFF_FFFF) as usize, (handle >> 32) as u32)
+}
+
+/// Store an expression in the table, returning a handle to it. Handles are never 0.
+pub fn insert(expression: ExpressionType) -> jlong {
+    let mut table = table();
+    let index = match table.free.pop() {
+        Some(index) => index,
+        None => {
+            table.slots.push(Slot {
+                generation: 0,
+                expression: None,
+            });
+            (table.slots.len() - 1) as u32
+        }
+    };
+    let slot = &mut table.slots[index as usize];
+    // Generations start at 1, so that no valid handle is 0.
+    slot.generation = slot.generation.wrapping_add(1).max(1);
+    slot.expression = Some(Arc::new(expression));
+    (((slot.generation as u64) << 32) | index as u64) as jlong
+}
+
+/// Get the expression referenced by `handle`, or `None` if the handle is invalid or freed.
+///
+/// The expression is reference counted, so it stays alive until the caller is done with it,
+/// even if the handle is freed concurrently.
+pub fn get(handle: jlong) -> Option<Arc<ExpressionType>> {
+    let (index, generation) = split(handle);
+    let table = table();
+    let slot = table.slots.get(index)?;
+    if slot.generation != generation {
+        return None;
+    }
+    slot.expression.clone()
+}
+
+/// Free the expression referenced by `handle`. Returns `false` if the handle was already
+/// freed, or never valid.
+pub fn remove(handle: jlong) -> bool {
+    let (index, generation) = split(handle);
+    let mut table = table();
+    let Some(slot) = table.slots.get_mut(index) else {
+        return false;
+    };
+    if slot.generation != generation || slot.expression.is_none() {
+        return false;
+    }
+    // Drop the expression outside the lock.
+    let expression = slot.expression.take();
+    slot.generation = slot.generation.wrapping_add(1);
+    table.free.push(index as u32);
+    drop(table);
+    drop(expression);
+    true
+}
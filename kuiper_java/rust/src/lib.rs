@@ -3,13 +3,214 @@
 //! JNI bindings for the kuiper language.
 
 use jni::{
-    objects::{JClass, JObject, JObjectArray, JString},
-    sys::{jlong, jstring},
+    objects::{JByteArray, JClass, JObject, JObjectArray, JString, JThrowable, JValue},
+    sys::{jbyteArray, jlong, jobjectArray, jstring},
     JNIEnv,
 };
-use kuiper_lang::ExpressionType;
+use kuiper_lang::{
+    CompileError, CompilerConfig, ExpressionType, Span, TransformError, TypeCheckerMode,
+};
 use serde_json::Value;
 
+mod handles;
+
+/// Create a `KuiperException`, or a subclass of it, with span and error kind.
+fn new_exception<'local>(
+    env: &mut JNIEnv<'local>,
+    class: &str,
+    message: String,
+    span: Option<&Span>,
+    kind: &str,
+) -> jni::errors::Result<JObject<'local>> {
+    let message = env.new_string(message)?;
+    let kind = env.new_string(kind)?;
+    let (start, end) = span.map_or((-1, -1), |s| (s.start as jlong, s.end as jlong));
+    env.new_object(
+        class,
+        "(Ljava/lang/String;JJLjava/lang/String;)V",
+        &[
+            JValue::Object(&message),
+            JValue::Long(start),
+            JValue::Long(end),
+            JValue::Object(&kind),
+        ],
+    )
+}
+
+/// Throw a subclass of `KuiperException` with span and error kind.
+fn throw_exception(
+    env: &mut JNIEnv<'_>,
+    class: &str,
+    message: String,
+    span: Option<&Span>,
+    kind: &str,
+) {
+    let result =
+        new_exception(env, class, message, span, kind).and_then(|e| env.throw(JThrowable::from(e)));
+    if let Err(e) = result {
+        let _ = env.throw_new("com/cognite/kuiper/KuiperException", e.to_string());
+    }
+}
+
+fn compile_error_kind(error: &CompileError) -> &'static str {
+    match error {
+        CompileError::Build(_) => "build",
+        CompileError::Parser(_) => "parser",
+        CompileError::Optimizer(_) => "optimizer",
+        CompileError::TypeChecker(_) => "type",
+    }
+}
+
+/// Throw a `KuiperCompileException` from a compile error.
+fn throw_compile_error(env: &mut JNIEnv<'_>, error: CompileError) {
+    throw_exception(
+        env,
+        "com/cognite/kuiper/KuiperCompileException",
+        error.to_string(),
+        error.span().as_ref(),
+        compile_error_kind(&error),
+    );
+}
+
+/// Throw a `KuiperRuntimeException` from a transform error.
+fn throw_runtime_error(env: &mut JNIEnv<'_>, error: TransformError) {
+    throw_exception(
+        env,
+        "com/cognite/kuiper/KuiperRuntimeException",
+        error.to_string(),
+        error.span().as_ref(),
+        error.kind().name(),
+    );
+}
+
+/// Read a java `String[]` into a list of rust strings.
+fn get_string_array(env: &mut JNIEnv<'_>, array: &JObjectArray<'_>) -> Result<Vec<String>, String> {
+    let len = env
+        .get_array_length(array)
+        .map_err(|_| "Failed to get inputs array length".to_owned())?;
+
+    let mut res = Vec::with_capacity(len as usize);
+    for i in 0..len {
+        let obj = env
+            .get_object_array_element(array, i)
+            .map_err(|_| format!("Failed to get inputs array element {i}"))?;
+        let str = obj.into();
+        let v = env
+            .get_string(&str)
+            .map_err(|_| "Failed to get java string".to_owned())?;
+        let inp = v
+            .to_str()
+            .map_err(|_| "Failed to parse java string to utf-8".to_owned())?;
+        res.push(inp.to_owned());
+    }
+    Ok(res)
+}
+
+/// Read a `KuiperCompileOptions` object into a compiler config.
+fn get_compiler_config(
+    env: &mut JNIEnv<'_>,
+    options: &JObject<'_>,
+) -> Result<CompilerConfig, String> {
+    let mut config = CompilerConfig::new();
+    if options.is_null() {
+        return Ok(config);
+    }
+
+    let err = |e: jni::errors::Error| format!("Failed to read compile options: {e}");
+
+    let limit = env
+        .get_field(options, "optimizerOperationLimit", "J")
+        .and_then(|v| v.j())
+        .map_err(err)?;
+    let expansions = env
+        .get_field(options, "maxMacroExpansions", "I")
+        .and_then(|v| v.i())
+        .map_err(err)?;
+    let mode = env
+        .get_field(
+            options,
+            "typeCheckerMode",
+            "Lcom/cognite/kuiper/TypeCheckerMode;",
+        )
+        .and_then(|v| v.l())
+        .map_err(err)?;
+    let mode = if mode.is_null() {
+        TypeCheckerMode::Off
+    } else {
+        match env
+            .call_method(&mode, "ordinal", "()I", &[])
+            .and_then(|v| v.i())
+            .map_err(err)?
+        {
+            0 => TypeCheckerMode::Early,
+            1 => TypeCheckerMode::Late,
+            _ => TypeCheckerMode::Off,
+        }
+    };
+    let denied = env
+        .get_field(options, "deniedFunctions", "[Ljava/lang/String;")
+        .and_then(|v| v.l())
+        .map_err(err)?;
+
+    config = config
+        .optimizer_operation_limit(limit)
+        .max_macro_expansions(expansions)
+        .type_checker_mode(mode);
+    if !denied.is_null() {
+        for name in get_string_array(env, &JObjectArray::from(denied))? {
+            config = config.deny_function(name);
+        }
+    }
+    Ok(config)
+}
+
+/// Run an expression on a list of JSON strings, returning either a JSON string or a
+/// `KuiperException` object, which is not thrown.
+fn run_item<'local>(
+    env: &mut JNIEnv<'local>,
+    expr: &ExpressionType,
+    inputs: &[String],
+) -> jni::errors::Result<JObject<'local>> {
+    let mut values = Vec::with_capacity(inputs.len());
+    for inp in inputs {
+        match serde_json::from_str::<Value>(inp) {
+            Ok(v) => values.push(v),
+            Err(e) => {
+                return new_exception(
+                    env,
+                    "com/cognite/kuiper/KuiperException",
+                    format!("Input is not valid JSON: {e}"),
+                    None,
+                    "invalid_json",
+                )
+            }
+        }
+    }
+
+    let r = match expr.run(values.iter()) {
+        Ok(r) => r,
+        Err(e) => {
+            return new_exception(
+                env,
+                "com/cognite/kuiper/KuiperRuntimeException",
+                e.to_string(),
+                e.span().as_ref(),
+                e.kind().name(),
+            )
+        }
+    };
+    match serde_json::to_string(r.as_ref()) {
+        Ok(out) => Ok(env.new_string(out)?.into()),
+        Err(e) => new_exception(
+            env,
+            "com/cognite/kuiper/KuiperException",
+            e.to_string(),
+            None,
+            "serialization",
+        ),
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case, reason = "JNI names")]
 /// Compile a kuiper expression, called from JNI.
@@ -22,6 +223,7 @@ pub extern "system" fn Java_com_cognite_kuiper_Kuiper_compile_1expression<'local
     _class: JClass<'local>,
     input: JString<'local>,
     known_inputs: JObjectArray<'local>,
+    options: JObject<'local>,
 ) -> jlong {
     let Ok(input) = env.get_string(&input) else {
         let _ = env.throw_new(
@@ -77,11 +279,18 @@ pub extern "system" fn Java_com_cognite_kuiper_Kuiper_compile_1expression<'local
 
     let inputs_ref: Vec<_> = inputs.iter().map(|v| v.as_str()).collect();
 
-    match kuiper_lang::compile_expression(input, &inputs_ref) {
-        Ok(r) => Box::leak(Box::new(r)) as *mut _ as i64,
+    let config = match get_compiler_config(&mut env, &options) {
+        Ok(c) => c,
         Err(e) => {
-            // let span = e.span().unwrap_or_else(|| Range { start: 0, end: 0 });
-            let _ = env.throw_new("com/cognite/kuiper/KuiperException", e.to_string());
+            let _ = env.throw_new("com/cognite/kuiper/KuiperException", e);
+            return 0;
+        }
+    };
+
+    match kuiper_lang::compile_expression_with_config(input, &inputs_ref, &config) {
+        Ok(r) => handles::insert(r),
+        Err(e) => {
+            throw_compile_error(&mut env, e);
             0
         }
     }
@@ -94,20 +303,20 @@ pub extern "system" fn Java_com_cognite_kuiper_Kuiper_compile_1expression<'local
 /// # Safety
 ///
 /// Do not call this method, it must be linked from JNI.
-pub unsafe extern "system" fn Java_com_cognite_kuiper_Kuiper_run_1expression<'local>(
+pub extern "system" fn Java_com_cognite_kuiper_Kuiper_run_1expression<'local>(
     mut env: JNIEnv<'local>,
     _class: JClass<'local>,
     expression: jlong,
     inputs: JObjectArray<'local>,
 ) -> jstring {
-    if expression == 0 {
+    let Some(expr) = handles::get(expression) else {
         let _ = env.throw_new(
             "com/cognite/kuiper/KuiperException",
-            "Passed null kuiper expression",
+            "Kuiper expression is closed or invalid",
         );
 
         return JObject::null().into_raw();
-    }
+    };
 
     let Ok(len) = env.get_array_length(&inputs) else {
         let _ = env.throw_new(
@@ -156,13 +365,10 @@ pub unsafe extern "system" fn Java_com_cognite_kuiper_Kuiper_run_1expression<'lo
         final_inputs.push(value);
     }
 
-    // SAFETY: No way for us to do any further checks here, if java passes us
-    // something that isn't a pointer, we'll pass them a segfault right back.
-    let expr = unsafe { &*(expression as *const ExpressionType) };
     let r = match expr.run(final_inputs.iter()) {
         Ok(r) => r,
         Err(e) => {
-            let _ = env.throw_new("com/cognite/kuiper/KuiperException", format!("{e}"));
+            throw_runtime_error(&mut env, e);
             return JObject::null().into_raw();
         }
     };
@@ -186,25 +392,145 @@ pub unsafe extern "system" fn Java_com_cognite_kuiper_Kuiper_run_1expression<'lo
 
 #[no_mangle]
 #[allow(non_snake_case, reason = "JNI names")]
-/// Destroy a kuiper expression.
+/// Destroy a kuiper expression, called from JNI.
+///
+/// Freeing a handle that is already freed does nothing.
 ///
 /// # Safety
 ///
-/// Do not call this method, called from JNI. `expression` must be a
-/// valid pointer allocated by `...compile_1expression`
-pub unsafe extern "system" fn Java_com_cognite_kuiper_Kuiper_free_1expression<'local>(
-    mut env: JNIEnv<'local>,
+/// Do not call this method, it must be linked from JNI.
+pub extern "system" fn Java_com_cognite_kuiper_Kuiper_free_1expression<'local>(
+    _env: JNIEnv<'local>,
     _class: JClass<'local>,
     expression: jlong,
 ) {
-    if expression == 0 {
+    handles::remove(expression);
+}
+
+#[no_mangle]
+#[allow(non_snake_case, reason = "JNI names")]
+/// Run a kuiper expression once for each list of inputs, called from JNI.
+///
+/// Returns an array with one element per list of inputs, either the result as a JSON string,
+/// or a `KuiperException` if that run failed.
+///
+/// # Safety
+///
+/// Do not call this method, it must be linked from JNI.
+pub extern "system" fn Java_com_cognite_kuiper_Kuiper_run_1many<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    expression: jlong,
+    inputs: JObjectArray<'local>,
+) -> jobjectArray {
+    let Some(expr) = handles::get(expression) else {
         let _ = env.throw_new(
             "com/cognite/kuiper/KuiperException",
-            "Passed null kuiper expression",
+            "Kuiper expression is closed or invalid",
         );
 
-        return;
+        return JObject::null().into_raw();
+    };
+
+    let result = (|| {
+        let len = env.get_array_length(&inputs)?;
+        let output = env.new_object_array(len, "java/lang/Object", JObject::null())?;
+        for i in 0..len {
+            // Each item gets its own local frame, so that large batches do not
+            // exhaust the local reference table.
+            env.with_local_frame(8, |env| -> jni::errors::Result<()> {
+                let row = JObjectArray::from(env.get_object_array_element(&inputs, i)?);
+                let item = match get_string_array(env, &row) {
+                    Ok(row) => run_item(env, &expr, &row)?,
+                    Err(e) => {
+                        new_exception(env, "com/cognite/kuiper/KuiperException", e, None, "input")?
+                    }
+                };
+                env.set_object_array_element(&output, i, item)
+            })?;
+        }
+        Ok::<_, jni::errors::Error>(output)
+    })();
+
+    match result {
+        Ok(r) => r.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new("com/cognite/kuiper/KuiperException", e.to_string());
+            JObject::null().into_raw()
+        }
+    }
+}
+
+/// Read a java `byte[][]` of CBOR encoded values.
+fn get_cbor_inputs(env: &mut JNIEnv<'_>, array: &JObjectArray<'_>) -> Result<Vec<Value>, String> {
+    let len = env
+        .get_array_length(array)
+        .map_err(|_| "Failed to get inputs array length".to_owned())?;
+
+    let mut res = Vec::with_capacity(len as usize);
+    for i in 0..len {
+        let obj = env
+            .get_object_array_element(array, i)
+            .map_err(|_| format!("Failed to get inputs array element {i}"))?;
+        let bytes = env
+            .convert_byte_array(JByteArray::from(obj))
+            .map_err(|_| "Failed to get java byte array".to_owned())?;
+        let value: Value = ciborium::from_reader(bytes.as_slice())
+            .map_err(|e| format!("Input is not valid CBOR: {e}"))?;
+        res.push(value);
     }
+    Ok(res)
+}
 
-    unsafe { drop(Box::from_raw(expression as *mut ExpressionType)) }
+#[no_mangle]
+#[allow(non_snake_case, reason = "JNI names")]
+/// Run a kuiper expression with CBOR encoded inputs and output, called from JNI.
+///
+/// # Safety
+///
+/// Do not call this method, it must be linked from JNI.
+pub extern "system" fn Java_com_cognite_kuiper_Kuiper_run_1expression_1cbor<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    expression: jlong,
+    inputs: JObjectArray<'local>,
+) -> jbyteArray {
+    let Some(expr) = handles::get(expression) else {
+        let _ = env.throw_new(
+            "com/cognite/kuiper/KuiperException",
+            "Kuiper expression is closed or invalid",
+        );
+
+        return JObject::null().into_raw();
+    };
+
+    let final_inputs = match get_cbor_inputs(&mut env, &inputs) {
+        Ok(r) => r,
+        Err(e) => {
+            let _ = env.throw_new("com/cognite/kuiper/KuiperException", e);
+            return JObject::null().into_raw();
+        }
+    };
+
+    let r = match expr.run(final_inputs.iter()) {
+        Ok(r) => r,
+        Err(e) => {
+            throw_runtime_error(&mut env, e);
+            return JObject::null().into_raw();
+        }
+    };
+    let mut out = Vec::new();
+    if let Err(e) = ciborium::into_writer(r.as_ref(), &mut out) {
+        let _ = env.throw_new("com/cognite/kuiper/KuiperException", format!("{e}"));
+        return JObject::null().into_raw();
+    }
+    let Ok(r) = env.byte_array_from_slice(&out) else {
+        let _ = env.throw_new(
+            "com/cognite/kuiper/KuiperException",
+            "Failed to create byte array for result",
+        );
+        return JObject::null().into_raw();
+    };
+
+    r.into_raw()
 }
@@ -1,10 +1,14 @@
 package com.cognite.kuiper;
 
 class Kuiper {
-    public static native long compile_expression(String input, String[] known_inputs) throws KuiperException;
+    public static native long compile_expression(String input, String[] known_inputs, KuiperCompileOptions options) throws KuiperException;
 
     public static native String run_expression(long expression, String[] inputs) throws KuiperException;
 
+    public static native byte[] run_expression_cbor(long expression, byte[][] inputs) throws KuiperException;
+
+    public static native Object[] run_many(long expression, String[][] inputs) throws KuiperException;
+
     public static native void free_expression(long expression);
 
     static {
@@ -0,0 +1,8 @@
+package com.cognite.kuiper;
+
+/** Thrown when an expression fails to compile. */
+public class KuiperCompileException extends KuiperException {
+    public KuiperCompileException(String message, long start, long end, String kind) {
+        super(message, start, end, kind);
+    }
+}
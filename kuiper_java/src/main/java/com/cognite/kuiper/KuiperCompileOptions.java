@@ -0,0 +1,33 @@
+package com.cognite.kuiper;
+
+/** Options for compiling a kuiper expression. */
+public class KuiperCompileOptions {
+    long optimizerOperationLimit = 100_000;
+    int maxMacroExpansions = 20;
+    TypeCheckerMode typeCheckerMode = TypeCheckerMode.OFF;
+    String[] deniedFunctions = new String[0];
+
+    /** Set the maximum number of operations during constant execution in the optimizer. */
+    public KuiperCompileOptions setOptimizerOperationLimit(long limit) {
+        this.optimizerOperationLimit = limit;
+        return this;
+    }
+
+    /** Set the maximum number of macro expansions before compilation fails. */
+    public KuiperCompileOptions setMaxMacroExpansions(int limit) {
+        this.maxMacroExpansions = limit;
+        return this;
+    }
+
+    /** Set when to run the type checker. */
+    public KuiperCompileOptions setTypeCheckerMode(TypeCheckerMode mode) {
+        this.typeCheckerMode = mode;
+        return this;
+    }
+
+    /** Set the names of functions that may not be used in the expression. */
+    public KuiperCompileOptions setDeniedFunctions(String... names) {
+        this.deniedFunctions = names;
+        return this;
+    }
+}
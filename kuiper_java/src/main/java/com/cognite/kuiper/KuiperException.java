@@ -1,16 +1,25 @@
 package com.cognite.kuiper;
 
 public class KuiperException extends Exception {
+    /** Start offset of the error in the expression, or -1 if unknown. */
     public long start;
+    /** End offset of the error in the expression, or -1 if unknown. */
     public long end;
+    /** The kind of error, for example "parser", "type", or "divide_by_zero". May be null. */
+    public String kind;
 
-    public KuiperException(String message, long start, long end) {
+    public KuiperException(String message, long start, long end, String kind) {
         super(message);
         this.start = start;
         this.end = end;
+        this.kind = kind;
+    }
+
+    public KuiperException(String message, long start, long end) {
+        this(message, start, end, null);
     }
 
     public KuiperException(String message) {
-        super(message);
+        this(message, -1, -1, null);
     }
 }
@@ -2,18 +2,91 @@ package com.cognite.kuiper;
 
 import java.lang.ref.Cleaner;
 
-public class KuiperExpression {
-    private long expression;
+/**
+ * A compiled kuiper expression.
+ *
+ * The native expression is freed when this object is closed, or when it is garbage collected.
+ * Prefer closing it explicitly, for example with try-with-resources, to release native memory
+ * promptly. Using the expression after it has been closed throws a {@link KuiperException}.
+ */
+public class KuiperExpression implements AutoCloseable {
+    private static final Cleaner cleaner = Cleaner.create();
 
-    static Cleaner cleaner = Cleaner.create();
+    /**
+     * Owns the native handle. This must not reference the expression itself,
+     * or the expression would never become unreachable.
+     */
+    private static class State implements Runnable {
+        private final long handle;
+
+        State(long handle) {
+            this.handle = handle;
+        }
+
+        @Override
+        public void run() {
+            Kuiper.free_expression(handle);
+        }
+    }
+
+    private final long expression;
+    private final Cleaner.Cleanable cleanable;
 
     public KuiperExpression(String input, String... known_inputs) throws KuiperException {
-        this.expression = Kuiper.compile_expression(input, known_inputs);
-        long ptr = this.expression;
-        cleaner.register(this, () -> Kuiper.free_expression(ptr));
+        this(input, new KuiperCompileOptions(), known_inputs);
+    }
+
+    public KuiperExpression(String input, KuiperCompileOptions options, String... known_inputs) throws KuiperException {
+        this.expression = Kuiper.compile_expression(input, known_inputs, options);
+        this.cleanable = cleaner.register(this, new State(this.expression));
     }
 
     public String run(String... input) throws KuiperException {
         return Kuiper.run_expression(this.expression, input);
     }
+
+    /**
+     * Run the expression with CBOR encoded inputs, returning the CBOR encoded result.
+     *
+     * This avoids converting values to and from JSON strings. With Jackson, use a
+     * {@code CBORMapper} to convert a {@code JsonNode} to and from CBOR.
+     */
+    public byte[] runCbor(byte[]... input) throws KuiperException {
+        return Kuiper.run_expression_cbor(this.expression, input);
+    }
+
+    /**
+     * Run the expression once for each input, in a single native call.
+     * Use this for expressions with a single input.
+     */
+    public KuiperResult[] runMany(String... inputs) throws KuiperException {
+        String[][] rows = new String[inputs.length][];
+        for (int i = 0; i < inputs.length; i++) {
+            rows[i] = new String[] { inputs[i] };
+        }
+        return runMany(rows);
+    }
+
+    /**
+     * Run the expression once for each list of inputs, in a single native call.
+     * A failure in one run does not affect the others, errors are returned in the result.
+     */
+    public KuiperResult[] runMany(String[][] inputs) throws KuiperException {
+        Object[] raw = Kuiper.run_many(this.expression, inputs);
+        KuiperResult[] results = new KuiperResult[raw.length];
+        for (int i = 0; i < raw.length; i++) {
+            if (raw[i] instanceof KuiperException) {
+                results[i] = new KuiperResult(null, (KuiperException) raw[i]);
+            } else {
+                results[i] = new KuiperResult((String) raw[i], null);
+            }
+        }
+        return results;
+    }
+
+    /** Free the native expression. Calling this more than once has no effect. */
+    @Override
+    public void close() {
+        cleanable.clean();
+    }
 }
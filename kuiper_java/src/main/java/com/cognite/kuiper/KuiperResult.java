@@ -0,0 +1,35 @@
+package com.cognite.kuiper;
+
+/** The result of a single run in a batch, either a JSON string or an error. */
+public class KuiperResult {
+    private final String result;
+    private final KuiperException error;
+
+    KuiperResult(String result, KuiperException error) {
+        this.result = result;
+        this.error = error;
+    }
+
+    /** Whether this run succeeded. */
+    public boolean isOk() {
+        return error == null;
+    }
+
+    /** The result as a JSON string, or null if the run failed. */
+    public String getResult() {
+        return result;
+    }
+
+    /** The error, or null if the run succeeded. */
+    public KuiperException getError() {
+        return error;
+    }
+
+    /** Get the result as a JSON string, throwing the error if the run failed. */
+    public String get() throws KuiperException {
+        if (error != null) {
+            throw error;
+        }
+        return result;
+    }
+}
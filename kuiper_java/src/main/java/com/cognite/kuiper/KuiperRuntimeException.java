@@ -0,0 +1,8 @@
+package com.cognite.kuiper;
+
+/** Thrown when running an expression fails. */
+public class KuiperRuntimeException extends KuiperException {
+    public KuiperRuntimeException(String message, long start, long end, String kind) {
+        super(message, start, end, kind);
+    }
+}
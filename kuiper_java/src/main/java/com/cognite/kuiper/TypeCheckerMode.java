@@ -0,0 +1,11 @@
+package com.cognite.kuiper;
+
+/** When to run the type checker during compilation. */
+public enum TypeCheckerMode {
+    /** Run the type checker before optimization. */
+    EARLY,
+    /** Run the type checker after optimization. */
+    LATE,
+    /** Disable the type checker. */
+    OFF,
+}
@@ -2,8 +2,11 @@ package com.cognite.kuiper;
 
 import org.junit.Test;
 
+import static org.junit.Assert.assertArrayEquals;
 import static org.junit.Assert.assertEquals;
+import static org.junit.Assert.assertFalse;
 import static org.junit.Assert.assertThrows;
+import static org.junit.Assert.assertTrue;
 
 public class KuiperTest {
     public KuiperTest() {}
@@ -16,8 +19,11 @@ public class KuiperTest {
 
     @Test
     public void testCompileError() throws KuiperException {
-        KuiperException ex = assertThrows(KuiperException.class, () -> new KuiperExpression("1 + floor(5, 5)"));
+        KuiperCompileException ex = assertThrows(KuiperCompileException.class, () -> new KuiperExpression("1 + floor(5, 5)"));
         assertEquals("Compilation failed: Incorrect number of function args: function floor takes 1 arguments at 4..15", ex.getMessage());
+        assertEquals(4, ex.start);
+        assertEquals(15, ex.end);
+        assertEquals("build", ex.kind);
     }
 
     @Test
@@ -29,7 +35,81 @@ public class KuiperTest {
     @Test
     public void testRunError() throws KuiperException {
         var expr = new KuiperExpression("1 / input", "input");
-        KuiperException ex = assertThrows(KuiperException.class, () -> expr.run("0"));
+        KuiperRuntimeException ex = assertThrows(KuiperRuntimeException.class, () -> expr.run("0"));
         assertEquals("Divide by zero at 2..3", ex.getMessage());
+        assertEquals(2, ex.start);
+        assertEquals(3, ex.end);
+        assertEquals("divide_by_zero", ex.kind);
+    }
+
+    @Test
+    public void testRunMany() throws KuiperException {
+        var expr = new KuiperExpression("10 / input", "input");
+        KuiperResult[] res = expr.runMany("1", "0", "{", "5");
+        assertEquals(4, res.length);
+        assertEquals("10.0", res[0].get());
+        assertFalse(res[1].isOk());
+        assertEquals("divide_by_zero", res[1].getError().kind);
+        assertTrue(res[1].getError() instanceof KuiperRuntimeException);
+        assertEquals("invalid_json", res[2].getError().kind);
+        assertEquals("2.0", res[3].getResult());
+    }
+
+    @Test
+    public void testRunManyMultipleInputs() throws KuiperException {
+        var expr = new KuiperExpression("in1 + in2", "in1", "in2");
+        KuiperResult[] res = expr.runMany(new String[][] { { "1", "2" }, { "3", "4" } });
+        assertEquals("3", res[0].get());
+        assertEquals("7", res[1].get());
+    }
+
+    @Test
+    public void testClose() throws KuiperException {
+        var expr = new KuiperExpression("1 + 1");
+        try (expr) {
+            assertEquals("2", expr.run());
+        }
+        KuiperException ex = assertThrows(KuiperException.class, () -> expr.run());
+        assertEquals("Kuiper expression is closed or invalid", ex.getMessage());
+        // Closing twice is fine.
+        expr.close();
+
+        // A new expression may reuse the freed slot, the old one must stay closed.
+        try (var other = new KuiperExpression("2 + 2")) {
+            assertEquals("4", other.run());
+            assertThrows(KuiperException.class, () -> expr.run());
+        }
+    }
+
+    @Test
+    public void testRunCbor() throws KuiperException {
+        try (var expr = new KuiperExpression("input.a + 1", "input")) {
+            // {"a": 1}
+            byte[] input = new byte[] { (byte) 0xA1, 0x61, 0x61, 0x01 };
+            assertArrayEquals(new byte[] { 0x02 }, expr.runCbor(input));
+
+            KuiperException ex = assertThrows(KuiperException.class, () -> expr.runCbor(new byte[] { (byte) 0xA1 }));
+            assertTrue(ex.getMessage().startsWith("Input is not valid CBOR"));
+        }
+    }
+
+    @Test
+    public void testCompileOptions() throws KuiperException {
+        var denied = new KuiperCompileOptions().setDeniedFunctions("now");
+        KuiperCompileException ex = assertThrows(KuiperCompileException.class, () -> new KuiperExpression("now()", denied));
+        assertEquals("build", ex.kind);
+        assertTrue(ex.getMessage().contains("Function now is not allowed"));
+
+        var typed = new KuiperCompileOptions().setTypeCheckerMode(TypeCheckerMode.EARLY);
+        ex = assertThrows(KuiperCompileException.class, () -> new KuiperExpression("1 + \"test\"", typed));
+        assertEquals("type", ex.kind);
+
+        var limited = new KuiperCompileOptions().setOptimizerOperationLimit(1);
+        ex = assertThrows(KuiperCompileException.class, () -> new KuiperExpression("[1, 2, 3].map(x => x + 1)", limited));
+        assertEquals("optimizer", ex.kind);
+
+        try (var expr = new KuiperExpression("input + 1", new KuiperCompileOptions(), "input")) {
+            assertEquals("3", expr.run("2"));
+        }
     }
 }
@@ -9,18 +9,101 @@ use std::{
 
 use gloo_utils::format::JsValueSerdeExt;
 use js_sys::{Array, Function, Reflect};
+use kuiper_lang::docs::FUNCTIONS;
+use kuiper_lang::types::{Type, TypeError};
 use kuiper_lang::{
-    compile_expression_with_config as compile_expression_kuiper, CompileError,
-    DynamicFunctionBuilder, Expression, ExpressionMeta, ExpressionType, Span, TransformError,
+    compile_expression_with_config as compile_expression_kuiper, BuildError, CompileError,
+    DynamicFunctionBuilder, Expression, ExpressionMeta, ExpressionType, ParseError, PrettyError,
+    Span, TransformError, TransformErrorKind,
 };
-use serde_json::Value;
+use serde_json::{json, Value};
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen(getter_with_clone)]
+/// The category of a `KuiperError`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KuiperErrorKind {
+    /// The expression failed to parse.
+    Parser,
+    /// The expression parsed, but could not be built, e.g. an unknown function or variable.
+    Build,
+    /// The expression failed during optimization, typically a runtime error in a constant part.
+    Optimizer,
+    /// The type checker rejected the expression.
+    Type,
+    /// A runtime error with an incorrect type.
+    IncorrectType,
+    /// A runtime error from a failed conversion.
+    ConversionFailed,
+    /// A runtime error from an invalid operation.
+    InvalidOperation,
+    /// Division by zero at runtime.
+    DivideByZero,
+    /// A source value was missing at runtime.
+    SourceMissing,
+    /// The operation limit was exceeded.
+    OperationLimitExceeded,
+    /// The formatter failed.
+    Format,
+    /// Input or output could not be converted to or from JSON.
+    Serialization,
+    /// An error thrown by javascript code, such as a custom function.
+    Javascript,
+}
+
+#[wasm_bindgen]
 pub struct KuiperError {
+    #[wasm_bindgen(getter_with_clone)]
     pub message: String,
     pub start: Option<usize>,
     pub end: Option<usize>,
+    pub error_kind: KuiperErrorKind,
+    data: Option<Value>,
+}
+
+#[wasm_bindgen]
+impl KuiperError {
+    /// Structured data about the error, depending on its kind, or `undefined`.
+    ///
+    /// Parser errors have the unexpected `token`, if any, and a list of `expected` tokens.
+    /// Unknown functions and variables have the `function` or `variable` name, and
+    /// missing sources have the `source` name.
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Result<JsValue, KuiperError> {
+        match &self.data {
+            Some(data) => Ok(JsValue::from_serde(data)?),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+}
+
+fn transform_error_data(error: &TransformError) -> Option<Value> {
+    match error {
+        TransformError::SourceMissingError(d) => Some(json!({ "source": d.desc })),
+        _ => None,
+    }
+}
+
+fn compile_error_data(error: &CompileError) -> Option<Value> {
+    match error {
+        CompileError::Parser(ParseError::UnrecognizedToken { token, expected }) => {
+            Some(json!({ "token": token.1.to_string(), "expected": expected }))
+        }
+        CompileError::Parser(ParseError::UnrecognizedEof { expected, .. }) => {
+            Some(json!({ "token": null, "expected": expected }))
+        }
+        CompileError::Parser(ParseError::ExtraToken { token }) => {
+            Some(json!({ "token": token.1.to_string(), "expected": [] }))
+        }
+        CompileError::Build(BuildError::UnrecognizedFunction(d)) => {
+            Some(json!({ "function": d.detail }))
+        }
+        CompileError::Build(BuildError::UnknownVariable(d) | BuildError::VariableConflict(d)) => {
+            Some(json!({ "variable": d.detail }))
+        }
+        CompileError::Optimizer(e) => transform_error_data(e),
+        _ => None,
+    }
 }
 
 #[wasm_bindgen]
@@ -30,20 +113,52 @@ pub struct KuiperExpression {
 
 impl From<TransformError> for KuiperError {
     fn from(value: TransformError) -> Self {
+        let error_kind = match value.kind() {
+            TransformErrorKind::SourceMissing => KuiperErrorKind::SourceMissing,
+            TransformErrorKind::IncorrectType => KuiperErrorKind::IncorrectType,
+            TransformErrorKind::ConversionFailed => KuiperErrorKind::ConversionFailed,
+            TransformErrorKind::InvalidOperation => KuiperErrorKind::InvalidOperation,
+            TransformErrorKind::DivideByZero => KuiperErrorKind::DivideByZero,
+            TransformErrorKind::OperationLimitExceeded => KuiperErrorKind::OperationLimitExceeded,
+            // Kinds added to kuiper_lang later are reported as invalid operations until they get their own kind here.
+            _ => KuiperErrorKind::InvalidOperation,
+        };
         Self {
             message: value.to_string(),
             start: value.span().map(|s| s.start),
             end: value.span().map(|s| s.end),
+            error_kind,
+            data: transform_error_data(&value),
         }
     }
 }
 
 impl From<CompileError> for KuiperError {
     fn from(value: CompileError) -> Self {
+        let error_kind = match &value {
+            CompileError::Build(_) => KuiperErrorKind::Build,
+            CompileError::Parser(_) => KuiperErrorKind::Parser,
+            CompileError::Optimizer(_) => KuiperErrorKind::Optimizer,
+            CompileError::TypeChecker(_) => KuiperErrorKind::Type,
+        };
         Self {
             message: value.to_string(),
             start: value.span().map(|s| s.start),
             end: value.span().map(|s| s.end),
+            error_kind,
+            data: compile_error_data(&value),
+        }
+    }
+}
+
+impl From<TypeError> for KuiperError {
+    fn from(value: TypeError) -> Self {
+        Self {
+            message: value.to_string(),
+            start: Some(value.span().start),
+            end: Some(value.span().end),
+            error_kind: KuiperErrorKind::Type,
+            data: None,
         }
     }
 }
@@ -54,6 +169,8 @@ impl From<serde_json::Error> for KuiperError {
             message: value.to_string(),
             start: Some(value.column()),
             end: Some(value.column()),
+            error_kind: KuiperErrorKind::Serialization,
+            data: None,
         }
     }
 }
@@ -64,6 +181,8 @@ impl From<JsValue> for KuiperError {
             message: format!("{:?}", value),
             start: None,
             end: None,
+            error_kind: KuiperErrorKind::Javascript,
+            data: None,
         }
     }
 }
@@ -96,36 +215,104 @@ impl KuiperResultWithCompletion {
     }
 }
 
+#[wasm_bindgen]
+pub struct KuiperResultWithOpcount {
+    result: Value,
+    opcount: i64,
+}
+
+#[wasm_bindgen]
+impl KuiperResultWithOpcount {
+    pub fn get_result(&self) -> Result<JsValue, KuiperError> {
+        Ok(JsValue::from_serde(&self.result)?)
+    }
+
+    /// The number of operations performed while running the expression.
+    #[wasm_bindgen(getter)]
+    pub fn opcount(&self) -> i64 {
+        self.opcount
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string_js(&self) -> String {
+        format!("{}, {}", &self.result, self.opcount)
+    }
+}
+
+fn inputs_to_json(data: Vec<JsValue>) -> Result<Vec<Value>, KuiperError> {
+    Ok(data
+        .into_iter()
+        .map(|d| d.into_serde())
+        .collect::<Result<_, _>>()?)
+}
+
 #[wasm_bindgen]
 impl KuiperExpression {
     #[wasm_bindgen(variadic)]
     pub fn run(&self, data: Vec<JsValue>) -> Result<JsValue, KuiperError> {
-        let json_items: Vec<Value> = data
-            .into_iter()
-            .map(|d| d.into_serde())
-            .collect::<Result<_, _>>()?;
-        let json: Vec<&Value> = json_items.iter().collect();
-        let res = self.expression.run(json)?;
+        let json_items = inputs_to_json(data)?;
+        let res = self.expression.run(json_items.iter())?;
+        Ok(JsValue::from_serde(&*res)?)
+    }
+
+    /// Run the expression with a list of inputs, failing if it performs more than
+    /// `max_operation_count` operations. If set to -1, no limit is enforced.
+    pub fn run_limited(
+        &self,
+        data: Vec<JsValue>,
+        max_operation_count: i64,
+    ) -> Result<JsValue, KuiperError> {
+        let json_items = inputs_to_json(data)?;
+        let res = self
+            .expression
+            .run_limited(json_items.iter(), max_operation_count)?;
         Ok(JsValue::from_serde(&*res)?)
     }
 
+    #[wasm_bindgen(variadic)]
+    pub fn run_get_opcount(
+        &self,
+        data: Vec<JsValue>,
+    ) -> Result<KuiperResultWithOpcount, KuiperError> {
+        let json_items = inputs_to_json(data)?;
+        let (res, opcount) = self.expression.run_get_opcount(json_items.iter())?;
+        Ok(KuiperResultWithOpcount {
+            result: res.into_owned(),
+            opcount,
+        })
+    }
+
     #[wasm_bindgen(variadic)]
     pub fn run_get_completions(
         &self,
         data: Vec<JsValue>,
     ) -> Result<KuiperResultWithCompletion, KuiperError> {
-        let json_items: Vec<Value> = data
-            .into_iter()
-            .map(|d| d.into_serde())
-            .collect::<Result<_, _>>()?;
-        let json: Vec<&Value> = json_items.iter().collect();
-        let (res, comp) = self.expression.run_get_completions(json)?;
+        let json_items = inputs_to_json(data)?;
+        let (res, comp) = self.expression.run_get_completions(json_items.iter())?;
         Ok(KuiperResultWithCompletion {
             result: res.into_owned(),
             completions: comp,
         })
     }
 
+    /// Infer the output type of the expression from a list of JSON schemas, one for each input,
+    /// and return it as a TypeScript type declaration named `type_name`.
+    pub fn output_typescript_type(
+        &self,
+        type_name: String,
+        input_schemas: Vec<JsValue>,
+    ) -> Result<String, KuiperError> {
+        let types = input_schemas
+            .into_iter()
+            .map(|s| Ok(Type::from_json_schema(&s.into_serde::<Value>()?)))
+            .collect::<Result<Vec<_>, KuiperError>>()?;
+        let output = self.expression.run_types(types)?;
+        Ok(format!(
+            "export type {type_name} = {};",
+            output.to_typescript()
+        ))
+    }
+
     #[wasm_bindgen(js_name = toString)]
     pub fn to_string_js(&self) -> String {
         self.expression.to_string()
@@ -152,15 +339,60 @@ pub fn compile_expression(
 }
 
 #[wasm_bindgen]
-pub fn format_expression(input: String) -> Result<String, KuiperError> {
-    let formatted = kuiper_lang::format_expression(&input).map_err(|e| KuiperError {
-        message: e.to_string(),
-        start: None,
-        end: None,
-    })?;
+pub fn format_expression(
+    input: String,
+    options: Option<FormatOptions>,
+) -> Result<String, KuiperError> {
+    let options = options.map(|o| o.options).unwrap_or_default();
+    let formatted =
+        kuiper_lang::format_expression_with_options(&input, &options).map_err(|e| KuiperError {
+            message: e.to_string(),
+            start: None,
+            end: None,
+            error_kind: match e {
+                PrettyError::Parser(_) => KuiperErrorKind::Parser,
+                _ => KuiperErrorKind::Format,
+            },
+            data: None,
+        })?;
     Ok(formatted)
 }
 
+/// Get the documentation for the built-in function `name`, as an object with
+/// `name`, `signature`, `description`, and a list of `examples`.
+/// Returns `undefined` if no such function exists.
+#[wasm_bindgen]
+pub fn get_method_docs(name: &str) -> Result<JsValue, KuiperError> {
+    match FUNCTIONS.iter().find(|f| f.name == name) {
+        Some(def) => Ok(JsValue::from_serde(def)?),
+        None => Ok(JsValue::UNDEFINED),
+    }
+}
+
+/// List the documentation for all built-in functions, sorted by name.
+#[wasm_bindgen]
+pub fn list_functions() -> Result<JsValue, KuiperError> {
+    Ok(JsValue::from_serde(FUNCTIONS)?)
+}
+
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct FormatOptions {
+    options: kuiper_lang::FormatOptions,
+}
+
+#[wasm_bindgen]
+impl FormatOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_indent_size(&mut self, indent_size: usize) {
+        self.options.indent_size = indent_size;
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Default)]
 pub struct CompilerConfig {
@@ -203,6 +435,19 @@ impl CompilerConfig {
         );
     }
 
+    pub fn set_type_checker_mode(&mut self, mode: TypeCheckerMode) {
+        self.config = Some(
+            self.config
+                .take()
+                .unwrap_or_default()
+                .type_checker_mode(mode.into()),
+        );
+    }
+
+    pub fn deny_function(&mut self, name: String) {
+        self.config = Some(self.config.take().unwrap_or_default().deny_function(name));
+    }
+
     pub fn add_custom_function(&mut self, name: String, implementation: js_sys::Function) {
         self.config = Some(
             self.config
@@ -218,6 +463,27 @@ impl CompilerConfig {
     }
 }
 
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub enum TypeCheckerMode {
+    /// Run the type checker before optimization.
+    Early,
+    /// Run the type checker after optimization.
+    Late,
+    /// Do not run the type checker.
+    Off,
+}
+
+impl From<TypeCheckerMode> for kuiper_lang::TypeCheckerMode {
+    fn from(value: TypeCheckerMode) -> Self {
+        match value {
+            TypeCheckerMode::Early => Self::Early,
+            TypeCheckerMode::Late => Self::Late,
+            TypeCheckerMode::Off => Self::Off,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Custom {
     function: Arc<js_sys::Function>,
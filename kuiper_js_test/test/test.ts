@@ -1,4 +1,4 @@
-import { compile_expression, CompilerConfig, KuiperError } from '@cognite/kuiper_js';
+import { compile_expression, CompilerConfig, format_expression, FormatOptions, get_method_docs, KuiperError, KuiperErrorKind, list_functions, TypeCheckerMode } from '@cognite/kuiper_js';
 import { strict as assert } from 'assert';
 
 describe('kuiper_js WASM module', function () {
@@ -17,9 +17,29 @@ describe('kuiper_js WASM module', function () {
             assert.equal(e.message, "Compilation failed: Divide by zero at 2..3");
             assert.equal(e.start, 2);
             assert.equal(e.end, 3);
+            assert.equal(e.error_kind, KuiperErrorKind.Optimizer);
         }
     });
 
+    it('errors have a kind', function () {
+        assert.throws(() => compile_expression("1 +", []),
+            (e: KuiperError) => e.error_kind === KuiperErrorKind.Parser);
+        assert.throws(() => compile_expression("foo()", []),
+            (e: KuiperError) => e.error_kind === KuiperErrorKind.Build);
+        const expr = compile_expression("a / b", ["a", "b"]);
+        assert.throws(() => expr.run(1, 0),
+            (e: KuiperError) => e.error_kind === KuiperErrorKind.DivideByZero);
+    });
+
+    it('errors have structured data', function () {
+        assert.throws(() => compile_expression("foo()", []),
+            (e: KuiperError) => e.data.function === "foo");
+        assert.throws(() => compile_expression("1 +", []),
+            (e: KuiperError) => e.data.token === null && e.data.expected.length > 0);
+        assert.throws(() => compile_expression("1 / 0", []),
+            (e: KuiperError) => e.data === undefined);
+    });
+
     it('Compiling should work with inputs', function () {
         const expr = compile_expression("a + b", ["a", "b"]);
         const res = expr.run(1, 2);
@@ -46,6 +66,24 @@ describe('kuiper_js WASM module', function () {
         assert.equal(res, 6);
     });
 
+    it('denied functions fail to compile', function () {
+        const config = new CompilerConfig();
+        config.deny_function("now");
+        try {
+            compile_expression("now()", [], config);
+            assert.fail("Expected compile error");
+        } catch (e) {
+            assert.ok(e instanceof KuiperError);
+            assert.equal(e.message, "Function now is not allowed at 0..5");
+        }
+    });
+
+    it('type checker mode can be set', function () {
+        const config = new CompilerConfig();
+        config.set_type_checker_mode(TypeCheckerMode.Early);
+        assert.throws(() => compile_expression("1 + 'a'", [], config), KuiperError);
+    });
+
     it('exceptions in custom functions are propagated', function () {
         const config = new CompilerConfig();
         config.add_custom_function("foo", (x: number) => { throw new Error("Custom function error"); });
@@ -58,4 +96,49 @@ describe('kuiper_js WASM module', function () {
             assert.equal(e.message, "Error: Custom function error at 0..6");
         }
     });
+
+    it('expressions can be formatted', function () {
+        assert.equal(format_expression("[\n1+1\n]"), "[\n    1 + 1\n]");
+        const options = new FormatOptions();
+        options.set_indent_size(2);
+        assert.equal(format_expression("[\n1+1\n]", options), "[\n  1 + 1\n]");
+    });
+
+    it('operation limits are enforced', function () {
+        const expr = compile_expression("a.map(x => x + 1)", ["a"]);
+        assert.deepEqual(expr.run_limited([[1, 2, 3]], BigInt(100)), [2, 3, 4]);
+        assert.throws(() => expr.run_limited([[1, 2, 3]], BigInt(2)),
+            (e: KuiperError) => e.error_kind === KuiperErrorKind.OperationLimitExceeded);
+    });
+
+    it('operation count is returned', function () {
+        const expr = compile_expression("a.map(x => x + 1)", ["a"]);
+        const res = expr.run_get_opcount([1, 2, 3]);
+        assert.deepEqual(res.get_result(), [2, 3, 4]);
+        assert.ok(res.opcount > 0);
+    });
+
+    it('function documentation is available', function () {
+        const docs = get_method_docs("pow");
+        assert.equal(docs.name, "pow");
+        assert.equal(docs.signature, "pow(x, y)");
+        assert.ok(docs.examples.length > 0);
+        assert.equal(get_method_docs("does_not_exist"), undefined);
+
+        const functions = list_functions();
+        assert.ok(functions.some((f: { name: string }) => f.name === "map"));
+    });
+
+    it('output types can be generated as TypeScript', function () {
+        const expr = compile_expression("{ \"id\": input.id, \"tags\": input.tags.map(t => t.name) }", ["input"]);
+        const decl = expr.output_typescript_type("Output", [{
+            type: "object",
+            properties: {
+                id: { type: "integer" },
+                tags: { type: "array", items: { type: "object", properties: { name: { type: "string" } }, required: ["name"] } }
+            },
+            required: ["id", "tags"],
+        }]);
+        assert.equal(decl, "export type Output = { \"id\": number; \"tags\": string[] };");
+    });
 });
@@ -305,9 +305,35 @@ fn bench_rockwell_format(c: &mut Criterion) {
     });
 }
 
+fn bench_projection(c: &mut Criterion) {
+    // Pure projections borrow from the input, so this should not clone `items`.
+    let expr = compile_expression(r#"input.payload.items[1]"#, &["input"]).unwrap();
+    let items: Vec<_> = (0..1000)
+        .map(|i| json!({ "timestamp": 1676550645000i64 + i, "value": i }))
+        .collect();
+    let input = json!({ "payload": { "items": items } });
+
+    c.bench_function("projection", move |f| {
+        f.iter(|| expr.run(black_box([&input])).unwrap())
+    });
+}
+
+fn bench_filter(c: &mut Criterion) {
+    // Filtering a borrowed array only clones the elements that are kept.
+    let expr = compile_expression(r#"input.items.filter(i => i.value > 990)"#, &["input"]).unwrap();
+    let items: Vec<_> = (0..1000)
+        .map(|i| json!({ "timestamp": 1676550645000i64 + i, "value": i }))
+        .collect();
+    let input = json!({ "items": items });
+
+    c.bench_function("filter", move |f| {
+        f.iter(|| expr.run(black_box([&input])).unwrap())
+    });
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().with_profiler(perf::FlamegraphProfiler::new(100));
-    targets = bench_trivial_map, bench_cognite_format, bench_rockwell_format
+    targets = bench_trivial_map, bench_cognite_format, bench_rockwell_format, bench_projection, bench_filter
 }
 criterion_main!(benches);
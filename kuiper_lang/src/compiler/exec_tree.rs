@@ -1,4 +1,7 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
 use logos::Span;
 use thiserror::Error;
@@ -9,7 +12,7 @@ use crate::{
         DynamicFunctionSource, ExpressionType, FunctionType, IfExpression, IsExpression,
         LambdaExpression, MacroCallExpression, ObjectElement, ObjectExpression, OpExpression,
         SelectorElement, SelectorExpression, SourceElement, TemplateStringExpression,
-        UnaryOpExpression,
+        TryExpression, UnaryOpExpression,
     },
     parse::{
         Definition, Expression, FunctionParameter, Lambda, Macro, OuterScopeItem, Program, Selector,
@@ -139,6 +142,7 @@ struct BuilderInner {
     macro_counter: MacroCounter,
     macro_stack: Vec<String>,
     custom_functions: DynamicFunctionSource,
+    denied_functions: HashSet<String>,
 }
 
 impl ExecTreeBuilder {
@@ -173,6 +177,7 @@ impl ExecTreeBuilder {
                 macro_counter: MacroCounter::new(compiler_config.max_macro_expansions),
                 macro_stack: Vec::new(),
                 custom_functions: compiler_config.custom_function_source.clone(),
+                denied_functions: compiler_config.denied_functions.clone(),
             },
             expression: program.expression,
             outer_definitions,
@@ -278,6 +283,34 @@ impl BuilderInner {
         Ok(ExpressionType::Lambda(r))
     }
 
+    // These are kept out of `build_expression`, since every local there adds to the stack
+    // usage of deeply nested expressions.
+    fn build_is(
+        &mut self,
+        expr: crate::parse::IsExpression,
+        depth: usize,
+    ) -> Result<ExpressionType, BuildError> {
+        Ok(ExpressionType::Is(IsExpression::new(
+            self.build_expression(*expr.lhs, depth + 1)?,
+            expr.rhs,
+            expr.not,
+        )?))
+    }
+
+    fn build_try(
+        &mut self,
+        inner: Expression,
+        handler: FunctionParameter,
+        loc: Span,
+        depth: usize,
+    ) -> Result<ExpressionType, BuildError> {
+        Ok(ExpressionType::Try(TryExpression::new(
+            self.build_expression(inner, depth + 1)?,
+            self.build_function_param(handler, depth + 1)?,
+            loc,
+        )?))
+    }
+
     fn build_function_param(
         &mut self,
         expr: FunctionParameter,
@@ -407,6 +440,12 @@ impl BuilderInner {
                 if let Some(m) = self.macros.get(&name).cloned() {
                     self.build_macro_call(m, args, loc, depth + 1)
                 } else {
+                    if self.denied_functions.contains(&name) {
+                        return Err(BuildError::other(
+                            loc,
+                            &format!("Function {name} is not allowed"),
+                        ));
+                    }
                     let args = args
                         .into_iter()
                         .map(|e| self.build_function_param(e, depth + 1))
@@ -425,11 +464,12 @@ impl BuilderInner {
                 vec![],
                 span,
             )?)),
-            Expression::Is(i) => Ok(ExpressionType::Is(IsExpression::new(
-                self.build_expression(*i.lhs, depth + 1)?,
-                i.rhs,
-                i.not,
-            )?)),
+            Expression::Is(i) => self.build_is(i, depth),
+            Expression::Try {
+                inner,
+                handler,
+                loc,
+            } => self.build_try(*inner, *handler, loc, depth),
             Expression::If { args, loc } => Ok(ExpressionType::If(IfExpression::new(
                 args.into_iter()
                     .map(|e| self.build_expression(e, depth + 1))
@@ -1,7 +1,7 @@
 mod exec_tree;
 mod optimizer;
 
-use std::{fmt::Display, sync::Arc};
+use std::{collections::HashSet, fmt::Display, sync::Arc};
 
 pub use exec_tree::BuildError;
 use logos::Span;
@@ -37,6 +37,7 @@ pub struct CompilerConfig {
     pub(crate) max_macro_expansions: i32,
     pub(crate) type_checker: TypeCheckerMode,
     pub(crate) custom_function_source: DynamicFunctionSource,
+    pub(crate) denied_functions: HashSet<String>,
 }
 
 impl std::fmt::Debug for CompilerConfig {
@@ -45,6 +46,7 @@ impl std::fmt::Debug for CompilerConfig {
             .field("optimizer_operation_limit", &self.optimizer_operation_limit)
             .field("max_macro_expansions", &self.max_macro_expansions)
             .field("type_checker", &self.type_checker)
+            .field("denied_functions", &self.denied_functions)
             .finish()
     }
 }
@@ -74,6 +76,13 @@ impl CompilerConfig {
         self
     }
 
+    /// Deny the use of a function, making compilation fail if the expression calls it.
+    /// This applies to both built-in and custom functions.
+    pub fn deny_function(mut self, name: impl Into<String>) -> Self {
+        self.denied_functions.insert(name.into());
+        self
+    }
+
     /// Add a custom function to the compiler.
     /// This allows you to define custom functions in Rust and use them in your expressions.
     /// The function should implement the `DynamicFunction` and `FunctionExpression` traits,
@@ -107,6 +116,7 @@ impl Default for CompilerConfig {
             max_macro_expansions: 20,
             type_checker: TypeCheckerMode::Off,
             custom_function_source: DynamicFunctionSource::default(),
+            denied_functions: HashSet::new(),
         }
     }
 }
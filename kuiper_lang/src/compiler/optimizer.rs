@@ -17,6 +17,29 @@ fn is_deterministic(expr: &mut ExpressionType) -> bool {
     true
 }
 
+fn resolve_children(
+    root: &mut ExpressionType,
+    num_inputs: usize,
+    opcount: &mut i64,
+    max_opcount: i64,
+) -> Result<(), TransformError> {
+    // Errors inside a try expression are handled at runtime, so children that fail
+    // are left as they are.
+    let catches_errors = matches!(root, ExpressionType::Try(_));
+    for child in root.iter_children_mut() {
+        match resolve_constants(child, num_inputs, opcount, max_opcount) {
+            Ok(Some(res)) => *child = res,
+            Ok(None) => (),
+            Err(TransformError::OperationLimitExceeded) => {
+                return Err(TransformError::OperationLimitExceeded)
+            }
+            Err(_) if catches_errors => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
 fn resolve_constants(
     root: &mut ExpressionType,
     num_inputs: usize,
@@ -31,13 +54,7 @@ fn resolve_constants(
     if !is_deterministic(root) {
         // If the expression is not deterministic, we cannot optimize it,
         // and we shouldn't run it, but we can still try to optimize its children.
-        for child in root.iter_children_mut() {
-            let res: Option<ExpressionType> =
-                resolve_constants(child, num_inputs, opcount, max_opcount)?;
-            if let Some(res) = res {
-                *child = res;
-            }
-        }
+        resolve_children(root, num_inputs, opcount, max_opcount)?;
         return Ok(None);
     }
 
@@ -52,13 +69,7 @@ fn resolve_constants(
             // since any execution that is variable between runs would return a source missing error before anything else.
             TransformError::SourceMissingError(_) => {
                 // If the source is missing we should try to optimize each child.
-                for child in root.iter_children_mut() {
-                    let res: Option<ExpressionType> =
-                        resolve_constants(child, num_inputs, opcount, max_opcount)?;
-                    if let Some(res) = res {
-                        *child = res;
-                    }
-                }
+                resolve_children(root, num_inputs, opcount, max_opcount)?;
                 Ok(None)
             }
             _ => Err(e),
@@ -152,7 +163,7 @@ mod tests {
     pub fn test_divide_by_zero() {
         let err = parse_fail_optimizer("2 / 0", &[]);
         match err {
-            TransformError::InvalidOperation(d) => {
+            TransformError::DivideByZero(d) => {
                 assert_eq!(d.desc, "Divide by zero");
                 assert_eq!(d.span, Span { start: 2, end: 3 });
             }
@@ -0,0 +1,1589 @@
+// This file is automatically created by kuiper_documentation/codegen.py. Do not edit it directly.
+//
+// To change the content of this file, edit kuiper_documentation/functions.yaml instead.
+
+use serde::Serialize;
+
+/// An example of how to use a built-in function.
+#[derive(Serialize, Debug)]
+pub struct FunctionExample {
+    /// Example expression.
+    pub input: &'static str,
+    /// Result of the expression, if it is deterministic.
+    pub output: Option<&'static str>,
+}
+
+/// Documentation for a built-in function.
+#[derive(Serialize, Debug)]
+pub struct FunctionDef {
+    /// Name of the function.
+    pub name: &'static str,
+    /// Signature of the function, like `pow(x, y)`.
+    pub signature: &'static str,
+    /// Description of the function, in markdown.
+    pub description: &'static str,
+    /// Examples of how to use the function.
+    pub examples: &'static [FunctionExample],
+}
+
+/// All built-in functions, sorted by name.
+pub static FUNCTIONS: &[FunctionDef] = &[
+    FunctionDef {
+        name: "acos",
+        signature: "acos(x)",
+        description: "Return the inverse cosine of `x` in radians between 0 and pi.",
+        examples: &[
+            FunctionExample {
+                input: "acos(0)",
+                output: Some("1.5707963267948966"),
+            },
+            FunctionExample {
+                input: "acos(1)",
+                output: Some("0.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "all",
+        signature: "all(x)",
+        description: "Return `true` if all items in the array `x` are true.",
+        examples: &[
+            FunctionExample {
+                input: "[true, false, false, true].all()",
+                output: Some("false"),
+            },
+            FunctionExample {
+                input: "[true, true, true, true].all()",
+                output: Some("true"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "any",
+        signature: "any(x)",
+        description: "Return `true` if any item in the array `x` is true.",
+        examples: &[
+            FunctionExample {
+                input: "[true, false, false, true].any()",
+                output: Some("true"),
+            },
+            FunctionExample {
+                input: "[false, false, false, false].any()",
+                output: Some("false"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "asin",
+        signature: "asin(x)",
+        description: "Return the inverse sine of `x` in radians between -pi/2 and pi/2.",
+        examples: &[
+            FunctionExample {
+                input: "asin(0)",
+                output: Some("0.0"),
+            },
+            FunctionExample {
+                input: "asin(1)",
+                output: Some("1.5707963267948966"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "atan",
+        signature: "atan(x)",
+        description: "Return the inverse tangent of `x` in radians between -pi/2 and pi/2.",
+        examples: &[
+            FunctionExample {
+                input: "atan(0)",
+                output: Some("0.0"),
+            },
+            FunctionExample {
+                input: "atan(1)",
+                output: Some("0.7853981633974483"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "atan2",
+        signature: "atan2(x, y)",
+        description: "Return the inverse tangent of `x`/`y` in radians between -pi and pi.",
+        examples: &[
+            FunctionExample {
+                input: "atan2(3, 2)",
+                output: Some("0.982793723247329"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "avg",
+        signature: "avg(x)",
+        description: "Return the mean of the numbers in the array `x`, as a float. Returns `null` if `x` is empty.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4].avg()",
+                output: Some("2.5"),
+            },
+            FunctionExample {
+                input: "[].avg()",
+                output: Some("null"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "binary_search",
+        signature: "binary_search(x, a)",
+        description: "Return the index of `a` in the sorted array `x`, or -1 if `x` does not contain `a`. This is faster than\n[index_of](#index_of) for large arrays, but `x` must be sorted in ascending order, and contain only numbers or only strings.\nIf `x` contains `a` more than once, any of the matching indices may be returned.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 3, 5, 7, 9].binary_search(7)",
+                output: Some("3"),
+            },
+            FunctionExample {
+                input: "[\"apple\", \"banana\", \"cherry\"].binary_search(\"kiwi\")",
+                output: Some("-1"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "cartesian_product",
+        signature: "cartesian_product(x, y)",
+        description: "Return every combination of an element from the list `x` with an element from the list `y`,\nas objects with the fields `left` and `right`.",
+        examples: &[
+            FunctionExample {
+                input: "cartesian_product([1, 2], [\"a\", \"b\"])",
+                output: Some("[{ \"left\": 1, \"right\": \"a\" }, { \"left\": 1, \"right\": \"b\" }, { \"left\": 2, \"right\": \"a\" }, { \"left\": 2, \"right\": \"b\" }]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "case",
+        signature: "case(x, c1, r1, c2, r2, ..., (default))",
+        description: "Compare `x` to each of `c1`, `c2`, etc. and return the matching `r1`, `r2` of the first match. If no entry matches, a final optional expression can be returned as default.",
+        examples: &[
+            FunctionExample {
+                input: "case(\"b\", \"a\", 1, \"b\", 2, \"c\", 3, 0)",
+                output: Some("2"),
+            },
+            FunctionExample {
+                input: "case(\"d\", \"a\", 1, \"b\", 2, \"c\", 3, 0)",
+                output: Some("0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "ceil",
+        signature: "ceil(x)",
+        description: "Return `x` rounded up to the nearest integer.",
+        examples: &[
+            FunctionExample {
+                input: "ceil(16.2)",
+                output: Some("17"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "chars",
+        signature: "chars(x)",
+        description: "Create an array of characters from a string.",
+        examples: &[
+            FunctionExample {
+                input: "\"test\".chars()",
+                output: Some("[\"t\", \"e\", \"s\", \"t\"]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "chunk",
+        signature: "chunk(x, s)",
+        description: "Convert the list `x` into several lists of length at most `s`.",
+        examples: &[
+            FunctionExample {
+                input: "chunk([1, 2, 3, 4, 5, 6, 7], 3)",
+                output: Some("[[1, 2, 3], [4, 5, 6], [7]]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "coalesce",
+        signature: "coalesce(a, b, ...)",
+        description: "Return the first non-null value in the list of values.",
+        examples: &[
+            FunctionExample {
+                input: "coalesce(null, \"a\", \"b\")",
+                output: Some("\"a\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "concat",
+        signature: "concat(x, y, ...)",
+        description: "Concatenate any number of strings.",
+        examples: &[
+            FunctionExample {
+                input: "concat(\"Hello, \", \"world!\")",
+                output: Some("\"Hello, world!\""),
+            },
+            FunctionExample {
+                input: "{\"externalId\": concat(\"some-prefix:\", \"my-tag\")}",
+                output: Some("{\"externalId\": \"some-prefix:my-tag\"}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "contains",
+        signature: "contains(x, a)",
+        description: "Return `true` if the array or string `x` contains item `a`.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4].contains(4)",
+                output: Some("true"),
+            },
+            FunctionExample {
+                input: "\"hello world\".contains(\"llo wo\")",
+                output: Some("true"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "cos",
+        signature: "cos(x)",
+        description: "Return the cosine of `x`, where `x` is in radians.",
+        examples: &[
+            FunctionExample {
+                input: "cos(0)",
+                output: Some("1.0"),
+            },
+            FunctionExample {
+                input: "cos(3.141592653589793 / 2)",
+                output: Some("0.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "crc32",
+        signature: "crc32(x)",
+        description: "Compute the CRC-32 checksum of `x`, as an integer.\n`x` is either a string, which is checksummed as UTF-8, or an array of bytes, given as integers between 0 and 255.",
+        examples: &[
+            FunctionExample {
+                input: "crc32(\"123456789\")",
+                output: Some("3421780262"),
+            },
+            FunctionExample {
+                input: "crc32([104, 105])",
+                output: Some("3633523372"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "digest",
+        signature: "digest(a, b, ...)",
+        description: "Compute the SHA256 hash of the list of values.",
+        examples: &[
+            FunctionExample {
+                input: "digest(\"foo\", \"bar\", 123, [1, 2, 3])",
+                output: Some("lDN5G9Qz3fKZM6joQq+1OdF8P1rs2WYrgawlFXflqss="),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "distinct",
+        signature: "distinct(x)",
+        description: "Return the list `x` with duplicate elements removed, keeping the first occurrence of each. Elements are compared by value, so objects with the same fields are equal regardless of field order.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 1, \"a\", \"a\", {\"b\": 1}, {\"b\": 1}].distinct()",
+                output: Some("[1, 2, \"a\", {\"b\": 1}]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "distinct_by",
+        signature: "distinct_by(x, (a(, b)) => ...)",
+        description: "Return a list or object where the elements are distinct by the returned value of the given lambda function. The lambda function either takes list values, or object (value, key) pairs.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4, 5].distinct_by(x => x % 2)",
+                output: Some("[1, 2]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "ends_with",
+        signature: "ends_with(item, substring)",
+        description: "Return `true` if `item` ends with `substring`.",
+        examples: &[
+            FunctionExample {
+                input: "\"hello world\".ends_with(\"world\")",
+                output: Some("true"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "except",
+        signature: "except(x, (v(, k)) => ...)` or `except(x, l)",
+        description: "Return a list or object where keys or entries matching the predicate have been removed.\nIf the second argument is a lambda, it will be given the entry and if it returns `true`, the entry is removed.\nIf the second argument is a list, any entry also found in this list will be removed.",
+        examples: &[
+            FunctionExample {
+                input: "{\n    \"x-axis\": 13.6,\n    \"y-axis\": 63.1,\n    \"z-axis\": 1.4,\n    \"offset\": 4.3,\n    \"power\": \"on\"\n}.except([\"offset\", \"power\"])",
+                output: Some("{\n    \"x-axis\": 13.6,\n    \"y-axis\": 63.1,\n    \"z-axis\": 1.4\n}"),
+            },
+            FunctionExample {
+                input: "{\n    \"a\": 1,\n    \"b\": 2,\n    \"c\": 3,\n    \"d\": 4\n}.except((v, k) => v > 2)",
+                output: Some("{\n    \"a\": 1,\n    \"b\": 2\n}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "exp",
+        signature: "exp(x)",
+        description: "Return e to the power of `x`.",
+        examples: &[
+            FunctionExample {
+                input: "exp(1)",
+                output: Some("2.718281828459045"),
+            },
+            FunctionExample {
+                input: "exp(10)",
+                output: Some("22026.465794806718"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "filter",
+        signature: "filter(x, (it(, index)) => ...)",
+        description: "Remove any item from the list `x` where the lambda function returns `false` or `null`. The lambda takes an optional second input which is the index of the item in the list.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4].filter(item => item > 2)",
+                output: Some("[3, 4]"),
+            },
+            FunctionExample {
+                input: "[{\"value\": 1.5}, {\"value\": \"n/a\"}, {\"value\": 2.0}].filter(dp => dp.value is number)",
+                output: Some("[{\"value\": 1.5}, {\"value\": 2.0}]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "flatmap",
+        signature: "flatmap(x, (it(, index)) => ...)",
+        description: "Apply the lambda function to every item in the list `x` and flatten the result. The lambda takes an optional second input which is the index of the item in the list.\n\nFor example, if the lambda function returns a list, the result of the `flatmap` will just be a list instead of a list of lists.",
+        examples: &[
+            FunctionExample {
+                input: "[[1, 2, 3], [2, 3, 4], [3, 4, 5]].flatmap(list => list.map(item => item + 1))",
+                output: Some("[2, 3, 4, 3, 4, 5, 4, 5, 6]"),
+            },
+            FunctionExample {
+                input: "[{\"tag\": \"sensor-1\", \"values\": [1.5, 2.0]}, {\"tag\": \"sensor-2\", \"values\": [3.0]}].flatmap(ts =>\n    ts.values.map(v => {\"externalId\": ts.tag, \"value\": v})\n)",
+                output: Some("[{\"externalId\": \"sensor-1\", \"value\": 1.5}, {\"externalId\": \"sensor-1\", \"value\": 2.0}, {\"externalId\": \"sensor-2\", \"value\": 3.0}]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "flatten_object",
+        signature: "flatten_object(x(, separator(, arrays)))",
+        description: "Flatten the nested object `x` into an object with a single level, where each key is the path to a value joined by `separator`, which defaults to `.`.\nIf `arrays` is `true`, arrays are flattened as well, using the index of each element as key. Otherwise arrays are kept as values. Empty objects and arrays are kept as values.",
+        examples: &[
+            FunctionExample {
+                input: "flatten_object({\"a\": {\"b\": 1, \"c\": [1, 2]}, \"d\": 3})",
+                output: Some("{\"a.b\": 1, \"a.c\": [1, 2], \"d\": 3}"),
+            },
+            FunctionExample {
+                input: "flatten_object({\"a\": {\"b\": 1, \"c\": [1, 2]}}, \"_\", true)",
+                output: Some("{\"a_b\": 1, \"a_c_0\": 1, \"a_c_1\": 2}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "fletcher16",
+        signature: "fletcher16(x)",
+        description: "Compute the Fletcher-16 checksum of `x`, as an integer.\n`x` is either a string, which is checksummed as UTF-8, or an array of bytes, given as integers between 0 and 255.",
+        examples: &[
+            FunctionExample {
+                input: "fletcher16(\"abcde\")",
+                output: Some("51440"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "float",
+        signature: "float(x)",
+        description: "Convert `x` into a floating point number if possible. If the conversion fails, the whole mapping will fail.\n\nConsider using [try_float](#try_float) instead if you need error handling.",
+        examples: &[
+            FunctionExample {
+                input: "float(\"6.1\")",
+                output: Some("6.1"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "floor",
+        signature: "floor(x)",
+        description: "Return `x` rounded down to the nearest integer.",
+        examples: &[
+            FunctionExample {
+                input: "floor(16.2)",
+                output: Some("16"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "format_timestamp",
+        signature: "format_timestamp(x, f)",
+        description: "Convert the Unix timestamp `x` into a string representation based on the format `f`.\n\nThe format is given using the table found [here](https://docs.rs/chrono/latest/chrono/format/strftime/index.html).",
+        examples: &[
+            FunctionExample {
+                input: "format_timestamp(1694159249120, \"%Y-%m-%d %H:%M:%S\")",
+                output: Some("\"2023-09-08 07:47:29\""),
+            },
+            FunctionExample {
+                input: "format_timestamp(now(), \"%d/%m - %Y\")",
+                output: Some("\"08/09 - 2023\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "geo_distance",
+        signature: "geo_distance(lat1, lon1, lat2, lon2)",
+        description: "Return the great-circle distance in meters between the points (`lat1`, `lon1`) and (`lat2`, `lon2`),\ngiven in degrees. The distance is computed with the haversine formula, treating the earth as a sphere\nwith a radius of 6371 km.",
+        examples: &[
+            FunctionExample {
+                input: "geo_distance(59.9139, 10.7522, 60.3913, 5.3221)",
+                output: Some("305066.7134297702"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "get",
+        signature: "get(x, path(, default))",
+        description: "Get the value at `path` in `x`, where `path` is a string like `a.b[0].c`. Use this when the path is not known until runtime, for example when it comes from the input data. Keys containing `.` or `[` can be quoted, as in `a['b.c']`, and negative indices count from the end of an array.\nIf nothing is found at `path`, return `default`, or `null` if `default` is not given.",
+        examples: &[
+            FunctionExample {
+                input: "get({\"a\": {\"b\": [1, 2, 3]}}, \"a.b[1]\")",
+                output: Some("2"),
+            },
+            FunctionExample {
+                input: "get({\"a\": {\"b\": [1, 2, 3]}}, \"a.c\", \"missing\")",
+                output: Some("\"missing\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "group_by",
+        signature: "group_by(x, (it(, index)) => ...)",
+        description: "Group the elements of the list `x` into an object, where the keys are the values returned by the lambda function, and the values are lists of the elements with that key, in their original order.\nThe lambda may return a string, number, boolean, or null. Null is grouped under the empty string.",
+        examples: &[
+            FunctionExample {
+                input: "[{\"deviceId\": \"a\", \"value\": 1}, {\"deviceId\": \"b\", \"value\": 2}, {\"deviceId\": \"a\", \"value\": 3}].group_by(x => x.deviceId)",
+                output: Some("{\"a\": [{\"deviceId\": \"a\", \"value\": 1}, {\"deviceId\": \"a\", \"value\": 3}], \"b\": [{\"deviceId\": \"b\", \"value\": 2}]}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "hex_decode",
+        signature: "hex_decode(x)",
+        description: "Decode the hexadecimal string `x`, with an optional `0x` prefix, into a string.\nFails if `x` is not valid hex, or if the decoded bytes are not valid UTF-8.",
+        examples: &[
+            FunctionExample {
+                input: "hex_decode(\"68656c6c6f\")",
+                output: Some("\"hello\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "hex_encode",
+        signature: "hex_encode(x)",
+        description: "Encode the UTF-8 bytes of the string `x` as lowercase hexadecimal.",
+        examples: &[
+            FunctionExample {
+                input: "hex_encode(\"hello\")",
+                output: Some("\"68656c6c6f\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "if",
+        signature: "if(x, y, (z))",
+        description: "Return `y` if `x` evaluates to `true`, otherwise return `z`, or `null` if `z` is omitted.\nThe conditional operator `x ? y : z` is shorthand for `if(x, y, z)`.",
+        examples: &[
+            FunctionExample {
+                input: "if(false, \"yes\", \"no\")",
+                output: Some("\"no\""),
+            },
+            FunctionExample {
+                input: "if(true, \"on\", \"off\")",
+                output: Some("\"on\""),
+            },
+            FunctionExample {
+                input: "2 > 1 ? \"yes\" : \"no\"",
+                output: Some("\"yes\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "if_value",
+        signature: "if_value(item, item => ...)",
+        description: "Map a value using a lambda if the value is not null. This is useful if you need to combine parts of some complex object or result of a longer calculation.",
+        examples: &[
+            FunctionExample {
+                input: "\"hello\".if_value(a => concat(a, \" world\"))",
+                output: Some("\"hello world\""),
+            },
+            FunctionExample {
+                input: "null.if_value(a => a + 1)",
+                output: Some("null"),
+            },
+            FunctionExample {
+                input: "[1, 2, 3].if_value(a => a[0] + a[1] + a[2])",
+                output: Some("6"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "index_of",
+        signature: "index_of(x, a)",
+        description: "Return the index of the first occurrence of `a` in the array `x`, or -1 if `x` does not contain `a`.",
+        examples: &[
+            FunctionExample {
+                input: "[\"a\", \"b\", \"c\"].index_of(\"b\")",
+                output: Some("1"),
+            },
+            FunctionExample {
+                input: "[1, 2, 3].index_of(4)",
+                output: Some("-1"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "int",
+        signature: "int(x)",
+        description: "Convert `x` into an integer if possible. If the conversion fails, the whole mapping will fail.\n\nConsider using [try_int](#try_int) instead if you need error handling.",
+        examples: &[
+            FunctionExample {
+                input: "int(\"6\")",
+                output: Some("6"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "int_from_hex",
+        signature: "int_from_hex(x)",
+        description: "Parse the hexadecimal string `x`, with an optional `0x` prefix and sign, as an integer.",
+        examples: &[
+            FunctionExample {
+                input: "int_from_hex(\"0x1A2B\")",
+                output: Some("6699"),
+            },
+            FunctionExample {
+                input: "int_from_hex(\"ff\")",
+                output: Some("255"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "ip_in_cidr",
+        signature: "ip_in_cidr(ip, cidr)",
+        description: "Return `true` if the IP address `ip` is in the CIDR range `cidr`, for example `10.0.0.0/8`.\nAn IPv4 address is never in an IPv6 range, and vice versa.",
+        examples: &[
+            FunctionExample {
+                input: "ip_in_cidr(\"10.1.2.3\", \"10.0.0.0/8\")",
+                output: Some("true"),
+            },
+            FunctionExample {
+                input: "ip_in_cidr(\"2001:db8::1\", \"2001:db8::/32\")",
+                output: Some("true"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "ip_to_int",
+        signature: "ip_to_int(ip)",
+        description: "Convert the IPv4 address `ip` to an integer. IPv6 addresses are only supported if they are IPv4-mapped,\nsince other IPv6 addresses are too large to be represented as a number.",
+        examples: &[
+            FunctionExample {
+                input: "ip_to_int(\"10.0.0.1\")",
+                output: Some("167772161"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "join",
+        signature: "join(a, b, ...)",
+        description: "Return the union of the given objects or arrays. If a key is present in multiple objects, each instance of the key is overwritten by later objects. Arrays are simply merged.",
+        examples: &[
+            FunctionExample {
+                input: "join({\"key1\": \"value1\"}, {\"key2\": \"value2\"})",
+                output: Some("{\n    \"key1\": \"value1\",\n    \"key2\": \"value2\"\n}"),
+            },
+            FunctionExample {
+                input: "join([1, 2, 3], [4, 5], [6, 7, 8])",
+                output: Some("[1, 2, 3, 4, 5, 6, 7, 8]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "json_diff",
+        signature: "json_diff(a, b)",
+        description: "Compare `a` and `b`, and return a JSON patch, as defined in RFC 6902, that transforms `a` into `b`. The patch is a list of `add`, `remove`, and `replace` operations, and is empty if `a` and `b` are equal.\nUse this to only forward the fields that have changed. The patch can be applied with `json_patch`.",
+        examples: &[
+            FunctionExample {
+                input: "json_diff({\"a\": 1, \"b\": 2}, {\"a\": 1, \"b\": 3, \"c\": 4})",
+                output: Some("[{\"op\": \"replace\", \"path\": \"/b\", \"value\": 3}, {\"op\": \"add\", \"path\": \"/c\", \"value\": 4}]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "json_patch",
+        signature: "json_patch(x, patch)",
+        description: "Apply the JSON patch `patch`, as defined in RFC 6902, to `x`. `patch` is a list of operations, which may be `add`, `remove`, `replace`, `move`, `copy`, or `test`.\nThe expression fails if an operation cannot be applied, or if a `test` operation does not match. Each operation, and each value copied by a `copy` operation, counts towards the operation limit.",
+        examples: &[
+            FunctionExample {
+                input: "json_patch({\"a\": 1}, [{\"op\": \"add\", \"path\": \"/b\", \"value\": [2]}, {\"op\": \"remove\", \"path\": \"/a\"}])",
+                output: Some("{\"b\": [2]}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "json_pointer",
+        signature: "json_pointer(x, pointer(, default))",
+        description: "Get the value in `x` referenced by the JSON pointer `pointer`, as defined in RFC 6901. A pointer is either empty, referencing `x` itself, or a list of keys and array indices each prefixed by `/`, where `~1` is an escaped `/` and `~0` is an escaped `~`.\nIf nothing is found at `pointer`, return `default`, or `null` if `default` is not given. Use `to_json_pointer` to build a pointer from a list of keys.",
+        examples: &[
+            FunctionExample {
+                input: "json_pointer({\"a\": {\"b\": [1, 2, 3]}}, \"/a/b/1\")",
+                output: Some("2"),
+            },
+            FunctionExample {
+                input: "json_pointer({\"a/b\": 1}, \"/a~1b\")",
+                output: Some("1"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "length",
+        signature: "length(x)",
+        description: "Return the length of the list, string, or object `x`.",
+        examples: &[
+            FunctionExample {
+                input: "length(\"Hello, world\")",
+                output: Some("12"),
+            },
+            FunctionExample {
+                input: "length([1, 2, 3])",
+                output: Some("3"),
+            },
+            FunctionExample {
+                input: "length({\"a\": 1, \"b\": 2})",
+                output: Some("2"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "levenshtein",
+        signature: "levenshtein(a, b)",
+        description: "Return the Levenshtein distance between the strings `a` and `b`, which is the number of single character insertions, deletions, or substitutions needed to turn `a` into `b`. This takes time proportional to the product of the string lengths, and each pair of characters compared counts towards the operation limit.",
+        examples: &[
+            FunctionExample {
+                input: "levenshtein(\"kitten\", \"sitting\")",
+                output: Some("3"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "log",
+        signature: "log(x, y)",
+        description: "Return the base `y` logarithm of `x`.",
+        examples: &[
+            FunctionExample {
+                input: "log(16, 2)",
+                output: Some("4.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "lower",
+        signature: "lower(x)",
+        description: "Convert all characters in the string `x` to lowercase. If `x` is a boolean or number, it will be converted to a string.",
+        examples: &[
+            FunctionExample {
+                input: "\"Hello World\".lower()",
+                output: Some("\"hello world\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "map",
+        signature: "map(x, (it(, index)) => ...)",
+        description: "Apply the lambda function to every item in the list `x`. The lambda takes an optional second input which is the index of the item in the list.\n\nIf applied to an object, the first input is the value, and the second is the key. The result is the new value.\n\nIf the value is `null`, the lambda is ignored and `map` returns `null`.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4].map(number => number * 2)",
+                output: Some("[2, 4, 6, 8]"),
+            },
+            FunctionExample {
+                input: "[{\"value\": 1.5, \"tag\": \"sensor-1\"}, {\"value\": 2.0, \"tag\": \"sensor-2\"}].map(item => {\n    \"externalId\": concat(\"prefix:\", item.tag),\n    \"value\": item.value\n})",
+                output: Some("[{\"externalId\": \"prefix:sensor-1\", \"value\": 1.5}, {\"externalId\": \"prefix:sensor-2\", \"value\": 2.0}]"),
+            },
+            FunctionExample {
+                input: "[\"a\", \"b\", \"c\"].map((item, index) => index)",
+                output: Some("[0, 1, 2]"),
+            },
+            FunctionExample {
+                input: "{\"a\": 1, \"b\": 2, \"c\": 3}.map((value, key) => concat(value, key))",
+                output: Some("{\"a\": \"1a\", \"b\": \"2b\", \"c\": \"3c\"}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "mask",
+        signature: "mask(x, paths, (replacement))",
+        description: "Replace the fields in `x` matching any of `paths` with `replacement`, which defaults to `***`. `paths` is a single path or a list of paths,\nusing the same syntax as [get](#get), and keys may contain the wildcards `*` and `?`.\n\nA path that is a single key, like `password`, matches that key at any depth. Longer paths, like `user.email`,\nmatch from the root of `x`, and are applied to every element when they reach an array.",
+        examples: &[
+            FunctionExample {
+                input: "mask({\"user\": {\"name\": \"bob\", \"password\": \"hunter2\"}}, \"password\")",
+                output: Some("{ \"user\": { \"name\": \"bob\", \"password\": \"***\" } }"),
+            },
+            FunctionExample {
+                input: "mask({\"users\": [{\"email\": \"a@b.c\", \"id\": 1}], \"email\": \"x\"}, [\"users.email\", \"*_token\"], null)",
+                output: Some("{ \"email\": \"x\", \"users\": [{ \"email\": null, \"id\": 1 }] }"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "max",
+        signature: "max(a, b, ...)",
+        description: "Return the larger of the given numbers. Can also be used on an array.",
+        examples: &[
+            FunctionExample {
+                input: "max(1, 2)",
+                output: Some("2"),
+            },
+            FunctionExample {
+                input: "max(1, 5, 2.0, 6)",
+                output: Some("6.0"),
+            },
+            FunctionExample {
+                input: "[1, 8, 9, 2, 5, 4].max()",
+                output: Some("9"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "max_by",
+        signature: "max_by(x, (it(, index)) => ...)",
+        description: "Return the element of the list `x` for which the lambda function returns the largest value, or `null` if the list is empty.\nThe lambda must return only numbers or only strings. If several elements have the largest value, the first is returned.",
+        examples: &[
+            FunctionExample {
+                input: "[{\"timestamp\": 2, \"value\": 1.2}, {\"timestamp\": 1, \"value\": 3.4}].max_by(x => x.timestamp)",
+                output: Some("{\"timestamp\": 2, \"value\": 1.2}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "median",
+        signature: "median(x)",
+        description: "Return the median of the numbers in the array `x`. If `x` has an odd number of elements, the middle element is returned as is,\notherwise the result is the mean of the two middle elements, as a float. Returns `null` if `x` is empty.",
+        examples: &[
+            FunctionExample {
+                input: "[3, 1, 2].median()",
+                output: Some("2"),
+            },
+            FunctionExample {
+                input: "[4, 1, 3, 2].median()",
+                output: Some("2.5"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "min",
+        signature: "min(a, b, ...)",
+        description: "Return the smaller of the given numbers. Can also be used on an array.",
+        examples: &[
+            FunctionExample {
+                input: "min(1, 2)",
+                output: Some("1"),
+            },
+            FunctionExample {
+                input: "min(1, 5, 2.0, 6)",
+                output: Some("1.0"),
+            },
+            FunctionExample {
+                input: "[1, 8, 9, 2, 5, 4].min()",
+                output: Some("1"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "min_by",
+        signature: "min_by(x, (it(, index)) => ...)",
+        description: "Return the element of the list `x` for which the lambda function returns the smallest value, or `null` if the list is empty.\nThe lambda must return only numbers or only strings. If several elements have the smallest value, the first is returned.",
+        examples: &[
+            FunctionExample {
+                input: "[{\"timestamp\": 2, \"value\": 1.2}, {\"timestamp\": 1, \"value\": 3.4}].min_by(x => x.timestamp)",
+                output: Some("{\"timestamp\": 1, \"value\": 3.4}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "now",
+        signature: "now()",
+        description: "Return the current time as a millisecond Unix timestamp, that is, the number of milliseconds since midnight 1/1/1970 UTC.",
+        examples: &[
+            FunctionExample {
+                input: "{\n    \"timestamp\": now()\n}",
+                output: Some("{\n    \"timestamp\": 1694159249120\n}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "pairs",
+        signature: "pairs(x)",
+        description: "Convert the object `x` into a list of key/value pairs.",
+        examples: &[
+            FunctionExample {
+                input: "{\n    \"a\": 1,\n    \"b\": 2,\n    \"c\": 3\n}.pairs()",
+                output: Some("[{\n    \"key\": \"a\",\n    \"value\": 1\n}, {\n    \"key\": \"b\",\n    \"value\": 2\n}, {\n    \"key\": \"c\",\n    \"value\": 3\n}]"),
+            },
+            FunctionExample {
+                input: "{\n    \"x-axis\": 12.4,\n    \"y-axis\": 17.3,\n    \"z-axis\": 2.1\n}.pairs().map(kv => {\n    \"externalId\": kv.key,\n    \"value\": kv.value\n})",
+                output: Some("[{\"externalId\": \"x-axis\", \"value\": 12.4}, {\"externalId\": \"y-axis\", \"value\": 17.3}, {\"externalId\": \"z-axis\", \"value\": 2.1}]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "parse_ip",
+        signature: "parse_ip(x)",
+        description: "Parse the IPv4 or IPv6 address `x`. Returns an object with the fields `address`, the normalized address,\n`version`, either 4 or 6, and the flags `is_loopback`, `is_private`, and `is_multicast`.\n\nReturns `null` if `x` is not a valid IP address.",
+        examples: &[
+            FunctionExample {
+                input: "parse_ip(\"10.0.0.1\")",
+                output: Some("{ \"address\": \"10.0.0.1\", \"is_loopback\": false, \"is_multicast\": false, \"is_private\": true, \"version\": 4 }"),
+            },
+            FunctionExample {
+                input: "parse_ip(\"not an ip\")",
+                output: Some("null"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "parse_json",
+        signature: "parse_json(string)",
+        description: "Parse a string as a JSON object, which can be used in further transformations. If the passed value isn't a string, it's returned as-is.",
+        examples: &[
+            FunctionExample {
+                input: "parse_json(\"{\\\"a\\\": 1, \\\"b\\\": 2}\")",
+                output: Some("{\"a\": 1, \"b\": 2}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "parse_number",
+        signature: "parse_number(x, (options))",
+        description: "Parse the string `x` as a number, using the separators given in `options`. Fails if `x` cannot be parsed.\n\n`options` is an object with the fields `decimal`, the decimal separator, defaulting to `.`,\nand `thousands`, the thousands separator, defaulting to `,`, or `.` if `decimal` is `,`.\nThousands separators are removed, and are only allowed before the decimal separator, between groups of three digits.\n\nReturns an integer if the number has no fractional part or exponent, otherwise a float.\nNumbers are returned unchanged.\nConsider using [try_parse_number](#try_parse_number) instead if you need error handling.",
+        examples: &[
+            FunctionExample {
+                input: "parse_number(\"1.234,56\", { \"decimal\": \",\", \"thousands\": \".\" })",
+                output: Some("1234.56"),
+            },
+            FunctionExample {
+                input: "parse_number(\"1,000,000\")",
+                output: Some("1000000"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "percentile",
+        signature: "percentile(x, p)",
+        description: "Return the `p`th percentile of the numbers in the array `x`, where `p` is between 0 and 100.\nValues between two elements are linearly interpolated, so `percentile(x, 50)` is the median of `x`. Returns `null` if `x` is empty.",
+        examples: &[
+            FunctionExample {
+                input: "[15, 20, 35, 40, 50].percentile(50)",
+                output: Some("35.0"),
+            },
+            FunctionExample {
+                input: "[1, 2, 3, 4].percentile(25)",
+                output: Some("1.75"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "pow",
+        signature: "pow(x, y)",
+        description: "Return `x` to the power of `y`.",
+        examples: &[
+            FunctionExample {
+                input: "pow(5, 3)",
+                output: Some("125.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "random",
+        signature: "random()",
+        description: "Return a random floating-point number between 0.0 (inclusive) and 1.0 (exclusive).",
+        examples: &[
+            FunctionExample {
+                input: "random()",
+                output: Some("0.123456789"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "reduce",
+        signature: "reduce(x, (acc, val) => ..., init)",
+        description: "Return the value obtained by reducing the list `x`. The lambda function is called once for each element in the list `val`, and the returned value is passed as `acc` in the next iteration. The `init` will be given as the initial `acc` for the first call to the lambda function.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4, 5].reduce((acc, val) => acc + val, 0)",
+                output: Some("15"),
+            },
+            FunctionExample {
+                input: "[1, 2, 3, 4, 5].reduce((acc, val) => acc * val, 1)",
+                output: Some("120"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "regex_all_captures",
+        signature: "regex_all_captures(haystack, regex)",
+        description: "Return an array of objects containing all capture groups from each match of the regex in the haystack. Unnamed capture groups are named after their index, so the match itself is always included as capture group `0`. If no match is found, this returns an empty array.\nSee [regex_is_match](#regex_is_match) for details on regex support.",
+        examples: &[
+            FunctionExample {
+                input: "regex_all_captures(\"f123 f45 ff\", \"f(?<v>[0-9]+)\")",
+                output: Some("[{\n  \"0\": \"f123\",\n  \"v\": \"123\"\n}, {\n  \"0\": \"f45\",\n  \"v\": \"45\"\n}]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "regex_all_matches",
+        signature: "regex_all_matches(haystack, regex)",
+        description: "Return an array of all the substrings that match the regex. If no match is found, this returns an empty array. If you only need the first match, use [regex_first_match](#regex_first_match).\nSee [regex_is_match](#regex_is_match) for details on regex support.",
+        examples: &[
+            FunctionExample {
+                input: "regex_all_matches(\"tests\", \"t[a-z]\")",
+                output: Some("[\"te\", \"ts\"]"),
+            },
+            FunctionExample {
+                input: "regex_all_matches(\"foo bar baz\", \"\\\\w{3}\")",
+                output: Some("[\"foo\", \"bar\", \"baz\"]"),
+            },
+            FunctionExample {
+                input: "regex_all_matches(\"test\", \"not test\")",
+                output: Some("[]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "regex_first_captures",
+        signature: "regex_first_captures(haystack, regex)",
+        description: "Return an object containing all capture groups from the first match of the regex in the haystack. Unnamed capture groups are named after their index, so the match itself is always included as capture group `0`. If no match is found, this returns null.\nSee [regex_is_match](#regex_is_match) for details on regex support.",
+        examples: &[
+            FunctionExample {
+                input: "regex_first_captures(\"test foo bar\", \"test (?<v1>\\\\w{3}) (\\\\w{3})\")",
+                output: Some("{\n  \"0\": \"test foo bar\",\n  \"v1\": \"foo\",\n  \"2\": \"bar\"\n}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "regex_first_match",
+        signature: "regex_first_match(haystack, regex)",
+        description: "Return the first substring in the haystack that matches the regex. If no match is found, this returns `null`. Prefer [regex_is_match](#regex_is_match) if all you need is to check for the existence of a match.\nSee [regex_is_match](#regex_is_match) for details on regex support.",
+        examples: &[
+            FunctionExample {
+                input: "regex_first_match(\"test\", \"te\")",
+                output: Some("\"te\""),
+            },
+            FunctionExample {
+                input: "regex_first_match(\"test\", \"te[st]{2}\")",
+                output: Some("\"test\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "regex_is_match",
+        signature: "regex_is_match(haystack, regex)",
+        description: "Return `true` if the haystack matches the regex. Prefer this over the other regex methods if you only need to check for the presence of a match.\nWe support a limited form of regex without certain complex features, such as backreferences and look-around. See [all the available regex syntax](https://docs.rs/regex/1.11.0/regex/index.html#syntax). We recommend using [regex101](https://regex101.com/) with the mode set to `rust` for debugging regex.",
+        examples: &[
+            FunctionExample {
+                input: "regex_is_match(\"test\", \"te\")",
+                output: Some("true"),
+            },
+            FunctionExample {
+                input: "regex_is_match(\"test\", \"^not test$\")",
+                output: Some("false"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "regex_replace",
+        signature: "regex_replace(haystack, regex, replace)",
+        description: "Replace the first occurrence of the regex in the haystack. The replace object supports referencing capture groups using either the index (`$1`) or the name (`$group`). Use `$$` if you need a literal `$` symbol. `${group}` is equivalent to `$group` but lets you specify the group name exactly.\nSee [regex_is_match](#regex_is_match) for details on regex support.",
+        examples: &[
+            FunctionExample {
+                input: "regex_replace(\"test\", \"te(?<v>[st]{2})\", \"fa$v\")",
+                output: Some("\"fast\""),
+            },
+            FunctionExample {
+                input: "regex_replace(\"2024-05-01\", \"(\\\\d+)-(\\\\d+)-(\\\\d+)\", \"$3/$2/$1\")",
+                output: Some("\"01/05/2024\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "regex_replace_all",
+        signature: "regex_replace_all(haystack, regex, replace)",
+        description: "Replace each occurrence of the regex in the haystack. See [regex_replace](#regex_replace) for details.",
+        examples: &[
+            FunctionExample {
+                input: "regex_replace_all(\"tests\", \"t(?<v>[se])\", \"${v}t\")",
+                output: Some("etsst"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "regex_split",
+        signature: "regex_split(haystack, regex)",
+        description: "Split the haystack on each match of the regex, returning an array of strings. Use [split](#split) to split on a plain string.\nSee [regex_is_match](#regex_is_match) for details on regex support.",
+        examples: &[
+            FunctionExample {
+                input: "regex_split(\"a,b;c\", \"[,;]\")",
+                output: Some("[\"a\", \"b\", \"c\"]"),
+            },
+            FunctionExample {
+                input: "regex_split(\"foo  bar baz\", \"\\\\s+\")",
+                output: Some("[\"foo\", \"bar\", \"baz\"]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "repeat",
+        signature: "repeat(x, n)",
+        description: "Return the string `x` repeated `n` times. Fails if the result would be longer than 16 MiB.",
+        examples: &[
+            FunctionExample {
+                input: "concat(\"|\", repeat(\"-\", 10), \"|\")",
+                output: Some("\"|----------|\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "replace",
+        signature: "replace(a, b, c)",
+        description: "Replace occurrences of `b` in string `a` with `c`.",
+        examples: &[
+            FunctionExample {
+                input: "\"tomato\".replace(\"tomato\", \"potato\")",
+                output: Some("\"potato\""),
+            },
+            FunctionExample {
+                input: "replace(\"potato\", \"o\", \"a\")",
+                output: Some("\"patata\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "resample",
+        signature: "resample(x, interval, (method))",
+        description: "Resample the datapoints in `x`, a list of objects with `timestamp` and `value` fields, to a regular grid with\na spacing of `interval` milliseconds. The grid consists of every multiple of `interval` between the first and last timestamp.\n\n`method` is either `linear`, the default, which interpolates linearly between the surrounding datapoints,\nor `previous`, which uses the value of the last datapoint at or before each grid point. Linear interpolation requires numeric values.",
+        examples: &[
+            FunctionExample {
+                input: "resample([{\"timestamp\": 500, \"value\": 5}, {\"timestamp\": 2500, \"value\": 25}], 1000)",
+                output: Some("[{ \"timestamp\": 1000, \"value\": 10.0 }, { \"timestamp\": 2000, \"value\": 20.0 }]"),
+            },
+            FunctionExample {
+                input: "resample([{\"timestamp\": 0, \"value\": \"off\"}, {\"timestamp\": 15, \"value\": \"on\"}, {\"timestamp\": 20, \"value\": \"off\"}], 10, \"previous\")",
+                output: Some("[{ \"timestamp\": 0, \"value\": \"off\" }, { \"timestamp\": 10, \"value\": \"off\" }, { \"timestamp\": 20, \"value\": \"off\" }]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "round",
+        signature: "round(x)",
+        description: "Return `x` rounded to the nearest integer.",
+        examples: &[
+            FunctionExample {
+                input: "round(16.2)",
+                output: Some("16"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "select",
+        signature: "select(x, (v(, k)) => ...)` or `select(x, [1, 2, 3])",
+        description: "Return a list or object where the lambda returns true. If the second argument is a list, the list values or object keys found in that list are used to select from the source.",
+        examples: &[
+            FunctionExample {
+                input: "{\n    \"x-axis\": 13.6,\n    \"y-axis\": 63.1,\n    \"z-axis\": 1.4,\n    \"offset\": 4.3,\n    \"power\": \"on\"\n}.select([\"x-axis\", \"y-axis\", \"z-axis\"])",
+                output: Some("{\n    \"x-axis\": 13.6,\n    \"y-axis\": 63.1,\n    \"z-axis\": 1.4\n}"),
+            },
+            FunctionExample {
+                input: "{\n    \"a\": 1,\n    \"b\": 2,\n    \"c\": 3\n}.select((v, k) => v > 2)",
+                output: Some("{\n    \"c\": 3\n}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "semver_compare",
+        signature: "semver_compare(a, b)",
+        description: "Compare the semantic versions `a` and `b`, returning -1 if `a` is lower than `b`, 0 if they are equal, and 1 if `a` is greater.\nBuild metadata is ignored. A leading `v` is allowed, and missing minor and patch versions are treated as 0.",
+        examples: &[
+            FunctionExample {
+                input: "semver_compare(\"1.2.3\", \"1.10.0\")",
+                output: Some("-1"),
+            },
+            FunctionExample {
+                input: "semver_compare(\"v2.1\", \"2.1.0\")",
+                output: Some("0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "semver_satisfies",
+        signature: "semver_satisfies(version, requirement)",
+        description: "Return `true` if the semantic version `version` matches `requirement`, which is a comma separated list\nof comparisons such as `>=1.2.0, <2`. The requirement syntax is the same as in Cargo, so a plain version like `1.2` means `^1.2`.",
+        examples: &[
+            FunctionExample {
+                input: "semver_satisfies(\"1.4.0\", \">=1.2.0, <2\")",
+                output: Some("true"),
+            },
+            FunctionExample {
+                input: "semver_satisfies(\"2.0.1\", \"~2.1\")",
+                output: Some("false"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "similarity",
+        signature: "similarity(a, b)",
+        description: "Return how similar the strings `a` and `b` are, as a number between `0.0` and `1.0`, where `1.0` means they are equal. This is the Levenshtein distance between `a` and `b` divided by the length of the longest string, subtracted from `1.0`.\nUse this for fuzzy matching, for example `similarity(lower(a), lower(b)) > 0.8`.",
+        examples: &[
+            FunctionExample {
+                input: "similarity(\"Pump 1A\", \"Pump 1B\")",
+                output: Some("0.8571428571428572"),
+            },
+            FunctionExample {
+                input: "similarity(\"abc\", \"abc\")",
+                output: Some("1.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "sin",
+        signature: "sin(x)",
+        description: "Return the sine of `x`, where `x` is in radians.",
+        examples: &[
+            FunctionExample {
+                input: "sin(0)",
+                output: Some("0.0"),
+            },
+            FunctionExample {
+                input: "sin(3.141592653589793 / 2)",
+                output: Some("1.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "slice",
+        signature: "slice(x, start(, end))",
+        description: "Create a sub-array from an array `x` from `start` to `end`. If `end` is not specified, go from `start` to the end of the array. If `start` or `end` are negative, count from the end of the array.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4].slice(1, 3)",
+                output: Some("[2, 3]"),
+            },
+            FunctionExample {
+                input: "[1, 2, 3, 4].slice(0, -3)",
+                output: Some("[1]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "sort",
+        signature: "sort(x(, descending))",
+        description: "Sort the list `x` in ascending order, or descending order if `descending` is `true`.\nThe list must contain only numbers or only strings. Numbers are sorted numerically, and strings lexicographically.",
+        examples: &[
+            FunctionExample {
+                input: "[3, 1.5, 10, 2].sort()",
+                output: Some("[1.5, 2, 3, 10]"),
+            },
+            FunctionExample {
+                input: "[\"b\", \"c\", \"a\"].sort(true)",
+                output: Some("[\"c\", \"b\", \"a\"]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "sort_by",
+        signature: "sort_by(x, (it(, index)) => ...(, descending))",
+        description: "Sort the list `x` by the value returned by the lambda function for each element, in ascending order, or descending order if `descending` is `true`.\nThe lambda must return only numbers or only strings. The sort is stable, so elements with equal keys keep their original order.",
+        examples: &[
+            FunctionExample {
+                input: "[{\"timestamp\": 3, \"value\": 1.2}, {\"timestamp\": 1, \"value\": 3.4}, {\"timestamp\": 2, \"value\": 5.6}].sort_by(x => x.timestamp)",
+                output: Some("[{\"timestamp\": 1, \"value\": 3.4}, {\"timestamp\": 2, \"value\": 5.6}, {\"timestamp\": 3, \"value\": 1.2}]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "sort_with",
+        signature: "sort_with(x, (a, b) => ...)",
+        description: "Sort the list `x` using a comparator function. The function is called with two elements `a` and `b`, and should return\na negative number if `a` should come before `b`, a positive number if `a` should come after `b`, and 0 if they are equal.\nThe sort is stable, so elements that compare as equal keep their original order.",
+        examples: &[
+            FunctionExample {
+                input: "[3, 1, 2].sort_with((a, b) => b - a)",
+                output: Some("[3, 2, 1]"),
+            },
+            FunctionExample {
+                input: "[{\"n\": \"b\", \"v\": 1}, {\"n\": \"a\", \"v\": 1}, {\"n\": \"c\", \"v\": 2}].sort_with((a, b) => if(a.v != b.v, b.v - a.v, if(a.n < b.n, -1, 1))).map(x => x.n)",
+                output: Some("[\"c\", \"a\", \"b\"]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "split",
+        signature: "split(a, b)",
+        description: "Split string `a` on any occurrences of `b`. If `b` is an empty string, this will split on each character, including before the first and after the last. Use [regex_split](#regex_split) to split on a regex.",
+        examples: &[
+            FunctionExample {
+                input: "\"hello world\".split(\" \")",
+                output: Some("[\"hello\", \"world\"]"),
+            },
+            FunctionExample {
+                input: "\"hello\".split(\"\")",
+                output: Some("[\"\", \"h\", \"e\", \"l\", \"l\", \"o\", \"\"]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "sqrt",
+        signature: "sqrt(x)",
+        description: "Return the square root of `x`.",
+        examples: &[
+            FunctionExample {
+                input: "sqrt(16)",
+                output: Some("4.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "starts_with",
+        signature: "starts_with(item, substring)",
+        description: "Return `true` if `item` starts with `substring`.",
+        examples: &[
+            FunctionExample {
+                input: "\"hello world\".starts_with(\"hello\")",
+                output: Some("true"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "stddev",
+        signature: "stddev(x)",
+        description: "Return the population standard deviation of the numbers in the array `x`. Returns `null` if `x` is empty.",
+        examples: &[
+            FunctionExample {
+                input: "[2, 4, 4, 4, 5, 5, 7, 9].stddev()",
+                output: Some("2.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "string",
+        signature: "string(x)",
+        description: "Convert `x` into a string.\n\n`null`s will be converted into empty strings.",
+        examples: &[
+            FunctionExample {
+                input: "string(true)",
+                output: Some("\"true\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "string_join",
+        signature: "string_join(x(, a))",
+        description: "Return a string with all the elements of `x`, separated by `a`. If `a` is omitted, the strings will be joined without any separator.",
+        examples: &[
+            FunctionExample {
+                input: "[\"hello\", \"there\"].string_join(\" \")",
+                output: Some("\"hello there\""),
+            },
+            FunctionExample {
+                input: "[1, 2, 3].string_join()",
+                output: Some("\"123\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "substring",
+        signature: "substring(x, start(, end))",
+        description: "Create a substring of an input string `x` from `start` to `end`. If `end` is not specified, go from `start` to end of string. If `start` or `end` are negative, count from the end of the string.",
+        examples: &[
+            FunctionExample {
+                input: "\"hello world\".substring(3, 8)",
+                output: Some("\"lo wo\""),
+            },
+            FunctionExample {
+                input: "\"hello world\".substring(0, -3)",
+                output: Some("\"hello wo\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "sum",
+        signature: "sum(x)",
+        description: "Sum the numbers in the array `x`.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4].sum()",
+                output: Some("10"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "tail",
+        signature: "tail(x(, n))",
+        description: "Take the last element of the list `x`. If `n` is given, takes the last `n` elements, and returns a list if `n` > 1.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4, 5].tail()",
+                output: Some("5"),
+            },
+            FunctionExample {
+                input: "[1, 2, 3, 4, 5].tail(2)",
+                output: Some("[4, 5]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "tan",
+        signature: "tan(x)",
+        description: "Return the tangent of `x`, where `x` is in radians.",
+        examples: &[
+            FunctionExample {
+                input: "tan(0)",
+                output: Some("0.0"),
+            },
+            FunctionExample {
+                input: "tan(3.141592653589793 / 4)",
+                output: Some("1.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "to_json_pointer",
+        signature: "to_json_pointer(x)",
+        description: "Build a JSON pointer from the list of keys and indices `x`, escaping `~` and `/` in each key.",
+        examples: &[
+            FunctionExample {
+                input: "to_json_pointer([\"a/b\", \"c\", 0])",
+                output: Some("\"/a~1b/c/0\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "to_object",
+        signature: "to_object(x, val => ...(, val => ...))",
+        description: "Convert the array `x` into an object by producing the key and value from two lambdas.\n\nThe first lambda produces the key, and the second (optional) produces the value. If the second is\nleft out, the input is used as a value directly.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3].to_object(v => string(v + 1))",
+                output: Some("{ \"2\": 1, \"3\": 2, \"4\": 3 }"),
+            },
+            FunctionExample {
+                input: "[1, 2, 3].to_object(v => string(v + 1), v => v - 1)",
+                output: Some("{ \"2\": 0, \"3\": 1, \"4\": 2 }"),
+            },
+            FunctionExample {
+                input: "{\"a\": 1, \"b\": 2, \"c\": 3}.pairs().to_object(pair => pair.key, pair => pair.value)",
+                output: Some("{\"a\": 1, \"b\": 2, \"c\": 3}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "to_unix_timestamp",
+        signature: "to_unix_timestamp(x, f)",
+        description: "Convert the string `x` into a millisecond Unix timestamp using the format string `f`.\n\nThe format is given using the table found [here](https://docs.rs/chrono/latest/chrono/format/strftime/index.html).",
+        examples: &[
+            FunctionExample {
+                input: "to_unix_timestamp(\"2023-05-01 12:43:23\", \"%Y-%m-%d %H:%M:%S\")",
+                output: Some("1682945003000"),
+            },
+            FunctionExample {
+                input: "{\n    \"timestamp\": to_unix_timestamp(\"2023-05-01 12:43:23\", \"%Y-%m-%d %H:%M:%S\")\n}",
+                output: Some("{\n    \"timestamp\": 1682945003000\n}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "translate",
+        signature: "translate(x, from, to)",
+        description: "Replace characters in the string `x` found in the string `from` with the corresponding character in the string `to`. If `to` and `from` are of different lengths, the expression will fail.",
+        examples: &[
+            FunctionExample {
+                input: "\"hello world\".translate(\"he\", \"HE\")",
+                output: Some("\"HEllo world\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "transpose",
+        signature: "transpose(x)",
+        description: "Transpose the list of lists `x`, turning rows into columns. If the lists have different lengths,\nshorter lists are padded with `null`.",
+        examples: &[
+            FunctionExample {
+                input: "transpose([[1, 2, 3], [4, 5, 6]])",
+                output: Some("[[1, 4], [2, 5], [3, 6]]"),
+            },
+            FunctionExample {
+                input: "transpose([[1, 2], [3]])",
+                output: Some("[[1, 3], [2, null]]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "trim_whitespace",
+        signature: "trim_whitespace(x)",
+        description: "Remove any whitespace from the start and end of `x`.",
+        examples: &[
+            FunctionExample {
+                input: "\"  hello   \".trim_whitespace()",
+                output: Some("\"hello\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "try_bool",
+        signature: "try_bool(a, b)",
+        description: "Try to convert `a` to a boolean; if it fails, return `b`.",
+        examples: &[
+            FunctionExample {
+                input: "try_bool(\"true\", null)",
+                output: Some("true"),
+            },
+            FunctionExample {
+                input: "try_bool(\"foo\", null)",
+                output: Some("null"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "try_float",
+        signature: "try_float(a, b)",
+        description: "Try to convert `a` to a float; if it fails, return `b`.",
+        examples: &[
+            FunctionExample {
+                input: "try_float(\"6.2\", 1.2)",
+                output: Some("6.2"),
+            },
+            FunctionExample {
+                input: "try_float(\"4,5\", null)",
+                output: Some("4.5"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "try_int",
+        signature: "try_int(a, b)",
+        description: "Try to convert `a` to an int; if it fails, return `b`.",
+        examples: &[
+            FunctionExample {
+                input: "try_int(\"6\", 1)",
+                output: Some("6"),
+            },
+            FunctionExample {
+                input: "try_int(\"4\", null)",
+                output: Some("4"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "try_parse_number",
+        signature: "try_parse_number(x, default, (options))",
+        description: "Try to parse `x` as a number, like [parse_number](#parse_number); if it fails, return `default`.",
+        examples: &[
+            FunctionExample {
+                input: "try_parse_number(\"12,5\", null, { \"decimal\": \",\" })",
+                output: Some("12.5"),
+            },
+            FunctionExample {
+                input: "try_parse_number(\"n/a\", 0)",
+                output: Some("0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "unflatten_object",
+        signature: "unflatten_object(x(, separator(, arrays)))",
+        description: "Convert the flat object `x` into a nested object by splitting each key on `separator`, which defaults to `.`. This is the inverse of `flatten_object`.\nIf `arrays` is `true`, nested objects whose keys are exactly the indices `0` to `n - 1` are converted into arrays. The result itself is always an object. The expression fails if a key is both a value and an object, for example if `x` contains both `a` and `a.b`.",
+        examples: &[
+            FunctionExample {
+                input: "unflatten_object({\"a.b\": 1, \"a.c\": 2, \"d\": 3})",
+                output: Some("{\"a\": {\"b\": 1, \"c\": 2}, \"d\": 3}"),
+            },
+            FunctionExample {
+                input: "unflatten_object({\"a_b\": 1, \"a_c_0\": 1, \"a_c_1\": 2}, \"_\", true)",
+                output: Some("{\"a\": {\"b\": 1, \"c\": [1, 2]}}"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "upper",
+        signature: "upper(x)",
+        description: "Convert all characters in the string `x` to uppercase. If `x` is a boolean or number, it will be converted to a string first.",
+        examples: &[
+            FunctionExample {
+                input: "\"Hello World\".upper()",
+                output: Some("\"HELLO WORLD\""),
+            },
+            FunctionExample {
+                input: "true.upper()",
+                output: Some("\"TRUE\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "uuid4",
+        signature: "uuid4()",
+        description: "Generate a random UUID (version 4) and return it as a string.",
+        examples: &[
+            FunctionExample {
+                input: "uuid4()",
+                output: Some("\"a3bb189e-8bf9-3888-9912-ace4e6543002\""),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "variance",
+        signature: "variance(x)",
+        description: "Return the population variance of the numbers in the array `x`. Returns `null` if `x` is empty.",
+        examples: &[
+            FunctionExample {
+                input: "[2, 4, 4, 4, 5, 5, 7, 9].variance()",
+                output: Some("4.0"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "window",
+        signature: "window(x, n)",
+        description: "Return every overlapping window of `n` consecutive elements in the list `x`. If `x` has fewer than `n` elements, the result is empty.",
+        examples: &[
+            FunctionExample {
+                input: "[1, 2, 3, 4].window(2)",
+                output: Some("[[1, 2], [2, 3], [3, 4]]"),
+            },
+            FunctionExample {
+                input: "[1, 4, 9, 16].window(2).map(w => w[1] - w[0])",
+                output: Some("[3, 5, 7]"),
+            },
+        ],
+    },
+    FunctionDef {
+        name: "zip",
+        signature: "zip(x, y, ..., (i1, i2, ...) => ...)",
+        description: "Take a number of arrays, call the given lambda function on each entry, and return a single array from the result of each call. The returned array will be as long as the longest argument, null will be given for the shorter input arrays when they run out.",
+        examples: &[
+            FunctionExample {
+                input: "zip([1, 2, 3], [\"a\", \"b\", \"c\"], (a, b) => concat(a, b))",
+                output: Some("[\"1a\", \"2b\", \"3c\"]"),
+            },
+        ],
+    },
+];
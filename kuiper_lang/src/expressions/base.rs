@@ -7,7 +7,7 @@ use crate::{
     compiler::BuildError,
     expressions::{
         run_builder::ExpressionRunBuilder, source::SourceData,
-        template_string::TemplateStringExpression, DefineExpression,
+        template_string::TemplateStringExpression, DefineExpression, TryExpression,
     },
     functions::DynamicFunction,
     types::{Type, TypeError, TypeExecutionState},
@@ -18,9 +18,19 @@ use self::objects::ToObjectFunction;
 
 use super::{
     functions::{
-        distinct_by::DistinctByFunction, except::ExceptFunction, filter::FilterFunction,
-        flatmap::FlatMapFunction, map::MapFunction, reduce::ReduceFunction, select::SelectFunction,
-        zip::ZipFunction, *,
+        distinct_by::DistinctByFunction,
+        except::ExceptFunction,
+        filter::FilterFunction,
+        flatmap::FlatMapFunction,
+        group_by::GroupByFunction,
+        map::MapFunction,
+        min_max_by::{MaxByFunction, MinByFunction},
+        reduce::ReduceFunction,
+        select::SelectFunction,
+        sort::{SortByFunction, SortFunction},
+        sort_with::SortWithFunction,
+        zip::ZipFunction,
+        *,
     },
     is_operator::IsExpression,
     lambda::LambdaExpression,
@@ -32,16 +42,21 @@ use super::{
 };
 
 use kuiper_lang_macros::PassThrough;
+use smallvec::SmallVec;
 
 /// Type for storing completions collected during expression execution.
 #[cfg(feature = "completions")]
 pub type Completions = std::collections::HashMap<Span, std::collections::HashSet<String>>;
 
+/// Inputs available to an expression during execution. Lambdas add their arguments to this
+/// for every call, and there are almost always only a few, so they are stored inline.
+pub(crate) type SourceValues<'data> = SmallVec<[Option<&'data dyn SourceData>; 8]>;
+
 /// State for expression execution. This struct is constructed for each expression.
 /// Notably lifetime heavy. `'a` is the lifetime of the input data.
 /// `'b` is the lifetime of the transform execution, so the temporary data in the transform.
 pub struct ExpressionExecutionState<'data, 'exec> {
-    data: &'exec Vec<Option<&'data dyn SourceData>>,
+    data: &'exec [Option<&'data dyn SourceData>],
     opcount: &'exec mut i64,
     max_opcount: i64,
     #[cfg(feature = "completions")]
@@ -61,7 +76,7 @@ impl<'data, 'exec> ExpressionExecutionState<'data, 'exec> {
     }
 
     pub(crate) fn new(
-        data: &'exec Vec<Option<&'data dyn SourceData>>,
+        data: &'exec [Option<&'data dyn SourceData>],
         opcount: &'exec mut i64,
         max_opcount: i64,
     ) -> Self {
@@ -82,10 +97,8 @@ impl<'data, 'exec> ExpressionExecutionState<'data, 'exec> {
     where
         'data: 'inner,
     {
-        let mut data = Vec::with_capacity(self.data.len() + num_values);
-        for elem in self.data.iter() {
-            data.push(*elem);
-        }
+        let mut data = SourceValues::with_capacity(self.data.len() + num_values);
+        data.extend_from_slice(self.data);
         let mut pushed = 0;
         for elem in extra_values.take(num_values) {
             data.push(Some(elem));
@@ -121,6 +134,19 @@ impl<'data, 'exec> ExpressionExecutionState<'data, 'exec> {
         }
     }
 
+    /// Increment the operation count by `count`, for functions that do work proportional
+    /// to the size of their input, and check if it exceeds the maximum.
+    pub(crate) fn inc_ops(&mut self, count: usize) -> Result<(), TransformError> {
+        *self.opcount = self
+            .opcount
+            .saturating_add(i64::try_from(count).unwrap_or(i64::MAX));
+        if *self.opcount > self.max_opcount && self.max_opcount > 0 {
+            Err(TransformError::OperationLimitExceeded)
+        } else {
+            Ok(())
+        }
+    }
+
     #[cfg(feature = "completions")]
     pub(crate) fn add_completion_entries<I: Iterator<Item = impl Into<String>>, F: Fn() -> I>(
         &mut self,
@@ -135,7 +161,7 @@ impl<'data, 'exec> ExpressionExecutionState<'data, 'exec> {
 
 #[derive(Debug)]
 pub struct InternalExpressionExecutionState<'data, 'exec> {
-    data: Vec<Option<&'data dyn SourceData>>,
+    data: SourceValues<'data>,
     opcount: &'exec mut i64,
     max_opcount: i64,
     #[cfg(feature = "completions")]
@@ -222,6 +248,13 @@ pub enum FunctionType {
     Pow(PowFunction),
     Log(LogFunction),
     Atan2(Atan2Function),
+    GeoDistance(GeoDistanceFunction),
+    Variance(VarianceFunction),
+    Stddev(StddevFunction),
+    Percentile(PercentileFunction),
+    Avg(AvgFunction),
+    Median(MedianFunction),
+    Resample(ResampleFunction),
     Floor(FloorFunction),
     Ceil(CeilFunction),
     Round(RoundFunction),
@@ -233,18 +266,33 @@ pub enum FunctionType {
     TryFloat(TryFloatFunction),
     TryInt(TryIntFunction),
     TryBool(TryBoolFunction),
+    ParseNumber(ParseNumberFunction),
+    TryParseNumber(TryParseNumberFunction),
+    IntFromHex(IntFromHexFunction),
     If(IfFunction),
+    #[cfg(feature = "time")]
     ToUnixTime(ToUnixTimeFunction),
+    #[cfg(feature = "time")]
     FormatTimestamp(FormatTimestampFunction),
     Case(CaseFunction),
     Pairs(PairsFunction),
+    FlattenObject(FlattenObjectFunction),
+    UnflattenObject(UnflattenObjectFunction),
     Map(MapFunction),
     FlatMap(FlatMapFunction),
     Reduce(ReduceFunction),
+    SortWith(SortWithFunction),
+    Sort(SortFunction),
+    SortBy(SortByFunction),
+    MinBy(MinByFunction),
+    MaxBy(MaxByFunction),
+    GroupBy(GroupByFunction),
     Filter(FilterFunction),
     Zip(ZipFunction),
     Length(LengthFunction),
     Chunk(ChunkFunction),
+    Window(WindowFunction),
+    #[cfg(feature = "time")]
     Now(NowFunction),
     Join(JoinFunction),
     Except(ExceptFunction),
@@ -261,25 +309,55 @@ pub enum FunctionType {
     Any(AnyFunction),
     All(AllFunction),
     Contains(ContainsFunction),
+    IndexOf(IndexOfFunction),
+    BinarySearch(BinarySearchFunction),
+    Transpose(TransposeFunction),
+    CartesianProduct(CartesianProductFunction),
+    Distinct(DistinctFunction),
     StringJoin(StringJoinFunction),
     Min(MinFunction),
     Max(MaxFunction),
+    #[cfg(feature = "digest")]
     Digest(DigestFunction),
+    #[cfg(feature = "digest")]
+    Crc32(Crc32Function),
+    #[cfg(feature = "digest")]
+    Fletcher16(Fletcher16Function),
     Coalesce(CoalesceFunction),
+    #[cfg(feature = "regex")]
     RegexIsMatch(RegexIsMatchFunction),
+    #[cfg(feature = "regex")]
     RegexFirstMatch(RegexFirstMatchFunction),
+    #[cfg(feature = "regex")]
     RegexAllMatches(RegexAllMatchesFunction),
+    #[cfg(feature = "regex")]
     RegexFirstCaptures(RegexFirstCapturesFunction),
+    #[cfg(feature = "regex")]
     RegexAllCaptures(RegexAllCapturesFunction),
+    #[cfg(feature = "regex")]
     RegexReplace(RegexReplaceFunction),
+    #[cfg(feature = "regex")]
     RegexReplaceAll(RegexReplaceAllFunction),
+    #[cfg(feature = "regex")]
+    RegexSplit(RegexSplitFunction),
     StartsWith(StartsWithFunction),
     EndsWith(EndsWithFunction),
     IfValue(IfValueFunction),
     ParseJson(ParseJsonFunction),
+    Get(GetFunction),
+    JsonPointer(JsonPointerFunction),
+    ToJsonPointer(ToJsonPointerFunction),
+    Mask(MaskFunction),
+    JsonDiff(JsonDiffFunction),
+    JsonPatch(JsonPatchFunction),
     Lower(LowerFunction),
     Upper(UpperFunction),
     Translate(TranslateFunction),
+    Levenshtein(LevenshteinFunction),
+    Similarity(SimilarityFunction),
+    HexEncode(HexEncodeFunction),
+    HexDecode(HexDecodeFunction),
+    Repeat(RepeatFunction),
     SqrtFunction(SqrtFunction),
     ExpFunction(ExpFunction),
     SinFunction(SinFunction),
@@ -290,6 +368,13 @@ pub enum FunctionType {
     AtanFunction(AtanFunction),
     Random(RandomFunction),
     Uuid4(Uuid4Function),
+    ParseIp(ParseIpFunction),
+    IpInCidr(IpInCidrFunction),
+    IpToInt(IpToIntFunction),
+    #[cfg(feature = "semver")]
+    SemverCompare(SemverCompareFunction),
+    #[cfg(feature = "semver")]
+    SemverSatisfies(SemverSatisfiesFunction),
     CustomFunction(Box<dyn DynamicFunction>),
 }
 
@@ -317,6 +402,13 @@ pub fn get_function_expression(
         "pow" => FunctionType::Pow(b.mk()?),
         "log" => FunctionType::Log(b.mk()?),
         "atan2" => FunctionType::Atan2(b.mk()?),
+        "geo_distance" => FunctionType::GeoDistance(b.mk()?),
+        "variance" => FunctionType::Variance(b.mk()?),
+        "stddev" => FunctionType::Stddev(b.mk()?),
+        "percentile" => FunctionType::Percentile(b.mk()?),
+        "avg" => FunctionType::Avg(b.mk()?),
+        "median" => FunctionType::Median(b.mk()?),
+        "resample" => FunctionType::Resample(b.mk()?),
         "floor" => FunctionType::Floor(b.mk()?),
         "ceil" => FunctionType::Ceil(b.mk()?),
         "round" => FunctionType::Round(b.mk()?),
@@ -327,18 +419,33 @@ pub fn get_function_expression(
         "try_float" => FunctionType::TryFloat(b.mk()?),
         "try_int" => FunctionType::TryInt(b.mk()?),
         "try_bool" => FunctionType::TryBool(b.mk()?),
+        "parse_number" => FunctionType::ParseNumber(b.mk()?),
+        "try_parse_number" => FunctionType::TryParseNumber(b.mk()?),
+        "int_from_hex" => FunctionType::IntFromHex(b.mk()?),
         "if" => FunctionType::If(b.mk()?),
+        #[cfg(feature = "time")]
         "to_unix_timestamp" => FunctionType::ToUnixTime(b.mk()?),
+        #[cfg(feature = "time")]
         "format_timestamp" => FunctionType::FormatTimestamp(b.mk()?),
         "case" => FunctionType::Case(b.mk()?),
         "pairs" => FunctionType::Pairs(b.mk()?),
+        "flatten_object" => FunctionType::FlattenObject(b.mk()?),
+        "unflatten_object" => FunctionType::UnflattenObject(b.mk()?),
         "map" => FunctionType::Map(b.mk()?),
         "flatmap" => FunctionType::FlatMap(b.mk()?),
         "reduce" => FunctionType::Reduce(b.mk()?),
+        "sort_with" => FunctionType::SortWith(b.mk()?),
+        "sort" => FunctionType::Sort(b.mk()?),
+        "sort_by" => FunctionType::SortBy(b.mk()?),
+        "min_by" => FunctionType::MinBy(b.mk()?),
+        "max_by" => FunctionType::MaxBy(b.mk()?),
+        "group_by" => FunctionType::GroupBy(b.mk()?),
         "filter" => FunctionType::Filter(b.mk()?),
         "zip" => FunctionType::Zip(b.mk()?),
         "length" => FunctionType::Length(b.mk()?),
         "chunk" => FunctionType::Chunk(b.mk()?),
+        "window" => FunctionType::Window(b.mk()?),
+        #[cfg(feature = "time")]
         "now" => FunctionType::Now(b.mk()?),
         "join" => FunctionType::Join(b.mk()?),
         "except" => FunctionType::Except(b.mk()?),
@@ -356,25 +463,55 @@ pub fn get_function_expression(
         "any" => FunctionType::Any(b.mk()?),
         "all" => FunctionType::All(b.mk()?),
         "contains" => FunctionType::Contains(b.mk()?),
+        "index_of" => FunctionType::IndexOf(b.mk()?),
+        "binary_search" => FunctionType::BinarySearch(b.mk()?),
+        "transpose" => FunctionType::Transpose(b.mk()?),
+        "cartesian_product" => FunctionType::CartesianProduct(b.mk()?),
+        "distinct" => FunctionType::Distinct(b.mk()?),
         "string_join" => FunctionType::StringJoin(b.mk()?),
         "min" => FunctionType::Min(b.mk()?),
         "max" => FunctionType::Max(b.mk()?),
+        #[cfg(feature = "digest")]
         "digest" => FunctionType::Digest(b.mk()?),
+        #[cfg(feature = "digest")]
+        "crc32" => FunctionType::Crc32(b.mk()?),
+        #[cfg(feature = "digest")]
+        "fletcher16" => FunctionType::Fletcher16(b.mk()?),
         "coalesce" => FunctionType::Coalesce(b.mk()?),
+        #[cfg(feature = "regex")]
         "regex_is_match" => FunctionType::RegexIsMatch(b.mk()?),
+        #[cfg(feature = "regex")]
         "regex_first_match" => FunctionType::RegexFirstMatch(b.mk()?),
+        #[cfg(feature = "regex")]
         "regex_all_matches" => FunctionType::RegexAllMatches(b.mk()?),
+        #[cfg(feature = "regex")]
         "regex_first_captures" => FunctionType::RegexFirstCaptures(b.mk()?),
+        #[cfg(feature = "regex")]
         "regex_all_captures" => FunctionType::RegexAllCaptures(b.mk()?),
+        #[cfg(feature = "regex")]
         "regex_replace" => FunctionType::RegexReplace(b.mk()?),
+        #[cfg(feature = "regex")]
         "regex_replace_all" => FunctionType::RegexReplaceAll(b.mk()?),
+        #[cfg(feature = "regex")]
+        "regex_split" => FunctionType::RegexSplit(b.mk()?),
         "starts_with" => FunctionType::StartsWith(b.mk()?),
         "ends_with" => FunctionType::EndsWith(b.mk()?),
         "if_value" => FunctionType::IfValue(b.mk()?),
         "parse_json" => FunctionType::ParseJson(b.mk()?),
+        "get" => FunctionType::Get(b.mk()?),
+        "json_pointer" => FunctionType::JsonPointer(b.mk()?),
+        "to_json_pointer" => FunctionType::ToJsonPointer(b.mk()?),
+        "mask" => FunctionType::Mask(b.mk()?),
+        "json_diff" => FunctionType::JsonDiff(b.mk()?),
+        "json_patch" => FunctionType::JsonPatch(b.mk()?),
         "lower" => FunctionType::Lower(b.mk()?),
         "upper" => FunctionType::Upper(b.mk()?),
         "translate" => FunctionType::Translate(b.mk()?),
+        "levenshtein" => FunctionType::Levenshtein(b.mk()?),
+        "similarity" => FunctionType::Similarity(b.mk()?),
+        "hex_encode" => FunctionType::HexEncode(b.mk()?),
+        "hex_decode" => FunctionType::HexDecode(b.mk()?),
+        "repeat" => FunctionType::Repeat(b.mk()?),
         "sqrt" => FunctionType::SqrtFunction(b.mk()?),
         "exp" => FunctionType::ExpFunction(b.mk()?),
         "sin" => FunctionType::SinFunction(b.mk()?),
@@ -385,6 +522,13 @@ pub fn get_function_expression(
         "atan" => FunctionType::AtanFunction(b.mk()?),
         "random" => FunctionType::Random(b.mk()?),
         "uuid4" => FunctionType::Uuid4(b.mk()?),
+        "parse_ip" => FunctionType::ParseIp(b.mk()?),
+        "ip_in_cidr" => FunctionType::IpInCidr(b.mk()?),
+        "ip_to_int" => FunctionType::IpToInt(b.mk()?),
+        #[cfg(feature = "semver")]
+        "semver_compare" => FunctionType::SemverCompare(b.mk()?),
+        #[cfg(feature = "semver")]
+        "semver_satisfies" => FunctionType::SemverSatisfies(b.mk()?),
         _ => return Err(BuildError::unrecognized_function(b.pos, name)),
     };
     Ok(ExpressionType::Function(expr))
@@ -427,6 +571,8 @@ pub enum ExpressionType {
     TemplateString(TemplateStringExpression),
     /// A define expression, i.e. a number of local variable definitions and an inner expression.
     Define(DefineExpression),
+    /// A "try" expression, which evaluates to a fallback if the inner expression fails.
+    Try(TryExpression),
 }
 
 impl ExpressionType {
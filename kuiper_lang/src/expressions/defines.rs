@@ -23,15 +23,15 @@ impl Expression for DefineExpression {
         &'a self,
         state: &mut super::ExpressionExecutionState<'a, '_>,
     ) -> Result<super::ResolveResult<'a>, super::TransformError> {
-        let mut inner = state.get_empty_temp_clone();
-
         // We need each definition to be available to each subsequent definition,
         // so we resolve them one by one, pushing the results into the temporary state as we go.
         // Since the temporary state needs to contain _references_ to the data,
         // we first allocate storage for that data, then mutable split off the first element
         // to get a mutable reference to it, which we can then push into the temporary state.
+        // The storage must be declared before the temporary state, so that it outlives it.
 
         let mut data = vec![None; self.defines.len()];
+        let mut inner = state.get_empty_temp_clone();
         let mut data_ref: &mut [Option<ResolveResult<'_>>] = &mut data[..];
         for (_, expr) in self.defines.iter() {
             let mut state = inner.get_temp_state();
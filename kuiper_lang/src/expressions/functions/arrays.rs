@@ -2,8 +2,8 @@ use itertools::Itertools;
 use serde_json::{Number, Value};
 
 use crate::{
-    expressions::{numbers::JsonNumber, Expression, ResolveResult},
-    types::{Array, Type, TypeError},
+    expressions::{numbers::JsonNumber, Expression, Operator, ResolveResult},
+    types::{Array, Object, Type, TypeError},
     TransformError,
 };
 
@@ -143,6 +143,59 @@ impl Expression for ChunkFunction {
     }
 }
 
+function_def!(WindowFunction, "window", 2);
+
+impl Expression for WindowFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let Value::Array(arr) = source.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to window",
+                "array",
+                TransformError::value_desc(&source),
+                &self.span,
+            ));
+        };
+
+        let window_size = self.args[1]
+            .resolve(state)?
+            .try_as_number("window", &self.span)?
+            .try_as_u64(&self.span)? as usize;
+
+        if window_size == 0 {
+            return Err(TransformError::new_invalid_operation(
+                "Window size must be greater than 0".to_string(),
+                &self.span,
+            ));
+        }
+
+        let mut res = Vec::new();
+        for window in arr.windows(window_size) {
+            // Each element is copied into up to `window_size` windows, so count every copied
+            // element towards the operation limit.
+            state.inc_ops(window.len())?;
+            res.push(Value::Array(window.to_vec()));
+        }
+        Ok(ResolveResult::Owned(Value::Array(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        let window_size = self.args[1].resolve_types(state)?;
+        window_size.assert_assignable_to(&Type::Integer, &self.span)?;
+        let source_arr = source.try_as_array(&self.span)?;
+        Ok(Type::array_of_type(Type::array_of_type(
+            source_arr.element_union(),
+        )))
+    }
+}
+
 function_def!(TailFunction, "tail", 1, Some(2));
 
 impl Expression for TailFunction {
@@ -459,13 +512,276 @@ impl Expression for ContainsFunction {
     }
 }
 
+function_def!(IndexOfFunction, "index_of", 2);
+
+impl Expression for IndexOfFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let raw_list = self.args[0].resolve(state)?;
+        let look_for = self.args[1].resolve(state)?;
+        match raw_list.as_ref() {
+            Value::Array(list) => {
+                let idx = list
+                    .iter()
+                    .position(|i| i == look_for.as_ref())
+                    .map(|i| i as i64)
+                    .unwrap_or(-1);
+                Ok(ResolveResult::Owned(Value::from(idx)))
+            }
+            _ => Err(TransformError::new_incorrect_type(
+                "Incorrect input to index_of",
+                "array",
+                TransformError::value_desc(&raw_list),
+                &self.span,
+            )),
+        }
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let arr = self.args[0].resolve_types(state)?;
+        self.args[1].resolve_types(state)?;
+        arr.assert_assignable_to(&Type::any_array(), &self.span)?;
+        Ok(Type::Integer)
+    }
+}
+
+/// Compare two values for `binary_search` and `sort`. Numbers are compared numerically, and strings
+/// lexicographically. Other combinations cannot be ordered.
+pub(crate) fn compare_values(
+    lhs: &Value,
+    rhs: &Value,
+    func: &str,
+    span: &logos::Span,
+) -> Result<std::cmp::Ordering, TransformError> {
+    use std::cmp::Ordering;
+    match (lhs, rhs) {
+        (Value::Number(l), Value::Number(r)) => {
+            let (l, r) = (JsonNumber::from(l), JsonNumber::from(r));
+            Ok(if l.eq(r, span) {
+                Ordering::Equal
+            } else if l.cmp(Operator::LessThan, r, span) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            })
+        }
+        (Value::String(l), Value::String(r)) => Ok(l.cmp(r)),
+        _ => Err(TransformError::new_invalid_operation(
+            format!(
+                "Cannot compare {} and {} in {func}",
+                TransformError::value_desc(lhs),
+                TransformError::value_desc(rhs)
+            ),
+            span,
+        )),
+    }
+}
+
+function_def!(BinarySearchFunction, "binary_search", 2);
+
+impl Expression for BinarySearchFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let raw_list = self.args[0].resolve(state)?;
+        let look_for = self.args[1].resolve(state)?;
+        let Value::Array(list) = raw_list.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to binary_search",
+                "array",
+                TransformError::value_desc(&raw_list),
+                &self.span,
+            ));
+        };
+
+        let mut err = None;
+        let res = list.binary_search_by(|item| {
+            compare_values(item, &look_for, "binary_search", &self.span).unwrap_or_else(|e| {
+                err.get_or_insert(e);
+                std::cmp::Ordering::Equal
+            })
+        });
+        if let Some(err) = err {
+            return Err(err);
+        }
+        let idx = res.map(|i| i as i64).unwrap_or(-1);
+        Ok(ResolveResult::Owned(Value::from(idx)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let arr = self.args[0].resolve_types(state)?;
+        let check = self.args[1].resolve_types(state)?;
+        arr.assert_assignable_to(&Type::any_array(), &self.span)?;
+        check.assert_assignable_to(&Type::number().union_with(Type::String), &self.span)?;
+        Ok(Type::Integer)
+    }
+}
+
+function_def!(TransposeFunction, "transpose", 1);
+
+impl Expression for TransposeFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let Value::Array(rows) = source.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to transpose",
+                "array",
+                TransformError::value_desc(&source),
+                &self.span,
+            ));
+        };
+
+        let rows = rows
+            .iter()
+            .map(|row| match row {
+                Value::Array(r) => Ok(r),
+                x => Err(TransformError::new_incorrect_type(
+                    "Incorrect row in transpose",
+                    "array",
+                    TransformError::value_desc(x),
+                    &self.span,
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or_default();
+
+        // Shorter rows are padded with null, so that every column has one entry per row.
+        let mut res = vec![Vec::with_capacity(rows.len()); width];
+        for row in rows {
+            for (idx, col) in res.iter_mut().enumerate() {
+                col.push(row.get(idx).cloned().unwrap_or_default());
+            }
+        }
+        Ok(ResolveResult::Owned(Value::Array(
+            res.into_iter().map(Value::Array).collect(),
+        )))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        let source_arr = source.try_as_array(&self.span)?;
+        let mut elem: Option<Type> = None;
+        for row in source_arr.all_elements() {
+            let row = row.try_as_array(&self.span)?.element_union();
+            elem = Some(match elem {
+                Some(e) => e.union_with(row),
+                None => row,
+            });
+        }
+        // Ragged rows are padded with null.
+        let elem = elem.map_or(Type::null(), |e| e.union_with(Type::null()));
+        Ok(Type::array_of_type(Type::array_of_type(elem)))
+    }
+}
+
+function_def!(CartesianProductFunction, "cartesian_product", 2);
+
+impl Expression for CartesianProductFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let left = self.args[0].resolve(state)?;
+        let right = self.args[1].resolve(state)?;
+        let (Value::Array(left), Value::Array(right)) = (left.as_ref(), right.as_ref()) else {
+            let wrong = if left.is_array() { &right } else { &left };
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to cartesian_product",
+                "array",
+                TransformError::value_desc(wrong),
+                &self.span,
+            ));
+        };
+
+        let mut res = Vec::with_capacity(left.len() * right.len());
+        for l in left {
+            for r in right {
+                // The output grows quickly, so count each pair towards the operation limit.
+                state.inc_op()?;
+                let mut pair = serde_json::Map::new();
+                pair.insert("left".to_owned(), l.clone());
+                pair.insert("right".to_owned(), r.clone());
+                res.push(Value::Object(pair));
+            }
+        }
+        Ok(ResolveResult::Owned(Value::Array(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let left = self.args[0].resolve_types(state)?;
+        let right = self.args[1].resolve_types(state)?;
+        let left = left.try_as_array(&self.span)?;
+        let right = right.try_as_array(&self.span)?;
+        Ok(Type::array_of_type(Type::Object(
+            Object::default()
+                .with_field("left", left.element_union())
+                .with_field("right", right.element_union()),
+        )))
+    }
+}
+
+function_def!(DistinctFunction, "distinct", 1);
+
+impl Expression for DistinctFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let Value::Array(xs) = source.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to distinct",
+                "array",
+                TransformError::value_desc(&source),
+                &self.span,
+            ));
+        };
+
+        // Values are compared by their JSON representation, like in distinct_by.
+        let mut found = std::collections::HashSet::new();
+        let res = xs
+            .iter()
+            .filter(|x| found.insert(x.to_string()))
+            .cloned()
+            .collect();
+        Ok(ResolveResult::Owned(Value::Array(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        let source_arr = source.try_as_array(&self.span)?;
+        Ok(Type::array_of_type(source_arr.element_union()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::Value;
 
     use crate::{
         compile_expression,
-        types::{Array, Type},
+        types::{Array, Object, Type},
     };
 
     #[test]
@@ -860,4 +1176,252 @@ mod tests {
             err.to_string()
         );
     }
+
+    #[test]
+    pub fn test_index_of() {
+        let expr = compile_expression(
+            r#"[
+                [1, 2, 3, 2].index_of(2),
+                [1, 2, 3].index_of(4),
+                ["a", {"b": 1}].index_of({"b": 1}),
+                index_of([], null),
+            ]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &serde_json::json!([1, -1, 1, -1]));
+    }
+
+    #[test]
+    pub fn test_binary_search() {
+        let expr = compile_expression(
+            r#"[
+                [1, 3, 5, 7, 9].binary_search(7),
+                [1, 3, 5, 7, 9].binary_search(4),
+                [1, 3, 5, 7, 9].binary_search(1.0),
+                [-10, -2.5, 0, 1e3].binary_search(-2.5),
+                ["apple", "banana", "cherry"].binary_search("cherry"),
+                [].binary_search(1),
+            ]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &serde_json::json!([3, -1, 0, 1, 2, -1]));
+
+        let expr = compile_expression("input.binary_search(2)", &["input"]).unwrap();
+        let input = serde_json::json!([1, "a", 3]);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Cannot compare string and number in binary_search"
+        );
+    }
+
+    #[test]
+    fn test_index_of_types() {
+        let expr = compile_expression("input.index_of(1)", &["input"]).unwrap();
+        let ty = expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .unwrap();
+        assert_eq!(Type::Integer, ty);
+        assert!(expr.run_types([Type::String]).is_err());
+
+        let expr = compile_expression("input.binary_search({})", &["input"]).unwrap();
+        assert!(expr.run_types([Type::any_array()]).is_err());
+    }
+
+    #[test]
+    pub fn test_transpose() {
+        let expr = compile_expression(
+            r#"{
+                "square": transpose([[1, 2, 3], [4, 5, 6]]),
+                "ragged": transpose([[1, 2], [3], [4, 5, 6]]),
+                "empty": transpose([]),
+                "empty_rows": transpose([[], []]),
+                "channels": transpose([input.ts, input.values]).map(s => { "ts": s[0], "value": s[1] }),
+            }"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = serde_json::json!({ "ts": [1, 2], "values": [0.5, 0.7] });
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!({
+                "square": [[1, 4], [2, 5], [3, 6]],
+                "ragged": [[1, 3, 4], [2, null, 5], [null, null, 6]],
+                "empty": [],
+                "empty_rows": [],
+                "channels": [{ "ts": 1, "value": 0.5 }, { "ts": 2, "value": 0.7 }],
+            })
+        );
+
+        let expr = compile_expression("transpose(input)", &["input"]).unwrap();
+        let input = serde_json::json!([[1], 2]);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect row in transpose. Got number, expected array"
+        );
+    }
+
+    #[test]
+    fn test_transpose_types() {
+        let expr = compile_expression("transpose(input)", &["input"]).unwrap();
+        let ty = expr
+            .run_types([Type::array_of_type(Type::array_of_type(Type::Integer))])
+            .unwrap();
+        assert_eq!(
+            ty,
+            Type::array_of_type(Type::array_of_type(Type::Integer.union_with(Type::null())))
+        );
+        assert!(expr.run_types([Type::array_of_type(Type::String)]).is_err());
+    }
+
+    #[test]
+    pub fn test_cartesian_product() {
+        let expr = compile_expression(
+            r#"{
+                "pairs": cartesian_product([1, 2], ["a", "b"]),
+                "empty": cartesian_product([1, 2], []),
+                "mapped": cartesian_product(input.sites, input.sensors).map(p => concat(p.left, "/", p.right)),
+            }"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = serde_json::json!({ "sites": ["s1", "s2"], "sensors": ["t", "p"] });
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!({
+                "pairs": [
+                    { "left": 1, "right": "a" },
+                    { "left": 1, "right": "b" },
+                    { "left": 2, "right": "a" },
+                    { "left": 2, "right": "b" },
+                ],
+                "empty": [],
+                "mapped": ["s1/t", "s1/p", "s2/t", "s2/p"],
+            })
+        );
+
+        let expr = compile_expression("cartesian_product([1], input)", &["input"]).unwrap();
+        let input = serde_json::json!("a");
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect input to cartesian_product. Got string, expected array"
+        );
+    }
+
+    #[test]
+    pub fn test_cartesian_product_op_limit() {
+        let expr = compile_expression("cartesian_product(input, input)", &["input"]).unwrap();
+        let input = Value::Array((0..100).map(Value::from).collect());
+        let err = expr.run_limited([&input], 1_000).unwrap_err();
+        assert!(matches!(err, crate::TransformError::OperationLimitExceeded));
+    }
+
+    #[test]
+    fn test_cartesian_product_types() {
+        let expr = compile_expression("cartesian_product(a, b)", &["a", "b"]).unwrap();
+        let ty = expr
+            .run_types([
+                Type::array_of_type(Type::Integer),
+                Type::array_of_type(Type::String),
+            ])
+            .unwrap();
+        assert_eq!(
+            ty,
+            Type::array_of_type(Type::Object(
+                Object::default()
+                    .with_field("left", Type::Integer)
+                    .with_field("right", Type::String)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_distinct() {
+        let expr = compile_expression(
+            r#"[
+                [1, 2, 1, 3, 2].distinct(),
+                ["a", "b", "a"].distinct(),
+                [{ "a": 1, "b": 2 }, { "b": 2, "a": 1 }, [1], [1], null, null].distinct(),
+                [].distinct(),
+            ]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!([[1, 2, 3], ["a", "b"], [{ "a": 1, "b": 2 }, [1], null], []])
+        );
+
+        let expr = compile_expression("distinct(input)", &["input"]).unwrap();
+        let input = serde_json::json!({ "a": 1 });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect input to distinct. Got object, expected array"
+        );
+        let ty = expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .unwrap();
+        assert_eq!(ty, Type::array_of_type(Type::Integer));
+    }
+
+    #[test]
+    fn test_window() {
+        let expr = compile_expression(
+            r#"[
+                [1, 2, 3, 4].window(2),
+                [1, 2, 3].window(3),
+                [1, 2].window(3),
+                [].window(1),
+            ]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!([[[1, 2], [2, 3], [3, 4]], [[1, 2, 3]], [], []])
+        );
+
+        // Deltas between consecutive samples.
+        let expr = compile_expression("input.window(2).map(w => w[1] - w[0])", &["input"]).unwrap();
+        let input = serde_json::json!([1, 4, 9, 16]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res.as_ref(), &serde_json::json!([3, 5, 7]));
+    }
+
+    #[test]
+    fn test_window_errors() {
+        let expr = compile_expression("window(input, 0)", &["input"]).unwrap();
+        let input = serde_json::json!([1, 2]);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(err.message(), "Window size must be greater than 0");
+
+        let expr = compile_expression("window(input, 100)", &["input"]).unwrap();
+        let input = Value::Array((0..1000).map(Value::from).collect());
+        let err = expr.run_limited([&input], 500).unwrap_err();
+        assert!(matches!(err, crate::TransformError::OperationLimitExceeded));
+
+        let ty = expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .unwrap();
+        assert_eq!(ty, Type::array_of_type(Type::array_of_type(Type::Integer)));
+
+        // Few, but large, windows.
+        let expr = compile_expression("window(input, 3000)", &["input"]).unwrap();
+        let input = Value::Array((0..6000).map(Value::from).collect());
+        let err = expr.run_limited([&input], 1_000_000).unwrap_err();
+        assert!(matches!(err, crate::TransformError::OperationLimitExceeded));
+        let input = Value::Array((0..3010).map(Value::from).collect());
+        expr.run_limited([&input], 1_000_000).unwrap();
+    }
 }
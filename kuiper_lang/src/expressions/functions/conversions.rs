@@ -145,6 +145,249 @@ impl Expression for TryBoolFunction {
     }
 }
 
+function_def!(ParseNumberFunction, "parse_number", 1, Some(2));
+
+/// Decimal and thousands separators used by `parse_number` and `try_parse_number`.
+struct NumberFormat {
+    decimal: String,
+    thousands: String,
+}
+
+impl NumberFormat {
+    fn from_options(options: Option<&Value>, func: &str) -> Result<Self, String> {
+        let mut decimal = None;
+        let mut thousands = None;
+        match options {
+            None | Some(Value::Null) => (),
+            Some(Value::Object(o)) => {
+                for (key, value) in o {
+                    let target = match key.as_str() {
+                        "decimal" => &mut decimal,
+                        "thousands" => &mut thousands,
+                        _ => return Err(format!("Unknown option {key} in {func}")),
+                    };
+                    let Value::String(s) = value else {
+                        return Err(format!("Option {key} in {func} must be a string"));
+                    };
+                    *target = Some(s.clone());
+                }
+            }
+            Some(_) => return Err(format!("Options to {func} must be an object")),
+        }
+        let decimal = decimal.unwrap_or_else(|| ".".to_owned());
+        if decimal.is_empty() {
+            return Err(format!("Decimal separator in {func} cannot be empty"));
+        }
+        let thousands =
+            thousands.unwrap_or_else(|| if decimal == "," { "." } else { "," }.to_owned());
+        if thousands == decimal {
+            return Err(format!(
+                "Decimal and thousands separator in {func} must be different"
+            ));
+        }
+        Ok(Self { decimal, thousands })
+    }
+
+    /// Parse `input` using this format. Returns an integer if the input has no
+    /// fractional part or exponent, and fits in one.
+    fn parse(&self, input: &str) -> Option<Value> {
+        let input = input.trim();
+        let (int_part, frac_part) = match input.split_once(self.decimal.as_str()) {
+            Some((i, f)) => (i, Some(f)),
+            None => (input, None),
+        };
+        // Thousands separators are only allowed before the decimal separator.
+        let int_part = if self.thousands.is_empty() {
+            int_part.to_owned()
+        } else {
+            if frac_part.is_some_and(|f| f.contains(self.thousands.as_str())) {
+                return None;
+            }
+            // If there are thousands separators, the first group has 1 to 3 digits and the rest exactly 3,
+            // so that e.g. "1,5" is not read as 15.
+            let mut groups = int_part.split(self.thousands.as_str());
+            let first = groups
+                .next()
+                .unwrap_or_default()
+                .trim_start_matches(['+', '-']);
+            if int_part.contains(self.thousands.as_str())
+                && (!(1..=3).contains(&first.len())
+                    || !first.chars().all(|c| c.is_ascii_digit())
+                    || !groups.all(|g| g.len() == 3 && g.chars().all(|c| c.is_ascii_digit())))
+            {
+                return None;
+            }
+            int_part.replace(self.thousands.as_str(), "")
+        };
+        let normalized = match frac_part {
+            Some(f) => format!("{int_part}.{f}"),
+            None => int_part,
+        };
+        // Rust also accepts "inf" and "NaN", which we do not want to treat as numbers.
+        if normalized.is_empty()
+            || !normalized
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+        {
+            return None;
+        }
+        if frac_part.is_none() {
+            if let Ok(i) = normalized.parse::<i64>() {
+                return Some(Value::from(i));
+            }
+            if let Ok(u) = normalized.parse::<u64>() {
+                return Some(Value::from(u));
+            }
+        }
+        normalized
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+    }
+}
+
+impl Expression for ParseNumberFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let options = match self.args.get(1) {
+            Some(a) => Some(a.resolve(state)?),
+            None => None,
+        };
+        let format = NumberFormat::from_options(options.as_deref(), "parse_number")
+            .map_err(|e| TransformError::new_invalid_operation(e, &self.span))?;
+        let dat = self.args[0].resolve(state)?;
+        match dat.as_ref() {
+            Value::Number(_) => Ok(dat),
+            Value::String(s) => match format.parse(s) {
+                Some(v) => Ok(ResolveResult::Owned(v)),
+                None => Err(TransformError::new_conversion_failed(
+                    format!("Failed to convert {s:?} to number in parse_number"),
+                    &self.span,
+                )),
+            },
+            _ => Err(TransformError::new_incorrect_type(
+                "Incorrect input to parse_number",
+                "string or number",
+                TransformError::value_desc(&dat),
+                &self.span,
+            )),
+        }
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<crate::types::Type, crate::types::TypeError> {
+        if let Some(options) = self.args.get(1) {
+            options
+                .resolve_types(state)?
+                .assert_assignable_to(&Type::any_object().union_with(Type::null()), &self.span)?;
+        }
+        let arg = self.args[0].resolve_types(state)?;
+        arg.assert_assignable_to(&Type::number().union_with(Type::String), &self.span)?;
+        if arg.is_numeric() {
+            return Ok(arg);
+        }
+        Ok(Type::number())
+    }
+}
+
+function_def!(TryParseNumberFunction, "try_parse_number", 2, Some(3));
+
+impl Expression for TryParseNumberFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let options = match self.args.get(2) {
+            Some(a) => Some(a.resolve(state)?),
+            None => None,
+        };
+        let format = NumberFormat::from_options(options.as_deref(), "try_parse_number")
+            .map_err(|e| TransformError::new_invalid_operation(e, &self.span))?;
+        let dat = self.args[0].resolve(state)?;
+        match dat.as_ref() {
+            Value::Number(_) => Ok(dat),
+            Value::String(s) => match format.parse(s) {
+                Some(v) => Ok(ResolveResult::Owned(v)),
+                None => self.args[1].resolve(state),
+            },
+            _ => self.args[1].resolve(state),
+        }
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<crate::types::Type, crate::types::TypeError> {
+        if let Some(options) = self.args.get(2) {
+            options
+                .resolve_types(state)?
+                .assert_assignable_to(&Type::any_object().union_with(Type::null()), &self.span)?;
+        }
+        let a1 = self.args[0].resolve_types(state)?;
+        let a2 = self.args[1].resolve_types(state)?;
+        if a1.is_numeric() {
+            return Ok(a1);
+        }
+        if !a1.is_assignable_to(&Type::number().union_with(Type::String)) {
+            return Ok(a2);
+        }
+        Ok(Type::number().union_with(a2))
+    }
+}
+
+function_def!(IntFromHexFunction, "int_from_hex", 1);
+
+impl Expression for IntFromHexFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let inp = self.args[0].resolve(state)?;
+        let inp = inp.try_as_string("int_from_hex", &self.span)?;
+        let trimmed = inp.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+        let digits = digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+            .unwrap_or(digits);
+
+        // from_str_radix accepts a leading sign, which we have already handled.
+        let value = if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+            None
+        } else if negative {
+            i64::from_str_radix(&format!("-{digits}"), 16)
+                .ok()
+                .map(Value::from)
+        } else {
+            u64::from_str_radix(digits, 16).ok().map(Value::from)
+        };
+        value.map(ResolveResult::Owned).ok_or_else(|| {
+            TransformError::new_conversion_failed(
+                format!("Failed to convert {inp:?} to integer in int_from_hex"),
+                &self.span,
+            )
+        })
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<crate::types::Type, crate::types::TypeError> {
+        self.args[0]
+            .resolve_types(state)?
+            .assert_assignable_to(&Type::String, &self.span)?;
+        Ok(Type::Integer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{compile_expression, types::Type};
@@ -296,4 +539,125 @@ mod tests {
         let t = exp.run_types([Type::null()]).unwrap();
         assert_eq!(t, Type::from_const("default"));
     }
+
+    #[test]
+    pub fn test_parse_number() {
+        let exp = compile_expression(
+            r#"{
+            "test1": parse_number("1.234,56", {"decimal": ",", "thousands": "."}),
+            "test2": parse_number("1,234.56"),
+            "test3": parse_number("1 234,5", {"decimal": ",", "thousands": " "}),
+            "test4": parse_number("-12.000", {"decimal": ","}),
+            "test5": parse_number("  42  "),
+            "test6": parse_number(7.5),
+            "test7": parse_number("1,5e3", {"decimal": ","}),
+        }"#,
+            &[],
+        )
+        .unwrap();
+
+        let result = exp.run([]).unwrap();
+
+        assert_eq!(1234.56, result.get("test1").unwrap().as_f64().unwrap());
+        assert_eq!(1234.56, result.get("test2").unwrap().as_f64().unwrap());
+        assert_eq!(1234.5, result.get("test3").unwrap().as_f64().unwrap());
+        assert_eq!(-12000, result.get("test4").unwrap().as_i64().unwrap());
+        assert_eq!(42, result.get("test5").unwrap().as_i64().unwrap());
+        assert_eq!(7.5, result.get("test6").unwrap().as_f64().unwrap());
+        assert_eq!(1500.0, result.get("test7").unwrap().as_f64().unwrap());
+    }
+
+    #[test]
+    pub fn test_parse_number_errors() {
+        let exp = compile_expression(r#"parse_number(input)"#, &["input"]).unwrap();
+        let err = exp.run([&json!("1.234,56")]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            r#"Failed to convert "1.234,56" to number in parse_number"#
+        );
+        exp.run([&json!("inf")]).unwrap_err();
+        exp.run([&json!("")]).unwrap_err();
+        exp.run([&json!("1,5")]).unwrap_err();
+        exp.run([&json!("1,2,3")]).unwrap_err();
+        exp.run([&json!("12,34.5")]).unwrap_err();
+        exp.run([&json!("1234,567")]).unwrap_err();
+
+        let exp = compile_expression(r#"parse_number("1", input)"#, &["input"]).unwrap();
+        let err = exp
+            .run([&json!({"decimal": ",", "thousands": ","})])
+            .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Decimal and thousands separator in parse_number must be different"
+        );
+        let err = exp.run([&json!({"separator": ","})]).unwrap_err();
+        assert_eq!(err.message(), "Unknown option separator in parse_number");
+    }
+
+    #[test]
+    pub fn test_try_parse_number() {
+        let exp = compile_expression(
+            r#"{
+            "test1": try_parse_number(input.value, null, {"decimal": ","}),
+            "test2": try_parse_number("1,234.5", 0, {"decimal": ","}),
+            "test3": try_parse_number("abc", -1),
+            "test4": try_parse_number(true, "x"),
+            "test5": try_parse_number(5, 0),
+            "test6": try_parse_number("1,5", -1),
+            "test7": try_parse_number("-1,234,567", null),
+        }"#,
+            &["input"],
+        )
+        .unwrap();
+
+        let input = json!({"value": "3.141,5"});
+        let result = exp.run([&input]).unwrap();
+
+        assert_eq!(3141.5, result.get("test1").unwrap().as_f64().unwrap());
+        assert_eq!(0, result.get("test2").unwrap().as_i64().unwrap());
+        assert_eq!(-1, result.get("test3").unwrap().as_i64().unwrap());
+        assert_eq!("x", result.get("test4").unwrap().as_str().unwrap());
+        assert_eq!(5, result.get("test5").unwrap().as_i64().unwrap());
+        assert_eq!(-1, result.get("test6").unwrap().as_i64().unwrap());
+        assert_eq!(-1234567, result.get("test7").unwrap().as_i64().unwrap());
+    }
+
+    #[test]
+    pub fn test_parse_number_types() {
+        let exp = compile_expression(r#"parse_number(input)"#, &["input"]).unwrap();
+        let ty = exp.run_types([Type::String]).unwrap();
+        assert_eq!(ty, Type::number());
+
+        let exp = compile_expression(r#"try_parse_number(input, "x")"#, &["input"]).unwrap();
+        let ty = exp.run_types([Type::Boolean]).unwrap();
+        assert_eq!(ty, Type::from_const("x"));
+    }
+
+    #[test]
+    pub fn test_int_from_hex() {
+        let exp = compile_expression(
+            r#"[
+            int_from_hex("ff"),
+            int_from_hex("0x1A2B"),
+            int_from_hex("-0x10"),
+            int_from_hex(" 0XFFFFFFFFFFFFFFFF "),
+            int_from_hex(input),
+        ]"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = json!("0");
+        let result = exp.run([&input]).unwrap();
+        assert_eq!(result.as_ref(), &json!([255, 0x1A2B, -16, u64::MAX, 0]));
+
+        let exp = compile_expression("int_from_hex(input)", &["input"]).unwrap();
+        for inp in ["", "0x", "+ff", "fg", "0x1_0", "10000000000000000"] {
+            let input = json!(inp);
+            let err = exp.run([&input]).unwrap_err();
+            assert_eq!(
+                err.message(),
+                format!("Failed to convert {inp:?} to integer in int_from_hex")
+            );
+        }
+    }
 }
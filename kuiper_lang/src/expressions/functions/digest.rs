@@ -1,9 +1,15 @@
+use std::borrow::Cow;
+
 use base64::Engine;
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 
-use crate::expressions::{
-    numbers::JsonNumber, Expression, ExpressionExecutionState, ResolveResult,
+use crate::{
+    expressions::{
+        numbers::JsonNumber, Expression, ExpressionExecutionState, ExpressionType, ResolveResult,
+    },
+    types::{Type, TypeError, TypeExecutionState},
+    TransformError,
 };
 
 function_def!(DigestFunction, "digest", 1, None);
@@ -68,6 +74,118 @@ impl Expression for DigestFunction {
     }
 }
 
+/// Get the bytes to checksum from `value`, which is either a string, checksummed as UTF-8,
+/// or an array of integers between 0 and 255.
+fn checksum_bytes<'a>(
+    value: &'a Value,
+    func: &str,
+    span: &logos::Span,
+) -> Result<Cow<'a, [u8]>, TransformError> {
+    match value {
+        Value::String(s) => Ok(Cow::Borrowed(s.as_bytes())),
+        Value::Array(a) => a
+            .iter()
+            .map(|v| {
+                v.as_u64()
+                    .and_then(|b| u8::try_from(b).ok())
+                    .ok_or_else(|| {
+                        TransformError::new_invalid_operation(
+                            format!("Bytes passed to {func} must be integers between 0 and 255"),
+                            span,
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Cow::Owned),
+        _ => Err(TransformError::new_incorrect_type(
+            &format!("Incorrect input to {func}"),
+            "string or array",
+            TransformError::value_desc(value),
+            span,
+        )),
+    }
+}
+
+fn checksum_types(
+    arg: &ExpressionType,
+    state: &mut TypeExecutionState<'_, '_>,
+    span: &logos::Span,
+) -> Result<Type, TypeError> {
+    arg.resolve_types(state)?.assert_assignable_to(
+        &Type::String.union_with(Type::array_of_type(Type::Integer)),
+        span,
+    )?;
+    Ok(Type::Integer)
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xEDB88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, b| {
+        CRC32_TABLE[((crc ^ *b as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+fn fletcher16(bytes: &[u8]) -> u16 {
+    let (sum1, sum2) = bytes.iter().fold((0u16, 0u16), |(sum1, sum2), b| {
+        let sum1 = (sum1 + *b as u16) % 255;
+        (sum1, (sum2 + sum1) % 255)
+    });
+    (sum2 << 8) | sum1
+}
+
+function_def!(Crc32Function, "crc32", 1);
+
+impl Expression for Crc32Function {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let value = self.args[0].resolve(state)?;
+        let bytes = checksum_bytes(&value, "crc32", &self.span)?;
+        Ok(ResolveResult::Owned(Value::from(crc32(&bytes))))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        checksum_types(&self.args[0], state, &self.span)
+    }
+}
+
+function_def!(Fletcher16Function, "fletcher16", 1);
+
+impl Expression for Fletcher16Function {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let value = self.args[0].resolve(state)?;
+        let bytes = checksum_bytes(&value, "fletcher16", &self.span)?;
+        Ok(ResolveResult::Owned(Value::from(fletcher16(&bytes))))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        checksum_types(&self.args[0], state, &self.span)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use base64::Engine;
@@ -125,4 +243,41 @@ mod tests {
             .unwrap();
         assert_eq!(t, crate::types::Type::String);
     }
+
+    #[test]
+    fn test_crc32() {
+        let expr = compile_expression(
+            r#"[crc32("123456789"), crc32(""), crc32([49, 50, 51, 52, 53, 54, 55, 56, 57])]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!([0xCBF43926u32, 0, 0xCBF43926u32])
+        );
+    }
+
+    #[test]
+    fn test_fletcher16() {
+        let expr =
+            compile_expression(r#"[fletcher16("abcde"), fletcher16("abcdef")]"#, &[]).unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &serde_json::json!([0xC8F0, 0x2057]));
+    }
+
+    #[test]
+    fn test_checksum_errors() {
+        let expr = compile_expression(r#"crc32(input)"#, &["input"]).unwrap();
+        let err = expr.run([&serde_json::json!([1, 256])]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Bytes passed to crc32 must be integers between 0 and 255"
+        );
+        let err = expr.run([&serde_json::json!(123)]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect input to crc32. Got number, expected string or array"
+        );
+    }
 }
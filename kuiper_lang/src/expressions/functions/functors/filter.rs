@@ -7,6 +7,8 @@ use crate::{
     TransformError,
 };
 
+use super::take_array;
+
 function_def!(FilterFunction, "filter", 2, lambda);
 
 impl Expression for FilterFunction {
@@ -15,26 +17,36 @@ impl Expression for FilterFunction {
         state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
     ) -> Result<crate::expressions::ResolveResult<'a>, TransformError> {
         let source = self.args[0].resolve(state)?;
-
-        match source.into_owned() {
-            Value::Array(x) => {
-                let mut res = Vec::with_capacity(x.len());
-                for item in x {
-                    let should_add = self.args[1].call(state, &[&item])?.as_bool();
-
-                    if should_add {
-                        res.push(item);
-                    }
-                }
-                Ok(ResolveResult::Owned(Value::Array(res)))
-            }
-            x => Err(TransformError::new_incorrect_type(
+        let Value::Array(xs) = source.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
                 "Incorrect input to filter",
                 "array",
-                TransformError::value_desc(&x),
+                TransformError::value_desc(&source),
                 &self.span,
-            )),
+            ));
+        };
+
+        let mut keep = Vec::new();
+        for (idx, item) in xs.iter().enumerate() {
+            let should_add = self.args[1]
+                .call(state, &[item, &Value::Number(idx.into())])?
+                .as_bool();
+            if should_add {
+                keep.push(idx);
+            }
         }
+
+        // Only clone the elements that are kept when filtering a borrowed array.
+        let res = match source {
+            ResolveResult::Borrowed(xs) => keep.into_iter().map(|i| xs[i].clone()).collect(),
+            source => {
+                let mut xs = take_array(source);
+                keep.into_iter()
+                    .map(|i| std::mem::take(&mut xs[i]))
+                    .collect()
+            }
+        };
+        Ok(ResolveResult::Owned(Value::Array(res)))
     }
 
     fn resolve_types(
@@ -51,8 +63,10 @@ impl Expression for FilterFunction {
         let mut end_dynamic = Type::never();
         let mut all_known = true;
         let mut final_elements = Vec::new();
-        for item in arr.elements {
-            let should_add = self.args[1].call_types(state, &[&item])?.truthyness();
+        for (idx, item) in arr.elements.into_iter().enumerate() {
+            let should_add = self.args[1]
+                .call_types(state, &[&item, &Type::from_const(idx)])?
+                .truthyness();
             match should_add {
                 Truthy::Never => (),
                 Truthy::Always if all_known => {
@@ -66,7 +80,7 @@ impl Expression for FilterFunction {
         }
         if let Some(old_end_dynamic) = arr.end_dynamic {
             match self.args[1]
-                .call_types(state, &[&*old_end_dynamic])?
+                .call_types(state, &[&*old_end_dynamic, &Type::Integer])?
                 .truthyness()
             {
                 Truthy::Never => (),
@@ -96,10 +110,10 @@ impl LambdaAcceptFunction for FilterFunction {
             return Err(BuildError::unexpected_lambda(&lambda.span));
         }
         let nargs = lambda.input_names.len();
-        if nargs != 1 {
+        if !(1..=2).contains(&nargs) {
             return Err(BuildError::n_function_args(
                 lambda.span.clone(),
-                "filter takes a function with one argument",
+                "filter takes a function with one or two arguments",
             ));
         }
         Ok(())
@@ -156,4 +170,39 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_filter_borrowed_input() {
+        let expr = compile_expression("input.items.filter(i => i.keep)", &["input"]).unwrap();
+        let input = serde_json::json!({ "items": [
+            { "keep": true, "v": 1 },
+            { "keep": false, "v": 2 },
+            { "keep": true, "v": 3 }
+        ]});
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!([{ "keep": true, "v": 1 }, { "keep": true, "v": 3 }])
+        );
+    }
+
+    #[test]
+    fn test_filter_with_index() {
+        let expr = compile_expression(r#"["a", "b", "c", "d"].filter((x, i) => i % 2 == 0)"#, &[])
+            .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &serde_json::json!(["a", "c"]));
+
+        let expr = compile_expression("input.filter((x, i) => i < 1)", &["input"]).unwrap();
+        let res = expr
+            .run_types([Type::Array(Array {
+                elements: vec![Type::String, Type::Integer],
+                end_dynamic: None,
+            })])
+            .unwrap();
+        assert_eq!(
+            res,
+            Type::array_of_type(Type::String.union_with(Type::Integer))
+        );
+    }
 }
@@ -18,8 +18,10 @@ impl Expression for FlatMapFunction {
         match source.as_ref() {
             Value::Array(x) => {
                 let mut res = Vec::with_capacity(x.len());
-                for val in x {
-                    let res_inner = self.args[1].call(state, &[val])?.into_owned();
+                for (idx, val) in x.iter().enumerate() {
+                    let res_inner = self.args[1]
+                        .call(state, &[val, &Value::Number(idx.into())])?
+                        .into_owned();
                     match res_inner {
                         Value::Array(y) => {
                             for item in y {
@@ -52,8 +54,8 @@ impl Expression for FlatMapFunction {
 
         let mut end_dynamic = Type::never();
 
-        for item in arr.elements {
-            let res = self.args[1].call_types(state, &[&item])?;
+        for (idx, item) in arr.elements.into_iter().enumerate() {
+            let res = self.args[1].call_types(state, &[&item, &Type::from_const(idx)])?;
             if let Ok(r) = res.try_as_array(&self.span) {
                 // If this might _not_ be an array, we need to consider the case where
                 // a non-array value is returned. Since this is uncertain, we no longer know
@@ -80,7 +82,7 @@ impl Expression for FlatMapFunction {
         }
 
         if let Some(arr_end_dynamic) = arr.end_dynamic {
-            let res = self.args[1].call_types(state, &[&*arr_end_dynamic])?;
+            let res = self.args[1].call_types(state, &[&*arr_end_dynamic, &Type::Integer])?;
             if let Ok(r) = res.try_as_array(&self.span) {
                 end_dynamic = end_dynamic.union_with(r.element_union());
             }
@@ -110,10 +112,10 @@ impl LambdaAcceptFunction for FlatMapFunction {
             return Err(BuildError::unexpected_lambda(&lambda.span));
         }
         let nargs = lambda.input_names.len();
-        if nargs != 1 {
+        if !(1..=2).contains(&nargs) {
             return Err(BuildError::n_function_args(
                 lambda.span.clone(),
-                "flatmap takes a function with one argument",
+                "flatmap takes a function with one or two arguments",
             ));
         }
         Ok(())
@@ -198,4 +200,11 @@ mod tests {
         let res = expr.run_types([Type::Any]).unwrap();
         assert_eq!(res, Type::array_of_type(Type::Any));
     }
+
+    #[test]
+    fn test_flatmap_with_index() {
+        let expr = compile_expression(r#"["a", "b"].flatmap((x, i) => [i, x])"#, &[]).unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &serde_json::json!([0, "a", 1, "b"]));
+    }
 }
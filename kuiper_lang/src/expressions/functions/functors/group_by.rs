@@ -0,0 +1,157 @@
+use serde_json::{Map, Value};
+
+use crate::{
+    expressions::{functions::LambdaAcceptFunction, Expression, ResolveResult},
+    types::Type,
+    BuildError, TransformError,
+};
+
+use super::take_array;
+
+function_def!(GroupByFunction, "group_by", 2, lambda);
+
+impl Expression for GroupByFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let Value::Array(xs) = source.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to group_by",
+                "array",
+                TransformError::value_desc(&source),
+                &self.span,
+            ));
+        };
+
+        let mut keys = Vec::with_capacity(xs.len());
+        for (idx, val) in xs.iter().enumerate() {
+            let key = self.args[1].call(state, &[val, &Value::Number(idx.into())])?;
+            keys.push(key.try_as_string("group_by", &self.span)?.into_owned());
+        }
+
+        let mut res = Map::new();
+        for (key, item) in keys.into_iter().zip(take_array(source)) {
+            match res.entry(key).or_insert_with(|| Value::Array(Vec::new())) {
+                Value::Array(group) => group.push(item),
+                _ => unreachable!(),
+            }
+        }
+        Ok(ResolveResult::Owned(Value::Object(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<crate::types::Type, crate::types::TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        let source_arr = source.try_as_array(&self.span)?;
+        let elem = source_arr.element_union();
+        let key = self.args[1].call_types(state, &[&elem, &Type::Integer])?;
+        key.assert_assignable_to(&Type::stringifyable(), &self.span)?;
+        Ok(Type::object_of_type(Type::array_of_type(elem)))
+    }
+}
+
+impl LambdaAcceptFunction for GroupByFunction {
+    fn validate_lambda(
+        idx: usize,
+        lambda: &crate::expressions::LambdaExpression,
+        _num_args: usize,
+    ) -> Result<(), BuildError> {
+        if idx != 1 {
+            return Err(BuildError::unexpected_lambda(&lambda.span));
+        }
+        let nargs = lambda.input_names.len();
+        if !(1..=2).contains(&nargs) {
+            return Err(BuildError::n_function_args(
+                lambda.span.clone(),
+                "group_by takes a function with one or two arguments",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{compile_expression, types::Type};
+
+    #[test]
+    fn test_group_by() {
+        let expr = compile_expression("input.group_by(x => x.deviceId)", &["input"]).unwrap();
+        let input = json!([
+            { "deviceId": "a", "value": 1 },
+            { "deviceId": "b", "value": 2 },
+            { "deviceId": "a", "value": 3 },
+        ]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!({
+                "a": [{ "deviceId": "a", "value": 1 }, { "deviceId": "a", "value": 3 }],
+                "b": [{ "deviceId": "b", "value": 2 }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_group_by_keys() {
+        let expr = compile_expression("[1, 2, 3, 4, 5].group_by(x => x % 2 == 0)", &[]).unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &json!({ "false": [1, 3, 5], "true": [2, 4] }));
+
+        let expr = compile_expression(r#"["a", "b", "c"].group_by((x, i) => i % 2)"#, &[]).unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &json!({ "0": ["a", "c"], "1": ["b"] }));
+
+        let expr = compile_expression("[].group_by(x => x)", &[]).unwrap();
+        assert_eq!(expr.run([]).unwrap().as_ref(), &json!({}));
+
+        // Null keys, including missing fields, are grouped under the empty string.
+        let expr = compile_expression("input.group_by(x => x.id)", &["input"]).unwrap();
+        let input = json!([{ "id": "" }, { "id": null }, {}, { "id": "a" }]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!({ "": [{ "id": "" }, { "id": null }, {}], "a": [{ "id": "a" }] })
+        );
+    }
+
+    #[test]
+    fn test_group_by_errors() {
+        let expr = compile_expression("input.group_by(x => x)", &["input"]).unwrap();
+        let input = json!([[1]]);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "group_by. Got array, expected string or number"
+        );
+        let input = json!({ "a": 1 });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect input to group_by. Got object, expected array"
+        );
+
+        assert!(compile_expression("[1].group_by((a, b, c) => a)", &[]).is_err());
+    }
+
+    #[test]
+    fn test_group_by_types() {
+        let expr = compile_expression("input.group_by(x => x)", &["input"]).unwrap();
+        let res = expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .unwrap();
+        assert_eq!(
+            res,
+            Type::object_of_type(Type::array_of_type(Type::Integer))
+        );
+        assert!(expr
+            .run_types([Type::array_of_type(Type::any_array())])
+            .is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use crate::{
+    expressions::{
+        functions::{compare_values, LambdaAcceptFunction},
+        Expression, ExpressionExecutionState, ExpressionType, ResolveResult,
+    },
+    types::{Type, TypeError, TypeExecutionState},
+    BuildError, TransformError,
+};
+
+use super::take_array;
+
+/// Select the element whose key compares as `target` to the keys of the elements before it.
+/// If several elements have the same key, the first is used.
+fn select_by<'a>(
+    args: &'a [Box<ExpressionType>],
+    state: &mut ExpressionExecutionState<'a, '_>,
+    target: Ordering,
+    func: &str,
+    span: &logos::Span,
+) -> Result<ResolveResult<'a>, TransformError> {
+    let source = args[0].resolve(state)?;
+    let Value::Array(xs) = source.as_ref() else {
+        return Err(TransformError::new_incorrect_type(
+            &format!("Incorrect input to {func}"),
+            "array",
+            TransformError::value_desc(&source),
+            span,
+        ));
+    };
+
+    let mut best: Option<(usize, Value)> = None;
+    for (idx, val) in xs.iter().enumerate() {
+        let key = args[1]
+            .call(state, &[val, &Value::Number(idx.into())])?
+            .into_owned();
+        let replace = match &best {
+            Some((_, best_key)) => compare_values(&key, best_key, func, span)? == target,
+            None => true,
+        };
+        if replace {
+            best = Some((idx, key));
+        }
+    }
+
+    let Some((idx, _)) = best else {
+        return Ok(ResolveResult::Owned(Value::Null));
+    };
+    Ok(match source {
+        ResolveResult::Borrowed(xs) => ResolveResult::Borrowed(&xs[idx]),
+        source => ResolveResult::Owned(take_array(source).swap_remove(idx)),
+    })
+}
+
+fn select_by_types(
+    args: &[Box<ExpressionType>],
+    state: &mut TypeExecutionState<'_, '_>,
+    span: &logos::Span,
+) -> Result<Type, TypeError> {
+    let source = args[0].resolve_types(state)?;
+    let source_arr = source.try_as_array(span)?;
+    let elem = source_arr.element_union();
+    let key = args[1].call_types(state, &[&elem, &Type::Integer])?;
+    key.assert_assignable_to(&Type::String.union_with(Type::number()), span)?;
+    // The result is null if the array is empty.
+    if source_arr.elements.is_empty() {
+        Ok(elem.nullable())
+    } else {
+        Ok(elem)
+    }
+}
+
+fn validate_key_lambda(
+    idx: usize,
+    lambda: &crate::expressions::LambdaExpression,
+    func: &str,
+) -> Result<(), BuildError> {
+    if idx != 1 {
+        return Err(BuildError::unexpected_lambda(&lambda.span));
+    }
+    let nargs = lambda.input_names.len();
+    if !(1..=2).contains(&nargs) {
+        return Err(BuildError::n_function_args(
+            lambda.span.clone(),
+            &format!("{func} takes a function with one or two arguments"),
+        ));
+    }
+    Ok(())
+}
+
+function_def!(MinByFunction, "min_by", 2, lambda);
+
+impl Expression for MinByFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        select_by(&self.args, state, Ordering::Less, "min_by", &self.span)
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        select_by_types(&self.args, state, &self.span)
+    }
+}
+
+impl LambdaAcceptFunction for MinByFunction {
+    fn validate_lambda(
+        idx: usize,
+        lambda: &crate::expressions::LambdaExpression,
+        _num_args: usize,
+    ) -> Result<(), BuildError> {
+        validate_key_lambda(idx, lambda, "min_by")
+    }
+}
+
+function_def!(MaxByFunction, "max_by", 2, lambda);
+
+impl Expression for MaxByFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        select_by(&self.args, state, Ordering::Greater, "max_by", &self.span)
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        select_by_types(&self.args, state, &self.span)
+    }
+}
+
+impl LambdaAcceptFunction for MaxByFunction {
+    fn validate_lambda(
+        idx: usize,
+        lambda: &crate::expressions::LambdaExpression,
+        _num_args: usize,
+    ) -> Result<(), BuildError> {
+        validate_key_lambda(idx, lambda, "max_by")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{compile_expression, types::Type};
+
+    #[test]
+    fn test_min_max_by() {
+        let input = json!([
+            { "timestamp": 2, "value": "b" },
+            { "timestamp": 3, "value": "c" },
+            { "timestamp": 1, "value": "a" },
+            { "timestamp": 3, "value": "d" },
+        ]);
+        let expr = compile_expression(
+            "[input.min_by(x => x.timestamp), input.max_by(x => x.timestamp)]",
+            &["input"],
+        )
+        .unwrap();
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([{ "timestamp": 1, "value": "a" }, { "timestamp": 3, "value": "c" }])
+        );
+
+        let expr = compile_expression(
+            r#"[["bb", "a", "ccc"].max_by(x => length(x)), ["b", "a"].min_by(x => x), [].max_by(x => x)]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &json!(["ccc", "a", null]));
+    }
+
+    #[test]
+    fn test_min_max_by_errors() {
+        let expr = compile_expression("input.max_by(x => x)", &["input"]).unwrap();
+        let input = json!([1, "a"]);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(err.message(), "Cannot compare string and number in max_by");
+        let input = json!({ "a": 1 });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect input to max_by. Got object, expected array"
+        );
+
+        assert!(compile_expression("[1].min_by((a, b, c) => a)", &[]).is_err());
+    }
+
+    #[test]
+    fn test_min_max_by_types() {
+        let expr = compile_expression("input.min_by(x => x)", &["input"]).unwrap();
+        let res = expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .unwrap();
+        assert_eq!(res, Type::Integer.nullable());
+        assert!(expr
+            .run_types([Type::array_of_type(Type::any_object())])
+            .is_err());
+    }
+}
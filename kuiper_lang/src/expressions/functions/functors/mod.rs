@@ -2,9 +2,26 @@ pub mod distinct_by;
 pub mod except;
 pub mod filter;
 pub mod flatmap;
+pub mod group_by;
 pub mod if_value;
 pub mod map;
+pub mod min_max_by;
 pub mod objects;
 pub mod reduce;
 pub mod select;
+pub mod sort;
+pub mod sort_with;
 pub mod zip;
+
+use serde_json::Value;
+
+use crate::expressions::ResolveResult;
+
+/// Take the elements of the array in `source`, cloning them if it is borrowed.
+/// Callers must already have checked that `source` is an array.
+pub(crate) fn take_array(source: ResolveResult<'_>) -> Vec<Value> {
+    match source.into_owned() {
+        Value::Array(xs) => xs,
+        _ => unreachable!("take_array called with a value that is not an array"),
+    }
+}
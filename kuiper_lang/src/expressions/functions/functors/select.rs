@@ -14,60 +14,70 @@ impl Expression for SelectFunction {
         state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
     ) -> Result<crate::expressions::ResolveResult<'a>, crate::TransformError> {
         let source = self.args[0].resolve(state)?;
-        match source.into_owned() {
-            Value::Object(x) => {
-                let mut output = Map::new();
-                match &*self.args[1] {
-                    crate::ExpressionType::Lambda(expr) => {
-                        for (k, v) in x {
-                            let should_add = expr
-                                .call(state, &[&v, &Value::String(k.to_owned())])?
-                                .as_bool();
+        let mut output = Map::new();
+        match (source, &*self.args[1]) {
+            (ResolveResult::Owned(Value::Object(x)), crate::ExpressionType::Lambda(expr)) => {
+                for (k, v) in x {
+                    let should_add = expr
+                        .call(state, &[&v, &Value::String(k.to_owned())])?
+                        .as_bool();
+                    if should_add {
+                        output.insert(k, v);
+                    }
+                }
+                Ok(ResolveResult::Owned(Value::Object(output)))
+            }
+            // Only clone the fields that are kept when selecting from a borrowed object.
+            (ResolveResult::Borrowed(Value::Object(x)), crate::ExpressionType::Lambda(expr)) => {
+                for (k, v) in x {
+                    let should_add = expr
+                        .call(state, &[v, &Value::String(k.to_owned())])?
+                        .as_bool();
+                    if should_add {
+                        output.insert(k.to_owned(), v.clone());
+                    }
+                }
+                Ok(ResolveResult::Owned(Value::Object(output)))
+            }
+            (source, expr) => {
+                let Value::Object(x) = source.as_ref() else {
+                    return Err(TransformError::new_incorrect_type(
+                        "Incorrect input passed as first argument to except",
+                        "object",
+                        TransformError::value_desc(&source),
+                        &self.span,
+                    ));
+                };
+                let res = expr.resolve(state)?;
+                match res.into_owned() {
+                    Value::Array(arr) => {
+                        for f in arr {
+                            let (should_add, k, v) = match f {
+                                Value::String(k) => match x.get(&k) {
+                                    Some(val) => Ok((true, k, val.to_owned())),
+                                    None => Ok((false, k, Value::Null)),
+                                },
+                                x => Err(TransformError::new_incorrect_type(
+                                    "Filter values should be of type string",
+                                    "string",
+                                    TransformError::value_desc(&x),
+                                    &self.span,
+                                )),
+                            }?;
                             if should_add {
                                 output.insert(k, v);
                             }
                         }
                         Ok(ResolveResult::Owned(Value::Object(output)))
                     }
-                    expr => {
-                        let res = expr.resolve(state)?;
-                        match res.into_owned() {
-                            Value::Array(arr) => {
-                                for f in arr {
-                                    let (should_add, k, v) = match f {
-                                        Value::String(k) => match x.get(&k) {
-                                            Some(val) => Ok((true, k, val.to_owned())),
-                                            None => Ok((false, k, Value::Null)),
-                                        },
-                                        x => Err(TransformError::new_incorrect_type(
-                                            "Filter values should be of type string",
-                                            "string",
-                                            TransformError::value_desc(&x),
-                                            &self.span,
-                                        )),
-                                    }?;
-                                    if should_add {
-                                        output.insert(k, v);
-                                    }
-                                }
-                                Ok(ResolveResult::Owned(Value::Object(output)))
-                            }
-                            x => Err(TransformError::new_incorrect_type(
-                                "Incorrect input passed as second argument to except",
-                                "array, lambda",
-                                TransformError::value_desc(&x),
-                                &self.span,
-                            )),
-                        }
-                    }
+                    x => Err(TransformError::new_incorrect_type(
+                        "Incorrect input passed as second argument to except",
+                        "array, lambda",
+                        TransformError::value_desc(&x),
+                        &self.span,
+                    )),
                 }
             }
-            x => Err(TransformError::new_incorrect_type(
-                "Incorrect input passed as first argument to except",
-                "object",
-                TransformError::value_desc(&x),
-                &self.span,
-            )),
         }
     }
 
@@ -255,4 +265,22 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_select_borrowed_input() {
+        let expr = compile_expression(
+            "{ 'lambda': input.select((v, k) => k != 'b'), 'keys': input.select(['b', 'd']) }",
+            &["input"],
+        )
+        .unwrap();
+        let input = serde_json::json!({ "a": 1, "b": { "c": 2 }, "d": [3] });
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!({
+                "lambda": { "a": 1, "d": [3] },
+                "keys": { "b": { "c": 2 }, "d": [3] }
+            })
+        );
+    }
 }
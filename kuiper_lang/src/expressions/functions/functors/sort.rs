@@ -0,0 +1,240 @@
+use logos::Span;
+use serde_json::Value;
+
+use crate::{
+    expressions::{
+        functions::{compare_values, LambdaAcceptFunction},
+        Expression, ExpressionExecutionState, ResolveResult,
+    },
+    types::{Type, TypeError, TypeExecutionState},
+    BuildError, ExpressionType, TransformError,
+};
+
+use super::{sort_with::merge_sort_by, take_array};
+
+/// Get the indices of `keys` in sorted order. The sort is stable, also when descending.
+fn sorted_order(
+    keys: &[&Value],
+    descending: bool,
+    func: &str,
+    span: &Span,
+) -> Result<Vec<usize>, TransformError> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    merge_sort_by(&mut order, |a, b| {
+        let ord = compare_values(keys[a], keys[b], func, span)?;
+        Ok(if descending { ord.reverse() } else { ord })
+    })?;
+    Ok(order)
+}
+
+/// Reorder the array in `source` according to `order`.
+fn reorder(source: ResolveResult<'_>, order: Vec<usize>) -> Value {
+    let mut xs = take_array(source);
+    Value::Array(
+        order
+            .into_iter()
+            .map(|i| std::mem::take(&mut xs[i]))
+            .collect(),
+    )
+}
+
+fn resolve_descending<'a>(
+    arg: Option<&'a ExpressionType>,
+    state: &mut ExpressionExecutionState<'a, '_>,
+) -> Result<bool, TransformError> {
+    match arg {
+        Some(arg) => Ok(arg.resolve(state)?.as_bool()),
+        None => Ok(false),
+    }
+}
+
+fn incorrect_input(func: &str, source: &Value, span: &Span) -> TransformError {
+    TransformError::new_incorrect_type(
+        &format!("Incorrect input to {func}"),
+        "array",
+        TransformError::value_desc(source),
+        span,
+    )
+}
+
+function_def!(SortFunction, "sort", 1, Some(2));
+
+impl Expression for SortFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let Value::Array(xs) = source.as_ref() else {
+            return Err(incorrect_input("sort", &source, &self.span));
+        };
+        let descending = resolve_descending(self.args.get(1), state)?;
+        let keys: Vec<&Value> = xs.iter().collect();
+        let order = sorted_order(&keys, descending, "sort", &self.span)?;
+        Ok(ResolveResult::Owned(reorder(source, order)))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        let source_arr = source.try_as_array(&self.span)?;
+        if let Some(descending) = self.args.get(1) {
+            descending.resolve_types(state)?;
+        }
+        Ok(Type::array_of_type(source_arr.element_union()))
+    }
+}
+
+function_def!(SortByFunction, "sort_by", 2, Some(3), lambda);
+
+impl Expression for SortByFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let Value::Array(xs) = source.as_ref() else {
+            return Err(incorrect_input("sort_by", &source, &self.span));
+        };
+        let descending = resolve_descending(self.args.get(2), state)?;
+        let mut keys = Vec::with_capacity(xs.len());
+        for (idx, val) in xs.iter().enumerate() {
+            keys.push(
+                self.args[1]
+                    .call(state, &[val, &Value::Number(idx.into())])?
+                    .into_owned(),
+            );
+        }
+        let keys: Vec<&Value> = keys.iter().collect();
+        let order = sorted_order(&keys, descending, "sort_by", &self.span)?;
+        Ok(ResolveResult::Owned(reorder(source, order)))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        let source_arr = source.try_as_array(&self.span)?;
+        let elem = source_arr.element_union();
+        let key = self.args[1].call_types(state, &[&elem, &Type::Integer])?;
+        key.assert_assignable_to(&Type::String.union_with(Type::number()), &self.span)?;
+        if let Some(descending) = self.args.get(2) {
+            descending.resolve_types(state)?;
+        }
+        Ok(Type::array_of_type(elem))
+    }
+}
+
+impl LambdaAcceptFunction for SortByFunction {
+    fn validate_lambda(
+        idx: usize,
+        lambda: &crate::expressions::LambdaExpression,
+        _num_args: usize,
+    ) -> Result<(), BuildError> {
+        if idx != 1 {
+            return Err(BuildError::unexpected_lambda(&lambda.span));
+        }
+        let nargs = lambda.input_names.len();
+        if !(1..=2).contains(&nargs) {
+            return Err(BuildError::n_function_args(
+                lambda.span.clone(),
+                "sort_by takes a function with one or two arguments",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{compile_expression, types::Type};
+
+    #[test]
+    fn test_sort() {
+        let expr = compile_expression(
+            r#"[[3, 1.5, -2, 10].sort(), ["b", "c", "a"].sort(), [3, 1, 2].sort(true), [].sort()]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([[-2, 1.5, 3, 10], ["a", "b", "c"], [3, 2, 1], []])
+        );
+    }
+
+    #[test]
+    fn test_sort_by() {
+        let expr = compile_expression("input.sort_by(x => x.timestamp)", &["input"]).unwrap();
+        let input = json!([
+            { "timestamp": 3, "value": "c" },
+            { "timestamp": 1, "value": "a" },
+            { "timestamp": 2, "value": "b" },
+        ]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([
+                { "timestamp": 1, "value": "a" },
+                { "timestamp": 2, "value": "b" },
+                { "timestamp": 3, "value": "c" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_sort_by_is_stable() {
+        let input = json!([
+            { "k": 1, "id": "a" },
+            { "k": 0, "id": "b" },
+            { "k": 1, "id": "c" },
+            { "k": 0, "id": "d" },
+        ]);
+        let expr =
+            compile_expression("input.sort_by(x => x.k).map(x => x.id)", &["input"]).unwrap();
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res.as_ref(), &json!(["b", "d", "a", "c"]));
+
+        let expr =
+            compile_expression("input.sort_by(x => x.k, true).map(x => x.id)", &["input"]).unwrap();
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res.as_ref(), &json!(["a", "c", "b", "d"]));
+    }
+
+    #[test]
+    fn test_sort_errors() {
+        let expr = compile_expression("input.sort()", &["input"]).unwrap();
+        let input = json!([1, "a"]);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(err.message(), "Cannot compare string and number in sort");
+        let input = json!({ "a": 1 });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect input to sort. Got object, expected array"
+        );
+
+        let expr = compile_expression("input.sort_by(x => x.k)", &["input"]).unwrap();
+        let input = json!([{ "k": 1 }, { "k": null }]);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(err.message(), "Cannot compare null and number in sort_by");
+
+        assert!(compile_expression("[1].sort_by((a, b, c) => a)", &[]).is_err());
+    }
+
+    #[test]
+    fn test_sort_types() {
+        let expr = compile_expression("input.sort_by(x => x.k)", &["input"]).unwrap();
+        let elem = Type::Object(crate::types::Object::default().with_field("k", Type::Integer));
+        let res = expr.run_types([Type::array_of_type(elem.clone())]).unwrap();
+        assert_eq!(res, Type::array_of_type(elem));
+
+        let expr = compile_expression("input.sort_by(x => [x])", &["input"]).unwrap();
+        assert!(expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .is_err());
+
+        let expr = compile_expression("input.sort()", &["input"]).unwrap();
+        let res = expr.run_types([Type::array_of_type(Type::String)]).unwrap();
+        assert_eq!(res, Type::array_of_type(Type::String));
+    }
+}
@@ -0,0 +1,213 @@
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use crate::{
+    expressions::{functions::LambdaAcceptFunction, Expression, ResolveResult},
+    types::Type,
+    BuildError, TransformError,
+};
+
+use super::take_array;
+
+function_def!(SortWithFunction, "sort_with", 2, lambda);
+
+/// Stable bottom-up merge sort of `items` using a fallible comparator.
+/// The standard library sorts may panic if the comparator is not a total order,
+/// which we cannot guarantee for user-provided comparators.
+pub(crate) fn merge_sort_by<E>(
+    items: &mut Vec<usize>,
+    mut cmp: impl FnMut(usize, usize) -> Result<Ordering, E>,
+) -> Result<(), E> {
+    let len = items.len();
+    let mut buf = items.clone();
+    let mut width = 1;
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            let (mut i, mut j) = (start, mid);
+            for slot in &mut buf[start..end] {
+                // Only take from the right half if it is strictly less, to keep the sort stable.
+                if j < end && (i >= mid || cmp(items[j], items[i])? == Ordering::Less) {
+                    *slot = items[j];
+                    j += 1;
+                } else {
+                    *slot = items[i];
+                    i += 1;
+                }
+            }
+        }
+        std::mem::swap(items, &mut buf);
+        width *= 2;
+    }
+    Ok(())
+}
+
+impl Expression for SortWithFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let Value::Array(xs) = source.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to sort_with",
+                "array",
+                TransformError::value_desc(&source),
+                &self.span,
+            ));
+        };
+
+        let mut order: Vec<usize> = (0..xs.len()).collect();
+        merge_sort_by(&mut order, |a, b| {
+            let res = self.args[1].call(state, &[&xs[a], &xs[b]])?;
+            match res.as_ref() {
+                Value::Number(n) => Ok(n.as_f64().unwrap_or_default().total_cmp(&0.0)),
+                other => Err(TransformError::new_incorrect_type(
+                    "Comparator in sort_with must return a number",
+                    "number",
+                    TransformError::value_desc(other),
+                    &self.span,
+                )),
+            }
+        })?;
+
+        let mut xs = take_array(source);
+        let res = order
+            .into_iter()
+            .map(|i| std::mem::take(&mut xs[i]))
+            .collect();
+        Ok(ResolveResult::Owned(Value::Array(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<crate::types::Type, crate::types::TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        let source_arr = source.try_as_array(&self.span)?;
+        let elem = source_arr.element_union();
+        let res = self.args[1].call_types(state, &[&elem, &elem])?;
+        res.assert_assignable_to(&Type::number(), &self.span)?;
+        Ok(Type::array_of_type(elem))
+    }
+}
+
+impl LambdaAcceptFunction for SortWithFunction {
+    fn validate_lambda(
+        idx: usize,
+        lambda: &crate::expressions::LambdaExpression,
+        _num_args: usize,
+    ) -> Result<(), BuildError> {
+        if idx != 1 {
+            return Err(BuildError::unexpected_lambda(&lambda.span));
+        }
+        let nargs = lambda.input_names.len();
+        if nargs != 2 {
+            return Err(BuildError::n_function_args(
+                lambda.span.clone(),
+                "sort_with takes a function with two arguments",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{compile_expression, types::Type};
+
+    #[test]
+    fn test_sort_with_numbers() {
+        let expr = compile_expression(
+            "[5, 3, 9, 1, 3, 7, 2].sort_with((a, b) => if(a < b, -1, if(a > b, 1, 0)))",
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &json!([1, 2, 3, 3, 5, 7, 9]));
+    }
+
+    #[test]
+    fn test_sort_with_multiple_fields() {
+        // Sort by group ascending, then by value descending.
+        let expr = compile_expression(
+            r#"input.sort_with((a, b) =>
+                if(a.group != b.group,
+                    if(a.group < b.group, -1, 1),
+                    b.value - a.value))"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = json!([
+            { "group": "b", "value": 1 },
+            { "group": "a", "value": 2 },
+            { "group": "b", "value": 5 },
+            { "group": "a", "value": 7 },
+        ]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([
+                { "group": "a", "value": 7 },
+                { "group": "a", "value": 2 },
+                { "group": "b", "value": 5 },
+                { "group": "b", "value": 1 },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_sort_with_is_stable() {
+        let expr = compile_expression(
+            "input.sort_with((a, b) => a.k - b.k).map(x => x.id)",
+            &["input"],
+        )
+        .unwrap();
+        let input = json!([
+            { "k": 1, "id": "a" },
+            { "k": 0, "id": "b" },
+            { "k": 1, "id": "c" },
+            { "k": 0, "id": "d" },
+            { "k": 1, "id": "e" },
+        ]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res.as_ref(), &json!(["b", "d", "a", "c", "e"]));
+    }
+
+    #[test]
+    fn test_sort_with_bad_comparator() {
+        let expr = compile_expression("input.sort_with((a, b) => a)", &["input"]).unwrap();
+        let input = json!(["x", "y"]);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Comparator in sort_with must return a number. Got string, expected number"
+        );
+
+        // An inconsistent comparator gives some order, but does not fail.
+        let expr = compile_expression("input.sort_with((a, b) => -1)", &["input"]).unwrap();
+        let input = json!([1, 2, 3, 4, 5]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res.as_array().unwrap().len(), 5);
+
+        assert!(compile_expression("[1, 2].sort_with(a => a)", &[]).is_err());
+    }
+
+    #[test]
+    fn test_sort_with_types() {
+        let expr = compile_expression("input.sort_with((a, b) => a - b)", &["input"]).unwrap();
+        let res = expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .unwrap();
+        assert_eq!(res, Type::array_of_type(Type::Integer));
+
+        let expr = compile_expression("input.sort_with((a, b) => 'x')", &["input"]).unwrap();
+        assert!(expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .is_err());
+    }
+}
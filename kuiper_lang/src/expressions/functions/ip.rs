@@ -0,0 +1,271 @@
+use std::net::IpAddr;
+
+use logos::Span;
+use serde_json::{Map, Value};
+
+use crate::{
+    expressions::{Expression, ExpressionExecutionState, ResolveResult},
+    types::{Object, Type, TypeError, TypeExecutionState},
+    TransformError,
+};
+
+/// Parse `value` as an IP address, failing if it is not a string or not a valid address.
+fn ip_arg(value: &Value, func: &str, span: &Span) -> Result<IpAddr, TransformError> {
+    let Value::String(s) = value else {
+        return Err(TransformError::new_incorrect_type(
+            &format!("Incorrect input to {func}"),
+            "string",
+            TransformError::value_desc(value),
+            span,
+        ));
+    };
+    s.parse().map_err(|_| {
+        TransformError::new_conversion_failed(
+            format!("Failed to parse {s:?} as an IP address in {func}"),
+            span,
+        )
+    })
+}
+
+/// Parse a CIDR range like `10.0.0.0/8`. A plain address is treated as a range
+/// containing only that address.
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u32)> {
+    let (addr, prefix) = match cidr.split_once('/') {
+        Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, Some(prefix)),
+        None => (cidr.parse::<IpAddr>().ok()?, None),
+    };
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = match prefix {
+        Some(p) => p.parse::<u32>().ok().filter(|p| *p <= max)?,
+        None => max,
+    };
+    Some((addr, prefix))
+}
+
+fn in_cidr(ip: IpAddr, net: IpAddr, prefix: u32) -> bool {
+    match (ip, net) {
+        (IpAddr::V4(ip), IpAddr::V4(net)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(net) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(net)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(net) & mask
+        }
+        _ => false,
+    }
+}
+
+function_def!(ParseIpFunction, "parse_ip", 1);
+
+impl Expression for ParseIpFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let value = self.args[0].resolve(state)?;
+        let Some(ip) = value.as_str().and_then(|s| s.parse::<IpAddr>().ok()) else {
+            return Ok(ResolveResult::Owned(Value::Null));
+        };
+        let (version, is_private) = match ip {
+            IpAddr::V4(v4) => (4, v4.is_private()),
+            // Unique local addresses, fc00::/7, are the IPv6 equivalent of private addresses.
+            IpAddr::V6(v6) => (6, v6.segments()[0] & 0xfe00 == 0xfc00),
+        };
+        let mut res = Map::new();
+        res.insert("address".to_owned(), Value::String(ip.to_string()));
+        res.insert("version".to_owned(), Value::from(version));
+        res.insert("is_loopback".to_owned(), Value::Bool(ip.is_loopback()));
+        res.insert("is_private".to_owned(), Value::Bool(is_private));
+        res.insert("is_multicast".to_owned(), Value::Bool(ip.is_multicast()));
+        Ok(ResolveResult::Owned(Value::Object(res)))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        self.args[0].resolve_types(state)?;
+        Ok(Type::Object(
+            Object::default()
+                .with_field("address", Type::String)
+                .with_field("version", Type::Integer)
+                .with_field("is_loopback", Type::Boolean)
+                .with_field("is_private", Type::Boolean)
+                .with_field("is_multicast", Type::Boolean),
+        )
+        .union_with(Type::null()))
+    }
+}
+
+function_def!(IpInCidrFunction, "ip_in_cidr", 2);
+
+impl Expression for IpInCidrFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let ip = ip_arg(
+            self.args[0].resolve(state)?.as_ref(),
+            "ip_in_cidr",
+            &self.span,
+        )?;
+        let cidr = self.args[1].resolve(state)?;
+        let cidr = cidr.try_as_string("ip_in_cidr", &self.span)?;
+        let Some((net, prefix)) = parse_cidr(&cidr) else {
+            return Err(TransformError::new_conversion_failed(
+                format!("Failed to parse {cidr:?} as a CIDR range in ip_in_cidr"),
+                &self.span,
+            ));
+        };
+        Ok(ResolveResult::Owned(Value::Bool(in_cidr(ip, net, prefix))))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        for arg in &self.args {
+            arg.resolve_types(state)?
+                .assert_assignable_to(&Type::String, &self.span)?;
+        }
+        Ok(Type::Boolean)
+    }
+}
+
+function_def!(IpToIntFunction, "ip_to_int", 1);
+
+impl Expression for IpToIntFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let ip = ip_arg(
+            self.args[0].resolve(state)?.as_ref(),
+            "ip_to_int",
+            &self.span,
+        )?;
+        let v4 = match ip {
+            IpAddr::V4(v4) => v4,
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().ok_or_else(|| {
+                TransformError::new_invalid_operation(
+                    "ip_to_int only supports IPv4 addresses, IPv6 addresses do not fit in a number"
+                        .to_owned(),
+                    &self.span,
+                )
+            })?,
+        };
+        Ok(ResolveResult::Owned(Value::from(u32::from(v4))))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        self.args[0]
+            .resolve_types(state)?
+            .assert_assignable_to(&Type::String, &self.span)?;
+        Ok(Type::Integer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{compile_expression, types::Type};
+
+    #[test]
+    fn test_parse_ip() {
+        let expr = compile_expression("parse_ip(input)", &["input"]).unwrap();
+        let run = |input: serde_json::Value| expr.run([&input]).unwrap().into_owned();
+        let res = run(json!("192.168.1.10"));
+        assert_eq!(
+            res,
+            json!({
+                "address": "192.168.1.10",
+                "version": 4,
+                "is_loopback": false,
+                "is_private": true,
+                "is_multicast": false,
+            })
+        );
+        let res = run(json!("0:0:0:0:0:0:0:1"));
+        assert_eq!(
+            res,
+            json!({
+                "address": "::1",
+                "version": 6,
+                "is_loopback": true,
+                "is_private": false,
+                "is_multicast": false,
+            })
+        );
+        let res = run(json!("fd12::1"));
+        assert_eq!(res["is_private"], json!(true));
+        let res = run(json!("999.1.1.1"));
+        assert_eq!(res, json!(null));
+        let res = run(json!(123));
+        assert_eq!(res, json!(null));
+    }
+
+    #[test]
+    fn test_ip_in_cidr() {
+        let expr = compile_expression(
+            r#"[
+                ip_in_cidr("10.1.2.3", "10.0.0.0/8"),
+                ip_in_cidr("11.1.2.3", "10.0.0.0/8"),
+                ip_in_cidr("10.1.2.3", "0.0.0.0/0"),
+                ip_in_cidr("10.1.2.3", "10.1.2.3"),
+                ip_in_cidr("10.1.2.3", "10.1.2.4/32"),
+                ip_in_cidr("2001:db8::1", "2001:db8::/32"),
+                ip_in_cidr("2001:db9::1", "2001:db8::/32"),
+                ip_in_cidr("10.1.2.3", "::/0"),
+            ]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([true, false, true, true, false, true, false, false])
+        );
+    }
+
+    #[test]
+    fn test_ip_in_cidr_errors() {
+        let expr = compile_expression("ip_in_cidr(input.ip, input.cidr)", &["input"]).unwrap();
+        let input = json!({"ip": "10.0.0.1", "cidr": "10.0.0.0/33"});
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            r#"Failed to parse "10.0.0.0/33" as a CIDR range in ip_in_cidr"#
+        );
+        let input = json!({"ip": "foo", "cidr": "10.0.0.0/8"});
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            r#"Failed to parse "foo" as an IP address in ip_in_cidr"#
+        );
+    }
+
+    #[test]
+    fn test_ip_to_int() {
+        let expr = compile_expression(
+            r#"[ip_to_int("10.0.0.1"), ip_to_int("255.255.255.255"), ip_to_int("::ffff:0.0.1.0")]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &json!([167772161, 4294967295u32, 256]));
+
+        let expr = compile_expression("ip_to_int(input)", &["input"]).unwrap();
+        let input = json!("2001:db8::1");
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "ip_to_int only supports IPv4 addresses, IPv6 addresses do not fit in a number"
+        );
+    }
+
+    #[test]
+    fn test_ip_types() {
+        let expr = compile_expression("ip_in_cidr(input, \"10.0.0.0/8\")", &["input"]).unwrap();
+        assert_eq!(expr.run_types([Type::String]).unwrap(), Type::Boolean);
+        expr.run_types([Type::Integer]).unwrap_err();
+
+        let expr = compile_expression("ip_to_int(input)", &["input"]).unwrap();
+        assert_eq!(expr.run_types([Type::String]).unwrap(), Type::Integer);
+    }
+}
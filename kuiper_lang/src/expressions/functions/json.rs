@@ -2,6 +2,7 @@ use serde_json::Value;
 
 use crate::{
     expressions::{Expression, ResolveResult},
+    types::Type,
     TransformError,
 };
 
@@ -43,6 +44,405 @@ impl Expression for ParseJsonFunction {
     }
 }
 
+function_def!(GetFunction, "get", 2, Some(3));
+
+/// A single step in a path passed to `get` or `mask`.
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(i64),
+}
+
+/// Parse a path like `a.b[0]["c.d"]` into a list of segments.
+fn parse_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let mut segments = Vec::new();
+    let mut chars = path.char_indices().peekable();
+    // Whether we are at the start of the path, or just after a `.`.
+    let mut expect_key = true;
+    while let Some(&(idx, c)) = chars.peek() {
+        match c {
+            '[' => {
+                chars.next();
+                match chars.peek() {
+                    Some(&(_, quote @ ('"' | '\''))) => {
+                        chars.next();
+                        let mut key = String::new();
+                        loop {
+                            match chars.next() {
+                                Some((_, '\\')) => match chars.next() {
+                                    Some((_, c)) => key.push(c),
+                                    None => return Err("Unterminated string".to_owned()),
+                                },
+                                Some((_, c)) if c == quote => break,
+                                Some((_, c)) => key.push(c),
+                                None => return Err("Unterminated string".to_owned()),
+                            }
+                        }
+                        if !matches!(chars.next(), Some((_, ']'))) {
+                            return Err(format!("Expected ']' after key at position {idx}"));
+                        }
+                        segments.push(PathSegment::Key(key));
+                    }
+                    _ => {
+                        let mut index = String::new();
+                        loop {
+                            match chars.next() {
+                                Some((_, ']')) => break,
+                                Some((_, c)) => index.push(c),
+                                None => return Err(format!("Unterminated '[' at position {idx}")),
+                            }
+                        }
+                        let index = index.trim().parse::<i64>().map_err(|_| {
+                            format!("Expected integer or quoted key in '[]' at position {idx}")
+                        })?;
+                        segments.push(PathSegment::Index(index));
+                    }
+                }
+                expect_key = false;
+            }
+            '.' if !expect_key => {
+                chars.next();
+                expect_key = true;
+            }
+            _ if expect_key => {
+                let mut key = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c == '.' || c == '[' {
+                        break;
+                    }
+                    key.push(c);
+                    chars.next();
+                }
+                if key.is_empty() {
+                    return Err(format!("Empty key at position {idx}"));
+                }
+                segments.push(PathSegment::Key(key));
+                expect_key = false;
+            }
+            c => return Err(format!("Unexpected character '{c}' at position {idx}")),
+        }
+    }
+    if expect_key && !segments.is_empty() {
+        return Err("Path cannot end with '.'".to_owned());
+    }
+    Ok(segments)
+}
+
+/// Get the index into an array of length `len`, counting from the end if `index` is negative.
+fn array_index(len: usize, index: i64) -> Option<usize> {
+    let index = if index < 0 {
+        len.checked_sub(index.unsigned_abs() as usize)?
+    } else {
+        index as usize
+    };
+    (index < len).then_some(index)
+}
+
+fn get_path<'a>(mut value: &'a Value, path: &[PathSegment]) -> Option<&'a Value> {
+    for segment in path {
+        value = match (segment, value) {
+            (PathSegment::Key(k), Value::Object(o)) => o.get(k)?,
+            (PathSegment::Index(i), Value::Array(a)) => &a[array_index(a.len(), *i)?],
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn get_path_mut<'a>(mut value: &'a mut Value, path: &[PathSegment]) -> Option<&'a mut Value> {
+    for segment in path {
+        value = match (segment, value) {
+            (PathSegment::Key(k), Value::Object(o)) => o.get_mut(k)?,
+            (PathSegment::Index(i), Value::Array(a)) => {
+                let idx = array_index(a.len(), *i)?;
+                &mut a[idx]
+            }
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+impl Expression for GetFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let path = self.args[1].resolve(state)?;
+        let path = parse_path(&path.try_as_string("get", &self.span)?).map_err(|e| {
+            TransformError::new_invalid_operation(format!("Invalid path in get: {e}"), &self.span)
+        })?;
+
+        let found = match source {
+            ResolveResult::Borrowed(v) => get_path(v, &path).map(ResolveResult::Borrowed),
+            ResolveResult::Owned(mut v) => {
+                get_path_mut(&mut v, &path).map(|v| ResolveResult::Owned(std::mem::take(v)))
+            }
+        };
+
+        match (found, self.args.get(2)) {
+            (Some(v), _) => Ok(v),
+            (None, Some(default)) => default.resolve(state),
+            (None, None) => Ok(ResolveResult::Owned(Value::Null)),
+        }
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        self.args[0].resolve_types(state)?;
+        let path = self.args[1].resolve_types(state)?;
+        path.assert_assignable_to(&Type::stringifyable(), &self.span)?;
+        if let Some(default) = self.args.get(2) {
+            default.resolve_types(state)?;
+        }
+        Ok(Type::Any)
+    }
+}
+
+function_def!(JsonPointerFunction, "json_pointer", 2, Some(3));
+
+impl Expression for JsonPointerFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let pointer = self.args[1].resolve(state)?;
+        let pointer = pointer.try_as_string("json_pointer", &self.span)?;
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(TransformError::new_invalid_operation(
+                format!("Invalid JSON pointer in json_pointer: {pointer:?} must be empty or start with '/'"),
+                &self.span,
+            ));
+        }
+
+        let found = match source {
+            ResolveResult::Borrowed(v) => v.pointer(&pointer).map(ResolveResult::Borrowed),
+            ResolveResult::Owned(mut v) => v
+                .pointer_mut(&pointer)
+                .map(|v| ResolveResult::Owned(std::mem::take(v))),
+        };
+
+        match (found, self.args.get(2)) {
+            (Some(v), _) => Ok(v),
+            (None, Some(default)) => default.resolve(state),
+            (None, None) => Ok(ResolveResult::Owned(Value::Null)),
+        }
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        self.args[0].resolve_types(state)?;
+        let pointer = self.args[1].resolve_types(state)?;
+        pointer.assert_assignable_to(&Type::stringifyable(), &self.span)?;
+        if let Some(default) = self.args.get(2) {
+            default.resolve_types(state)?;
+        }
+        Ok(Type::Any)
+    }
+}
+
+/// Escape a key for use in a JSON pointer.
+pub(super) fn escape_pointer_token(token: &str) -> String {
+    // `~` must be escaped first, so that the `~` in `~1` is not escaped again.
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+function_def!(ToJsonPointerFunction, "to_json_pointer", 1);
+
+impl Expression for ToJsonPointerFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let segments = self.args[0].resolve(state)?;
+        let Value::Array(segments) = segments.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to to_json_pointer",
+                "array",
+                TransformError::value_desc(&segments),
+                &self.span,
+            ));
+        };
+
+        let mut pointer = String::new();
+        for segment in segments {
+            let segment = ResolveResult::Borrowed(segment);
+            let segment = segment.try_as_string("to_json_pointer", &self.span)?;
+            pointer.push('/');
+            pointer.push_str(&escape_pointer_token(&segment));
+        }
+        Ok(ResolveResult::Owned(Value::String(pointer)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        let segments = self.args[0].resolve_types(state)?;
+        segments.assert_assignable_to(&Type::array_of_type(Type::stringifyable()), &self.span)?;
+        Ok(Type::String)
+    }
+}
+
+/// Match `text` against a glob `pattern`, where `*` matches any sequence of characters
+/// and `?` matches a single character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` in the pattern, and the position in the text it was matched at.
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` consume one more character and try again.
+                Some((bp, bt)) => {
+                    backtrack = Some((bp, bt + 1));
+                    p = bp + 1;
+                    t = bt + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Replace every key matching the glob `key` with `replacement`, at any depth.
+fn mask_key(value: &mut Value, key: &str, replacement: &Value) {
+    match value {
+        Value::Object(o) => {
+            for (k, v) in o.iter_mut() {
+                if glob_match(key, k) {
+                    *v = replacement.clone();
+                } else {
+                    mask_key(v, key, replacement);
+                }
+            }
+        }
+        Value::Array(a) => {
+            for v in a {
+                mask_key(v, key, replacement);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Replace every value matching `path` with `replacement`. Keys in the path may be globs,
+/// and arrays are searched element by element when the next segment is a key.
+fn mask_path(value: &mut Value, path: &[PathSegment], replacement: &Value) {
+    let Some((segment, rest)) = path.split_first() else {
+        *value = replacement.clone();
+        return;
+    };
+    match (segment, value) {
+        (PathSegment::Key(key), Value::Object(o)) => {
+            for (k, v) in o.iter_mut() {
+                if glob_match(key, k) {
+                    mask_path(v, rest, replacement);
+                }
+            }
+        }
+        (PathSegment::Index(idx), Value::Array(a)) => {
+            if let Some(idx) = array_index(a.len(), *idx) {
+                mask_path(&mut a[idx], rest, replacement);
+            }
+        }
+        (PathSegment::Key(_), Value::Array(a)) => {
+            for v in a {
+                mask_path(v, path, replacement);
+            }
+        }
+        _ => (),
+    }
+}
+
+function_def!(MaskFunction, "mask", 2, Some(3));
+
+impl Expression for MaskFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let patterns = self.args[1].resolve(state)?;
+        let patterns = match patterns.as_ref() {
+            Value::Array(a) => a.iter().map(ResolveResult::Borrowed).collect(),
+            _ => vec![patterns],
+        };
+        let patterns = patterns
+            .iter()
+            .map(|p| {
+                let p = p.try_as_string("mask", &self.span)?;
+                parse_path(&p)
+                    .and_then(|segments| {
+                        if segments.is_empty() {
+                            Err("Path cannot be empty".to_owned())
+                        } else {
+                            Ok(segments)
+                        }
+                    })
+                    .map_err(|e| {
+                        TransformError::new_invalid_operation(
+                            format!("Invalid path in mask: {e}"),
+                            &self.span,
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let replacement = match self.args.get(2) {
+            Some(r) => r.resolve(state)?.into_owned(),
+            None => Value::String("***".to_owned()),
+        };
+
+        let mut value = source.into_owned();
+        for pattern in patterns {
+            match pattern.as_slice() {
+                // A single key matches anywhere in the value.
+                [PathSegment::Key(key)] => mask_key(&mut value, key, &replacement),
+                path => mask_path(&mut value, path, &replacement),
+            }
+        }
+        Ok(ResolveResult::Owned(value))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        let patterns = self.args[1].resolve_types(state)?;
+        patterns.assert_assignable_to(
+            &Type::String.union_with(Type::array_of_type(Type::String)),
+            &self.span,
+        )?;
+        if let Some(replacement) = self.args.get(2) {
+            replacement.resolve_types(state)?;
+        }
+        if source.is_assignable_to(&Type::any_object().union_with(Type::any_array())) {
+            Ok(Type::Any)
+        } else {
+            // Only objects and arrays can contain masked fields.
+            Ok(source)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::compile_expression;
@@ -134,4 +534,215 @@ mod tests {
         let t = expr.run_types([crate::types::Type::Integer]).unwrap();
         assert_eq!(t, crate::types::Type::Integer);
     }
+
+    #[test]
+    fn test_parse_path() {
+        use super::{parse_path, PathSegment::*};
+
+        assert_eq!(parse_path("").unwrap(), vec![]);
+        assert_eq!(
+            parse_path("a.b[0].c").unwrap(),
+            vec![
+                Key("a".to_owned()),
+                Key("b".to_owned()),
+                Index(0),
+                Key("c".to_owned())
+            ]
+        );
+        assert_eq!(
+            parse_path(r#"[-1]["a.b"]['c\'d'].e"#).unwrap(),
+            vec![
+                Index(-1),
+                Key("a.b".to_owned()),
+                Key("c'd".to_owned()),
+                Key("e".to_owned())
+            ]
+        );
+        assert!(parse_path("a.").is_err());
+        assert!(parse_path("a..b").is_err());
+        assert!(parse_path(".a").is_err());
+        assert!(parse_path("a[b]").is_err());
+        assert!(parse_path("a[0").is_err());
+        assert!(parse_path("a[0]b").is_err());
+        assert!(parse_path(r#"a["b"#).is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let expr = compile_expression(
+            r#"
+        {
+            "v1": get(input, "a.b[1].c"),
+            "v2": get(input, input.path),
+            "v3": get(input, "a.b[-1]"),
+            "v4": get(input, "a.missing", "default"),
+            "v5": get(input, "a.b[5]", 0),
+            "v6": get(input, "a.b.c"),
+            "v7": get(input, ""),
+            "v8": get({"x": [1, {"y": 2}]}, "x[1].y"),
+            "v9": get(input, "a.n", 1),
+        }
+        "#,
+            &["input"],
+        )
+        .unwrap();
+        let input = serde_json::json!({
+            "a": { "b": [{ "c": 1 }, { "c": 2 }], "n": null },
+            "path": "a.b[0].c"
+        });
+        let res = expr.run([&input]).unwrap();
+
+        let o = res.as_object().unwrap();
+        assert_eq!(o.get("v1").unwrap(), &serde_json::json!(2));
+        assert_eq!(o.get("v2").unwrap(), &serde_json::json!(1));
+        assert_eq!(o.get("v3").unwrap(), &serde_json::json!({ "c": 2 }));
+        assert_eq!(o.get("v4").unwrap(), &serde_json::json!("default"));
+        assert_eq!(o.get("v5").unwrap(), &serde_json::json!(0));
+        assert_eq!(o.get("v6").unwrap(), &serde_json::json!(null));
+        assert_eq!(o.get("v7").unwrap(), &input);
+        assert_eq!(o.get("v8").unwrap(), &serde_json::json!(2));
+        // Only missing values are replaced by the default, not null.
+        assert_eq!(o.get("v9").unwrap(), &serde_json::json!(null));
+    }
+
+    #[test]
+    fn test_get_invalid_path() {
+        let expr = compile_expression(r#"get(input, input.path)"#, &["input"]).unwrap();
+        let input = serde_json::json!({ "path": "a..b" });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid path in get: Empty key at position 2 at 0..22"
+        );
+    }
+
+    #[test]
+    fn test_json_pointer() {
+        let expr = compile_expression(
+            r#"
+        {
+            "v1": json_pointer(input, "/a/b/1/c"),
+            "v2": json_pointer(input, ""),
+            "v3": json_pointer(input, "/a~1b/m~0n"),
+            "v4": json_pointer(input, "/a/b/5", "default"),
+            "v5": json_pointer(input, "/a/b/01"),
+            "v6": json_pointer({"x": [1, {"y": 2}]}, "/x/1/y"),
+            "v7": json_pointer(input, to_json_pointer(["a/b", "m~n"])),
+        }
+        "#,
+            &["input"],
+        )
+        .unwrap();
+        let input = serde_json::json!({
+            "a": { "b": [{ "c": 1 }, { "c": 2 }] },
+            "a/b": { "m~n": 3 }
+        });
+        let res = expr.run([&input]).unwrap();
+
+        let o = res.as_object().unwrap();
+        assert_eq!(o.get("v1").unwrap(), &serde_json::json!(2));
+        assert_eq!(o.get("v2").unwrap(), &input);
+        assert_eq!(o.get("v3").unwrap(), &serde_json::json!(3));
+        assert_eq!(o.get("v4").unwrap(), &serde_json::json!("default"));
+        // Leading zeros are not allowed in array indices.
+        assert_eq!(o.get("v5").unwrap(), &serde_json::json!(null));
+        assert_eq!(o.get("v6").unwrap(), &serde_json::json!(2));
+        assert_eq!(o.get("v7").unwrap(), &serde_json::json!(3));
+    }
+
+    #[test]
+    fn test_json_pointer_invalid() {
+        let expr = compile_expression(r#"json_pointer(input, "a/b")"#, &["input"]).unwrap();
+        let err = expr.run([&serde_json::json!({})]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Invalid JSON pointer in json_pointer: "a/b" must be empty or start with '/' at 0..26"#
+        );
+    }
+
+    #[test]
+    fn test_to_json_pointer() {
+        let expr = compile_expression(r#"to_json_pointer(input)"#, &["input"]).unwrap();
+        let input = serde_json::json!(["a", "b/c", "~d", 0, "~1"]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res.as_ref(), &serde_json::json!("/a/b~1c/~0d/0/~01"));
+
+        let input = serde_json::json!([]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res.as_ref(), &serde_json::json!(""));
+
+        assert!(expr.run([&serde_json::json!([["a"]])]).is_err());
+        assert!(expr.run([&serde_json::json!("a")]).is_err());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        use super::glob_match;
+        assert!(glob_match("password", "password"));
+        assert!(!glob_match("password", "passwords"));
+        assert!(glob_match("*token*", "access_token_v2"));
+        assert!(glob_match("pass?ord", "passWord"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        assert!(!glob_match("?", ""));
+    }
+
+    #[test]
+    fn test_mask_keys() {
+        let expr =
+            compile_expression(r#"mask(input, ["password", "*_token"])"#, &["input"]).unwrap();
+        let input = serde_json::json!({
+            "user": { "name": "bob", "password": "hunter2" },
+            "sessions": [{ "access_token": "abc", "id": 1 }, { "refresh_token": "def" }],
+            "password": { "nested": "also hidden" },
+        });
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!({
+                "user": { "name": "bob", "password": "***" },
+                "sessions": [{ "access_token": "***", "id": 1 }, { "refresh_token": "***" }],
+                "password": "***",
+            })
+        );
+    }
+
+    #[test]
+    fn test_mask_paths() {
+        let expr = compile_expression(
+            r#"mask(input, ["users.email", "meta.*.secret", "users[0].name", 'meta["a.b"]'], null)"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = serde_json::json!({
+            "users": [{ "name": "a", "email": "a@x" }, { "name": "b", "email": "b@x" }],
+            "email": "top level is not matched",
+            "meta": { "x": { "secret": 1 }, "y": { "secret": 2, "public": 3 }, "a.b": 4 },
+        });
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!({
+                "users": [{ "name": null, "email": null }, { "name": "b", "email": null }],
+                "email": "top level is not matched",
+                "meta": { "x": { "secret": null }, "y": { "secret": null, "public": 3 }, "a.b": null },
+            })
+        );
+    }
+
+    #[test]
+    fn test_mask_errors() {
+        let expr = compile_expression("mask(input, 'a..b')", &["input"]).unwrap();
+        let input = serde_json::json!({});
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Invalid path in mask: Empty key at position 2"
+        );
+
+        let expr = compile_expression("mask(input, '')", &["input"]).unwrap();
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(err.message(), "Invalid path in mask: Path cannot be empty");
+    }
 }
@@ -0,0 +1,405 @@
+use serde_json::{json, Value};
+
+use crate::{
+    expressions::{Expression, ResolveResult},
+    types::Type,
+    TransformError,
+};
+
+use super::json::escape_pointer_token;
+
+function_def!(JsonDiffFunction, "json_diff", 2);
+
+/// Append operations transforming `a` into `b` to `ops`, where `path` is the JSON pointer to `a`.
+fn diff(a: &Value, b: &Value, path: &mut String, ops: &mut Vec<Value>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (k, v) in a {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&escape_pointer_token(k));
+                match b.get(k) {
+                    Some(other) => diff(v, other, path, ops),
+                    None => ops.push(json!({ "op": "remove", "path": path })),
+                }
+                path.truncate(len);
+            }
+            for (k, v) in b {
+                if !a.contains_key(k) {
+                    ops.push(json!({
+                        "op": "add",
+                        "path": format!("{path}/{}", escape_pointer_token(k)),
+                        "value": v
+                    }));
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for (idx, (v, other)) in a.iter().zip(b.iter()).enumerate() {
+                let len = path.len();
+                path.push_str(&format!("/{idx}"));
+                diff(v, other, path, ops);
+                path.truncate(len);
+            }
+            for (idx, v) in b.iter().enumerate().skip(a.len()) {
+                ops.push(json!({ "op": "add", "path": format!("{path}/{idx}"), "value": v }));
+            }
+            // Remove from the end, so that earlier indices stay valid.
+            for idx in (b.len()..a.len()).rev() {
+                ops.push(json!({ "op": "remove", "path": format!("{path}/{idx}") }));
+            }
+        }
+        (a, b) => {
+            if a != b {
+                ops.push(json!({ "op": "replace", "path": path, "value": b }));
+            }
+        }
+    }
+}
+
+impl Expression for JsonDiffFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let a = self.args[0].resolve(state)?;
+        let b = self.args[1].resolve(state)?;
+        let mut ops = Vec::new();
+        diff(&a, &b, &mut String::new(), &mut ops);
+        Ok(ResolveResult::Owned(Value::Array(ops)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        self.args[0].resolve_types(state)?;
+        self.args[1].resolve_types(state)?;
+        Ok(Type::array_of_type(Type::any_object()))
+    }
+}
+
+function_def!(JsonPatchFunction, "json_patch", 2);
+
+/// Split a JSON pointer into the pointer to its parent and the final, unescaped, key.
+/// Returns `None` for the empty pointer, which references the whole document.
+fn split_pointer(pointer: &str) -> Result<Option<(&str, String)>, String> {
+    if pointer.is_empty() {
+        return Ok(None);
+    }
+    let Some((parent, last)) = pointer.rsplit_once('/') else {
+        return Err(format!("Invalid JSON pointer {pointer:?}"));
+    };
+    if !parent.is_empty() && !parent.starts_with('/') {
+        return Err(format!("Invalid JSON pointer {pointer:?}"));
+    }
+    Ok(Some((parent, last.replace("~1", "/").replace("~0", "~"))))
+}
+
+/// Parse an array index as defined in RFC 6901, which does not allow leading zeros.
+fn parse_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() > 1) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> Result<(), String> {
+    let Some((parent, key)) = split_pointer(path)? else {
+        *doc = value;
+        return Ok(());
+    };
+    match doc.pointer_mut(parent) {
+        Some(Value::Object(o)) => {
+            o.insert(key, value);
+        }
+        Some(Value::Array(a)) => {
+            let idx = if key == "-" {
+                a.len()
+            } else {
+                parse_index(&key)
+                    .filter(|i| *i <= a.len())
+                    .ok_or_else(|| format!("Invalid array index {key:?} in {path:?}"))?
+            };
+            a.insert(idx, value);
+        }
+        _ => return Err(format!("Cannot add a value at {path:?}")),
+    }
+    Ok(())
+}
+
+fn remove(doc: &mut Value, path: &str) -> Result<Value, String> {
+    let Some((parent, key)) = split_pointer(path)? else {
+        return Err("Cannot remove the whole document".to_owned());
+    };
+    let removed = match doc.pointer_mut(parent) {
+        Some(Value::Object(o)) => o.remove(&key),
+        Some(Value::Array(a)) => parse_index(&key)
+            .filter(|i| *i < a.len())
+            .map(|i| a.remove(i)),
+        _ => None,
+    };
+    removed.ok_or_else(|| format!("No value to remove at {path:?}"))
+}
+
+/// Number of values in `value`, including nested values.
+fn value_size(value: &Value) -> usize {
+    match value {
+        Value::Array(a) => 1 + a.iter().map(value_size).sum::<usize>(),
+        Value::Object(o) => 1 + o.values().map(value_size).sum::<usize>(),
+        _ => 1,
+    }
+}
+
+/// Apply a single RFC 6902 patch operation to `doc`, returning the number of values copied
+/// from the document, which counts towards the operation limit.
+fn apply_operation(doc: &mut Value, op: &Value) -> Result<usize, String> {
+    let Some(op) = op.as_object() else {
+        return Err("Patch operations must be objects".to_owned());
+    };
+    let get_str = |field: &str| {
+        op.get(field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| format!("Missing string field {field:?}"))
+    };
+    let get_value = |field: &str| {
+        op.get(field)
+            .cloned()
+            .ok_or_else(|| format!("Missing field {field:?}"))
+    };
+    let path = get_str("path")?;
+
+    let mut copied = 0;
+    match get_str("op")? {
+        "add" => add(doc, path, get_value("value")?),
+        "remove" => remove(doc, path).map(|_| ()),
+        "replace" => match doc.pointer_mut(path) {
+            Some(v) => {
+                *v = get_value("value")?;
+                Ok(())
+            }
+            None => Err(format!("No value to replace at {path:?}")),
+        },
+        "move" => {
+            let from = get_str("from")?;
+            if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                return Err(format!("Cannot move {from:?} into itself"));
+            }
+            let value = remove(doc, from)?;
+            add(doc, path, value)
+        }
+        "copy" => {
+            let from = get_str("from")?;
+            let value = doc
+                .pointer(from)
+                .cloned()
+                .ok_or_else(|| format!("No value to copy at {from:?}"))?;
+            copied = value_size(&value);
+            add(doc, path, value)
+        }
+        "test" => {
+            if doc.pointer(path) == Some(&get_value("value")?) {
+                Ok(())
+            } else {
+                Err(format!("Test failed at {path:?}"))
+            }
+        }
+        other => Err(format!("Unknown operation {other:?}")),
+    }?;
+    Ok(copied)
+}
+
+impl Expression for JsonPatchFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let mut doc = self.args[0].resolve(state)?.into_owned();
+        let patch = self.args[1].resolve(state)?;
+        let Value::Array(ops) = patch.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to json_patch",
+                "array",
+                TransformError::value_desc(&patch),
+                &self.span,
+            ));
+        };
+
+        for (idx, op) in ops.iter().enumerate() {
+            state.inc_op()?;
+            let copied = apply_operation(&mut doc, op).map_err(|e| {
+                TransformError::new_invalid_operation(
+                    format!("Failed to apply operation {idx} in json_patch: {e}"),
+                    &self.span,
+                )
+            })?;
+            // Copying a value into itself doubles the document, so copies count by size.
+            state.inc_ops(copied)?;
+        }
+        Ok(ResolveResult::Owned(doc))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        self.args[0].resolve_types(state)?;
+        let patch = self.args[1].resolve_types(state)?;
+        patch.assert_assignable_to(&Type::any_array(), &self.span)?;
+        Ok(Type::Any)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::compile_expression;
+
+    #[test]
+    fn test_json_diff() {
+        let expr = compile_expression("json_diff(a, b)", &["a", "b"]).unwrap();
+        let a = json!({
+            "same": 1,
+            "changed": "x",
+            "removed": true,
+            "nested": { "a/b": 1, "c": [1, 2, 3] },
+            "list": [1, 2]
+        });
+        let b = json!({
+            "same": 1,
+            "changed": "y",
+            "nested": { "a/b": 2, "c": [1] },
+            "list": [1, 2, { "x": 1 }],
+            "added": null
+        });
+        let res = expr.run([&a, &b]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([
+                { "op": "replace", "path": "/changed", "value": "y" },
+                { "op": "add", "path": "/list/2", "value": { "x": 1 } },
+                { "op": "replace", "path": "/nested/a~1b", "value": 2 },
+                { "op": "remove", "path": "/nested/c/2" },
+                { "op": "remove", "path": "/nested/c/1" },
+                { "op": "remove", "path": "/removed" },
+                { "op": "add", "path": "/added", "value": null },
+            ])
+        );
+
+        let res = expr.run([&a, &a]).unwrap();
+        assert_eq!(res.as_ref(), &json!([]));
+
+        let (a, b) = (json!(1), json!([1]));
+        let res = expr.run([&a, &b]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([{ "op": "replace", "path": "", "value": [1] }])
+        );
+    }
+
+    #[test]
+    fn test_json_diff_roundtrip() {
+        let expr = compile_expression("json_patch(a, json_diff(a, b))", &["a", "b"]).unwrap();
+        let a = json!({
+            "a": [1, { "b": 2 }, 3, 4],
+            "c": { "d~e": "f", "g": null },
+            "h": "i"
+        });
+        let b = json!({
+            "a": [1, { "b": 3, "x": [] }],
+            "c": { "g": { "k": 1 } },
+            "j": [1, 2]
+        });
+        let res = expr.run([&a, &b]).unwrap();
+        assert_eq!(res.as_ref(), &b);
+        let res = expr.run([&b, &a]).unwrap();
+        assert_eq!(res.as_ref(), &a);
+    }
+
+    #[test]
+    fn test_json_patch() {
+        let expr = compile_expression("json_patch(input, patch)", &["input", "patch"]).unwrap();
+        let input = json!({ "a": { "b": [1, 2] }, "c": "d" });
+        let patch = json!([
+            { "op": "test", "path": "/c", "value": "d" },
+            { "op": "add", "path": "/a/b/-", "value": 3 },
+            { "op": "add", "path": "/a/b/0", "value": 0 },
+            { "op": "remove", "path": "/a/b/1" },
+            { "op": "replace", "path": "/c", "value": "e" },
+            { "op": "copy", "from": "/a/b", "path": "/f" },
+            { "op": "move", "from": "/c", "path": "/a/c" },
+        ]);
+        let res = expr.run([&input, &patch]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!({ "a": { "b": [0, 2, 3], "c": "e" }, "f": [0, 2, 3] })
+        );
+
+        let patch = json!([{ "op": "replace", "path": "", "value": 5 }]);
+        let res = expr.run([&input, &patch]).unwrap();
+        assert_eq!(res.as_ref(), &json!(5));
+    }
+
+    #[test]
+    fn test_json_patch_errors() {
+        let expr = compile_expression("json_patch(input, patch)", &["input", "patch"]).unwrap();
+        let input = json!({ "a": { "b": [1, 2] }, "c": "d" });
+        for (patch, message) in [
+            (
+                json!([{ "op": "test", "path": "/c", "value": "e" }]),
+                r#"Failed to apply operation 0 in json_patch: Test failed at "/c""#,
+            ),
+            (
+                json!([{ "op": "add", "path": "/x/y", "value": 1 }]),
+                r#"Failed to apply operation 0 in json_patch: Cannot add a value at "/x/y""#,
+            ),
+            (
+                json!([{ "op": "add", "path": "/a/b/3", "value": 1 }]),
+                r#"Failed to apply operation 0 in json_patch: Invalid array index "3" in "/a/b/3""#,
+            ),
+            (
+                json!([{ "op": "remove", "path": "/a/b/01" }]),
+                r#"Failed to apply operation 0 in json_patch: No value to remove at "/a/b/01""#,
+            ),
+            (
+                json!([{ "op": "add", "path": "/c", "value": 1 }, { "op": "move", "from": "/a", "path": "/a/b" }]),
+                r#"Failed to apply operation 1 in json_patch: Cannot move "/a" into itself"#,
+            ),
+            (
+                json!([{ "op": "foo", "path": "/c" }]),
+                r#"Failed to apply operation 0 in json_patch: Unknown operation "foo""#,
+            ),
+            (
+                json!([{ "op": "add", "path": "/c" }]),
+                r#"Failed to apply operation 0 in json_patch: Missing field "value""#,
+            ),
+        ] {
+            let err = expr.run([&input, &patch]).unwrap_err();
+            assert_eq!(err.message(), message);
+        }
+    }
+
+    #[test]
+    fn test_json_patch_operation_limit() {
+        let expr = compile_expression("json_patch(input, patch)", &["input", "patch"]).unwrap();
+        let input = json!({ "a": [1] });
+        // Each operation copies "/a" into itself, doubling its size.
+        let patch: Vec<_> = (0..40)
+            .map(|i| json!({ "op": "copy", "from": "/a", "path": format!("/a/{}", i + 1) }))
+            .collect();
+        let patch = serde_json::Value::Array(patch);
+        let err = expr.run_limited([&input, &patch], 100_000).unwrap_err();
+        assert!(matches!(err, crate::TransformError::OperationLimitExceeded));
+
+        let patch = json!(patch.as_array().unwrap()[..5]);
+        let (res, opcount) = expr
+            .builder()
+            .with_values([&input, &patch])
+            .run_get_opcount()
+            .unwrap();
+        assert_eq!(res["a"].as_array().unwrap().len(), 6);
+        // 5 operations, and 2 + 4 + 8 + 16 + 32 copied values.
+        assert!(opcount >= 67, "{opcount}");
+    }
+}
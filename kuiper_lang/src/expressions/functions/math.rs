@@ -3,7 +3,7 @@ use serde_json::{Number, Value};
 
 use crate::{
     expressions::{numbers::JsonNumber, Expression, ResolveResult},
-    types::{Type, TypeError},
+    types::{Array, Type, TypeError},
     ExpressionType, TransformError,
 };
 
@@ -442,6 +442,278 @@ impl Expression for RandomFunction {
     }
 }
 
+/// Mean radius of the earth in meters, used by `geo_distance`.
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+function_def!(GeoDistanceFunction, "geo_distance", 4);
+
+impl Expression for GeoDistanceFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let mut coords = [0.0; 4];
+        for (arg, coord) in self.args.iter().zip(coords.iter_mut()) {
+            *coord = arg
+                .resolve(state)?
+                .try_as_number("geo_distance", &self.span)?
+                .as_f64()
+                .to_radians();
+        }
+        let [lat1, lon1, lat2, lon2] = coords;
+
+        // Haversine formula, assuming a spherical earth.
+        let a = ((lat2 - lat1) / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+        let res = 2.0 * EARTH_RADIUS_METERS * a.sqrt().min(1.0).asin();
+
+        Ok(ResolveResult::Owned(Value::Number(
+            Number::from_f64(res).ok_or_else(|| {
+                TransformError::new_conversion_failed(
+                    "Failed to convert result of geo_distance to number",
+                    &self.span,
+                )
+            })?,
+        )))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        for arg in &self.args {
+            arg.resolve_types(state)?
+                .assert_assignable_to(&Type::number(), &self.span)?;
+        }
+        Ok(Type::Float)
+    }
+}
+
+/// Collect the elements of the array `value` as numbers, for the statistics functions.
+fn json_number_array(
+    value: &Value,
+    desc: &str,
+    span: &Span,
+) -> Result<Vec<JsonNumber>, TransformError> {
+    let Value::Array(array) = value else {
+        return Err(TransformError::new_incorrect_type(
+            &format!("Incorrect input to {desc}"),
+            "array",
+            TransformError::value_desc(value),
+            span,
+        ));
+    };
+    array
+        .iter()
+        .map(|x| JsonNumber::try_from(x, desc, span))
+        .collect()
+}
+
+/// Collect the elements of the array `value` as floats, for the statistics functions.
+fn number_array(value: &Value, desc: &str, span: &Span) -> Result<Vec<f64>, TransformError> {
+    let values = json_number_array(value, desc, span)?;
+    Ok(values.into_iter().map(|n| n.as_f64()).collect())
+}
+
+/// Population variance of a non-empty list of numbers.
+fn variance(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+}
+
+fn float_result(
+    res: f64,
+    desc: &str,
+    span: &Span,
+) -> Result<ResolveResult<'static>, TransformError> {
+    Number::from_f64(res)
+        .map(|n| ResolveResult::Owned(Value::Number(n)))
+        .ok_or_else(|| {
+            TransformError::new_conversion_failed(
+                format!("Failed to convert result of {desc} to number"),
+                span,
+            )
+        })
+}
+
+fn number_array_types(
+    arg: &ExpressionType,
+    state: &mut crate::types::TypeExecutionState<'_, '_>,
+    span: &Span,
+) -> Result<Array, TypeError> {
+    let arr = arg.resolve_types(state)?;
+    let arr = arr.try_as_array(span)?;
+    for elem in arr.all_elements() {
+        elem.assert_assignable_to(&Type::number(), span)?;
+    }
+    Ok(arr)
+}
+
+/// The result type of an aggregate that is null for empty arrays.
+fn nullable_if_empty(arr: &Array, res: Type) -> Type {
+    if arr.elements.is_empty() {
+        res.nullable()
+    } else {
+        res
+    }
+}
+
+function_def!(VarianceFunction, "variance", 1);
+
+impl Expression for VarianceFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let values = number_array(
+            self.args[0].resolve(state)?.as_ref(),
+            "variance",
+            &self.span,
+        )?;
+        if values.is_empty() {
+            return Ok(ResolveResult::Owned(Value::Null));
+        }
+        float_result(variance(&values), "variance", &self.span)
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let arr = number_array_types(&self.args[0], state, &self.span)?;
+        Ok(nullable_if_empty(&arr, Type::Float))
+    }
+}
+
+function_def!(StddevFunction, "stddev", 1);
+
+impl Expression for StddevFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let values = number_array(self.args[0].resolve(state)?.as_ref(), "stddev", &self.span)?;
+        if values.is_empty() {
+            return Ok(ResolveResult::Owned(Value::Null));
+        }
+        float_result(variance(&values).sqrt(), "stddev", &self.span)
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let arr = number_array_types(&self.args[0], state, &self.span)?;
+        Ok(nullable_if_empty(&arr, Type::Float))
+    }
+}
+
+function_def!(PercentileFunction, "percentile", 2);
+
+impl Expression for PercentileFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let mut values = number_array(
+            self.args[0].resolve(state)?.as_ref(),
+            "percentile",
+            &self.span,
+        )?;
+        let p = self.args[1]
+            .resolve(state)?
+            .try_as_number("percentile", &self.span)?
+            .as_f64();
+        if !(0.0..=100.0).contains(&p) {
+            return Err(TransformError::new_invalid_operation(
+                format!("Percentile must be between 0 and 100, got {p}"),
+                &self.span,
+            ));
+        }
+        if values.is_empty() {
+            return Ok(ResolveResult::Owned(Value::Null));
+        }
+
+        // Linear interpolation between the two closest ranks.
+        values.sort_by(f64::total_cmp);
+        let rank = p / 100.0 * (values.len() - 1) as f64;
+        let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+        let res = values[lower] + (values[upper] - values[lower]) * (rank - lower as f64);
+        float_result(res, "percentile", &self.span)
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let arr = number_array_types(&self.args[0], state, &self.span)?;
+        self.args[1]
+            .resolve_types(state)?
+            .assert_assignable_to(&Type::number(), &self.span)?;
+        Ok(nullable_if_empty(&arr, Type::Float))
+    }
+}
+
+function_def!(AvgFunction, "avg", 1);
+
+impl Expression for AvgFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let values = json_number_array(self.args[0].resolve(state)?.as_ref(), "avg", &self.span)?;
+        if values.is_empty() {
+            return Ok(ResolveResult::Owned(Value::Null));
+        }
+        let sum: f64 = values.iter().map(|n| n.as_f64()).sum();
+        float_result(sum / values.len() as f64, "avg", &self.span)
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let arr = number_array_types(&self.args[0], state, &self.span)?;
+        Ok(nullable_if_empty(&arr, Type::Float))
+    }
+}
+
+function_def!(MedianFunction, "median", 1);
+
+impl Expression for MedianFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let mut values =
+            json_number_array(self.args[0].resolve(state)?.as_ref(), "median", &self.span)?;
+        if values.is_empty() {
+            return Ok(ResolveResult::Owned(Value::Null));
+        }
+        values.sort_by(|a, b| a.as_f64().total_cmp(&b.as_f64()));
+
+        let mid = values.len() / 2;
+        if values.len() % 2 == 1 {
+            // The middle element is returned as is, so integers stay integers.
+            return match values[mid].try_into_json() {
+                Some(v) => Ok(ResolveResult::Owned(v)),
+                None => float_result(values[mid].as_f64(), "median", &self.span),
+            };
+        }
+        let res = (values[mid - 1].as_f64() + values[mid].as_f64()) / 2.0;
+        float_result(res, "median", &self.span)
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, TypeError> {
+        let arr = number_array_types(&self.args[0], state, &self.span)?;
+        let res = arr.element_union().union_with(Type::Float);
+        Ok(nullable_if_empty(&arr, res))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -908,4 +1180,177 @@ mod tests {
         let ty = expr.run_types(std::iter::empty::<Type>()).unwrap();
         assert_eq!(Type::Float, ty);
     }
+
+    #[test]
+    pub fn test_geo_distance() {
+        let expr = compile_expression(
+            r#"{
+            "oslo_bergen": geo_distance(59.9139, 10.7522, 60.3913, 5.3221),
+            "same": geo_distance(input.lat, input.lon, input.lat, input.lon),
+            "antipodal": geo_distance(0, 0, 0, 180),
+        }"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = json!({ "lat": 63.43, "lon": 10.39 });
+        let res = expr.run([&input]).unwrap();
+        let oslo_bergen = res["oslo_bergen"].as_f64().unwrap();
+        assert!((oslo_bergen - 305_000.0).abs() < 1_000.0, "{oslo_bergen}");
+        assert_eq!(res["same"].as_f64().unwrap(), 0.0);
+        let antipodal = res["antipodal"].as_f64().unwrap();
+        assert!((antipodal - std::f64::consts::PI * 6_371_000.0).abs() < TINY);
+    }
+
+    #[test]
+    pub fn test_geo_distance_types() {
+        let expr = compile_expression("geo_distance(input, 0, 0, 0)", &["input"]).unwrap();
+        assert_eq!(expr.run_types([Type::Integer]).unwrap(), Type::Float);
+        expr.run_types([Type::String]).unwrap_err();
+    }
+
+    #[test]
+    pub fn test_variance_stddev() {
+        let expr = compile_expression(
+            r#"{
+            "var": variance([2, 4, 4, 4, 5, 5, 7, 9]),
+            "std": stddev([2, 4, 4, 4, 5, 5, 7, 9]),
+            "single": stddev([3.5]),
+            "input": variance(input),
+        }"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = json!([1.5, 2.5]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res["var"].as_f64().unwrap(), 4.0);
+        assert_eq!(res["std"].as_f64().unwrap(), 2.0);
+        assert_eq!(res["single"].as_f64().unwrap(), 0.0);
+        assert_eq!(res["input"].as_f64().unwrap(), 0.25);
+    }
+
+    #[test]
+    pub fn test_percentile() {
+        let expr = compile_expression(
+            r#"[
+            percentile([15, 20, 35, 40, 50], 0),
+            percentile([15, 20, 35, 40, 50], 50),
+            percentile([50, 40, 35, 20, 15], 100),
+            percentile([1, 2, 3, 4], 25),
+            percentile([1, 2, 3, 4], 90),
+            percentile([7], 33),
+        ]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        let res: Vec<f64> = res
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_f64().unwrap())
+            .collect();
+        let expected = [15.0, 35.0, 50.0, 1.75, 3.7, 7.0];
+        for (r, e) in res.iter().zip(expected) {
+            assert!((r - e).abs() < TINY, "{r} != {e}");
+        }
+    }
+
+    #[test]
+    pub fn test_avg_median() {
+        let expr = compile_expression(
+            r#"{
+            "avg": avg([1, 2, 3, 4]),
+            "avg_empty": avg([]),
+            "median_odd": median([3, 1, 2]),
+            "median_even": median([4, 1, 3, 2]),
+            "median_float": median([1.5, 0.5, 2.5]),
+            "median_empty": median(input),
+        }"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = json!([]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!({
+                "avg": 2.5,
+                "avg_empty": null,
+                "median_odd": 2,
+                "median_even": 2.5,
+                "median_float": 1.5,
+                "median_empty": null,
+            })
+        );
+        assert!(res["median_odd"].is_u64());
+    }
+
+    #[test]
+    pub fn test_avg_median_types() {
+        let expr = compile_expression("[avg(input), median(input)]", &["input"]).unwrap();
+        let res = expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .unwrap();
+        assert_eq!(
+            res,
+            Type::Array(Array {
+                elements: vec![
+                    Type::Float.nullable(),
+                    Type::Integer.union_with(Type::Float).nullable()
+                ],
+                end_dynamic: None,
+            })
+        );
+        assert!(expr.run_types([Type::array_of_type(Type::String)]).is_err());
+
+        let expr = compile_expression("avg(input)", &["input"]).unwrap();
+        let err = expr.run([&json!(["a"])]).unwrap_err();
+        assert!(err.message().contains("avg"), "{}", err.message());
+    }
+
+    #[test]
+    pub fn test_statistics_errors() {
+        let expr = compile_expression("percentile(input.arr, input.p)", &["input"]).unwrap();
+        let input = json!({ "arr": [1, 2], "p": 101 });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Percentile must be between 0 and 100, got 101"
+        );
+        let input = json!({ "arr": [], "p": 50 });
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!(null));
+        let input = json!({ "arr": [], "p": 101 });
+        expr.run([&input]).unwrap_err();
+
+        let expr = compile_expression("[variance(input), stddev(input)]", &["input"]).unwrap();
+        let input = json!([]);
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!([null, null]));
+
+        let expr = compile_expression("stddev(input)", &["input"]).unwrap();
+        let input = json!([1, "2"]);
+        expr.run([&input]).unwrap_err();
+        let input = json!(5);
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect input to stddev. Got number, expected array"
+        );
+    }
+
+    #[test]
+    pub fn test_statistics_types() {
+        let expr = compile_expression("variance(input)", &["input"]).unwrap();
+        let ty = expr
+            .run_types([Type::array_of_type(Type::Integer)])
+            .unwrap();
+        assert_eq!(ty, Type::Float.nullable());
+        let ty = expr
+            .run_types([Type::Array(Array {
+                elements: vec![Type::Integer],
+                end_dynamic: None,
+            })])
+            .unwrap();
+        assert_eq!(ty, Type::Float);
+        assert!(expr.run_types([Type::array_of_type(Type::String)]).is_err());
+    }
 }
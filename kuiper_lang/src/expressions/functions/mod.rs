@@ -3,16 +3,24 @@ mod macros;
 mod arrays;
 mod coalesce;
 mod conversions;
+#[cfg(feature = "digest")]
 mod digest;
 pub(super) mod dynamic;
 mod functors;
+mod ip;
 mod join;
 mod json;
+mod json_patch;
 mod logic;
 mod math;
+#[cfg(feature = "regex")]
 mod regex;
+#[cfg(feature = "semver")]
+mod semver;
 mod string;
+#[cfg(feature = "time")]
 mod time;
+mod timeseries;
 mod transforms;
 mod uuid;
 
@@ -20,16 +28,24 @@ use crate::compiler::BuildError;
 pub use arrays::*;
 pub use coalesce::*;
 pub use conversions::*;
+#[cfg(feature = "digest")]
 pub use digest::*;
 pub use functors::*;
+pub use ip::*;
 pub use join::*;
 pub use json::*;
+pub use json_patch::*;
 pub use logic::*;
 pub use macros::function_def;
 pub use math::*;
+#[cfg(feature = "regex")]
 pub use regex::*;
+#[cfg(feature = "semver")]
+pub use semver::*;
 pub use string::*;
+#[cfg(feature = "time")]
 pub use time::*;
+pub use timeseries::*;
 pub use transforms::*;
 pub use uuid::*;
 
@@ -318,6 +318,33 @@ impl Expression for RegexReplaceAllFunction {
     }
 }
 
+regex_function!(RegexSplitFunction, "regex_split", 1);
+
+impl Expression for RegexSplitFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, crate::TransformError> {
+        let arg = self.args[0].resolve(state)?;
+        let arg = arg.try_as_string(Self::INFO.name, &self.span)?;
+        let res = self
+            .re
+            .split(arg.as_ref())
+            .map(|s| Value::String(s.to_owned()))
+            .collect();
+        Ok(ResolveResult::Owned(Value::Array(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        let item = self.args[0].resolve_types(state)?;
+        item.assert_assignable_to(&Type::stringifyable(), &self.span)?;
+        Ok(Type::array_of_type(Type::String))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::{json, Value};
@@ -522,6 +549,7 @@ mod tests {
                 "v2": regex_replace("æøå", "[æø]{2}", "nope"),
                 "v3": regex_replace("test string", "test (?<v>[a-z]*)", "also $v"),
                 "v4": regex_replace("test", "^123$", "nope"),
+                "v5": regex_replace("2024-05-01", "([0-9]+)-([0-9]+)-([0-9]+)", "$3/$2/$1"),
             }
         "#,
             &[],
@@ -533,6 +561,7 @@ mod tests {
         assert_eq!(v["v2"], "nopeå");
         assert_eq!(v["v3"], "also string");
         assert_eq!(v["v4"], "test");
+        assert_eq!(v["v5"], "01/05/2024");
     }
 
     #[test]
@@ -557,6 +586,28 @@ mod tests {
         assert_eq!(v["v4"], "test");
     }
 
+    #[test]
+    pub fn test_regex_split() {
+        let expr = compile_expression(
+            r#"
+            {
+                "v1": "a,b;c".regex_split("[,;]"),
+                "v2": regex_split("foo  bar baz", "\\s+"),
+                "v3": regex_split("test", "nope"),
+                "v4": regex_split(",a,", ","),
+            }
+            "#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        let v = res.as_object().unwrap();
+        assert_eq!(v["v1"], json!(["a", "b", "c"]));
+        assert_eq!(v["v2"], json!(["foo", "bar", "baz"]));
+        assert_eq!(v["v3"], json!(["test"]));
+        assert_eq!(v["v4"], json!(["", "a", ""]));
+    }
+
     #[test]
     pub fn test_regex_non_constant() {
         let e =
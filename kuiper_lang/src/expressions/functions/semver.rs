@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+
+use logos::Span;
+use semver::{Version, VersionReq};
+use serde_json::Value;
+
+use crate::{
+    expressions::{Expression, ExpressionExecutionState, ExpressionType, ResolveResult},
+    types::{Type, TypeError, TypeExecutionState},
+    TransformError,
+};
+
+/// Parse a semantic version. A leading `v` is ignored, and missing minor and patch
+/// versions are treated as 0, so `v1.2` is parsed as `1.2.0`.
+fn parse_version(version: &str) -> Option<Version> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    if let Ok(v) = Version::parse(version) {
+        return Some(v);
+    }
+    let (core, rest) = version.split_at(version.find(['-', '+']).unwrap_or(version.len()));
+    let parts = core.split('.').count();
+    if parts >= 3 {
+        return None;
+    }
+    Version::parse(&format!("{core}{}{rest}", ".0".repeat(3 - parts))).ok()
+}
+
+fn version_arg(value: &Value, func: &str, span: &Span) -> Result<Version, TransformError> {
+    let Value::String(s) = value else {
+        return Err(TransformError::new_incorrect_type(
+            &format!("Incorrect input to {func}"),
+            "string",
+            TransformError::value_desc(value),
+            span,
+        ));
+    };
+    parse_version(s).ok_or_else(|| {
+        TransformError::new_conversion_failed(
+            format!("Failed to parse {s:?} as a semantic version in {func}"),
+            span,
+        )
+    })
+}
+
+fn string_arg_types(
+    args: &[Box<ExpressionType>],
+    state: &mut TypeExecutionState<'_, '_>,
+    span: &Span,
+) -> Result<(), TypeError> {
+    for arg in args {
+        arg.resolve_types(state)?
+            .assert_assignable_to(&Type::String, span)?;
+    }
+    Ok(())
+}
+
+function_def!(SemverCompareFunction, "semver_compare", 2);
+
+impl Expression for SemverCompareFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let lhs = self.args[0].resolve(state)?;
+        let lhs = version_arg(&lhs, "semver_compare", &self.span)?;
+        let rhs = self.args[1].resolve(state)?;
+        let rhs = version_arg(&rhs, "semver_compare", &self.span)?;
+        let res = match lhs.cmp_precedence(&rhs) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        };
+        Ok(ResolveResult::Owned(Value::from(res)))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        string_arg_types(&self.args, state, &self.span)?;
+        Ok(Type::Integer)
+    }
+}
+
+function_def!(SemverSatisfiesFunction, "semver_satisfies", 2);
+
+impl Expression for SemverSatisfiesFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let version = self.args[0].resolve(state)?;
+        let version = version_arg(&version, "semver_satisfies", &self.span)?;
+        let req = self.args[1].resolve(state)?;
+        let req = req.try_as_string("semver_satisfies", &self.span)?;
+        let req = VersionReq::parse(&req).map_err(|e| {
+            TransformError::new_conversion_failed(
+                format!(
+                    "Failed to parse {req:?} as a version requirement in semver_satisfies: {e}"
+                ),
+                &self.span,
+            )
+        })?;
+        Ok(ResolveResult::Owned(Value::Bool(req.matches(&version))))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        string_arg_types(&self.args, state, &self.span)?;
+        Ok(Type::Boolean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{compile_expression, types::Type};
+
+    #[test]
+    fn test_semver_compare() {
+        let expr = compile_expression(
+            r#"[
+                semver_compare("1.2.3", "1.2.3"),
+                semver_compare("1.2.3", "1.10.0"),
+                semver_compare("2.0.0", "1.99.99"),
+                semver_compare("1.0.0-alpha", "1.0.0"),
+                semver_compare("1.0.0+build1", "1.0.0+build2"),
+                semver_compare("v1.2", "1.2.0"),
+                semver_compare("2", "1.9.9"),
+            ]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &json!([0, -1, 1, -1, 0, 0, 1]));
+    }
+
+    #[test]
+    fn test_semver_satisfies() {
+        let expr = compile_expression(
+            r#"[
+                semver_satisfies("1.4.0", ">=1.2.0, <2"),
+                semver_satisfies("2.0.0", ">=1.2.0, <2"),
+                semver_satisfies("1.1.9", ">=1.2.0, <2"),
+                semver_satisfies("1.3.0", "^1.2"),
+                semver_satisfies("v3.1", "~3.1.0"),
+                semver_satisfies("1.5.0", "*"),
+            ]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &json!([true, false, false, true, true, true]));
+    }
+
+    #[test]
+    fn test_semver_errors() {
+        let expr = compile_expression("semver_satisfies(input.v, input.req)", &["input"]).unwrap();
+        let input = json!({ "v": "1.2.3.4", "req": ">1" });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            r#"Failed to parse "1.2.3.4" as a semantic version in semver_satisfies"#
+        );
+        let input = json!({ "v": "1.2.3", "req": "=>1" });
+        let err = expr.run([&input]).unwrap_err();
+        assert!(err
+            .message()
+            .starts_with(r#"Failed to parse "=>1" as a version requirement in semver_satisfies"#));
+        let input = json!({ "v": 1, "req": ">1" });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Incorrect input to semver_satisfies. Got number, expected string"
+        );
+    }
+
+    #[test]
+    fn test_semver_types() {
+        let expr = compile_expression("semver_compare(input, \"1.0.0\")", &["input"]).unwrap();
+        assert_eq!(expr.run_types([Type::String]).unwrap(), Type::Integer);
+        expr.run_types([Type::Integer]).unwrap_err();
+    }
+}
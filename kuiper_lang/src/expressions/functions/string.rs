@@ -4,7 +4,7 @@ use itertools::Itertools;
 use serde_json::Value;
 
 use crate::{
-    expressions::{Expression, ResolveResult},
+    expressions::{Expression, ExpressionType, FunctionType, ResolveResult},
     types::Type,
 };
 
@@ -30,15 +30,8 @@ impl Expression for ConcatFunction {
     ) -> Result<ResolveResult<'a>, crate::TransformError> {
         // Create a mutable string we can write to, in rust this is fast, a string is just Vec<u8>
         let mut res = String::new();
-        // Iterate over the arguments to the function
-        for expr in self.args.iter() {
-            // Resolve each argument by passing the state, then return any errors if they occur.
-            let resolved = expr.resolve(state)?;
-            // Convert the value to string
-            let dat = resolved.try_into_string("concat", &self.span)?;
-            // Push the resulting string to the result vector.
-            res.push_str(&dat);
-        }
+        // Write each argument to the string, see `write_into` below.
+        self.write_into(state, &mut res)?;
         // Since we own the data we want to return here, return ResolveResult::Value. If we had built a reference
         // to a previous result (which itself might be a reference to input data!), we could have returned a reference here instead.
         Ok(ResolveResult::Owned(Value::String(res)))
@@ -59,6 +52,37 @@ impl Expression for ConcatFunction {
     }
 }
 
+impl ConcatFunction {
+    /// Write the arguments to `res`. Nested calls to `concat` write directly to the same string,
+    /// so chained concatenation does not allocate intermediate strings.
+    fn write_into<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+        res: &mut String,
+    ) -> Result<(), crate::TransformError> {
+        // Iterate over the arguments to the function
+        for expr in self.args.iter() {
+            if let ExpressionType::Function(FunctionType::Concat(inner)) = expr {
+                inner.write_into(state, res)?;
+                continue;
+            }
+            // Resolve each argument by passing the state, then return any errors if they occur.
+            let resolved = expr.resolve(state)?;
+            match resolved {
+                // If nothing has been written yet and the argument is an owned string, reuse its allocation.
+                ResolveResult::Owned(Value::String(s)) if res.is_empty() => *res = s,
+                resolved => {
+                    // Convert the value to string
+                    let dat = resolved.try_as_string("concat", &self.span)?;
+                    // Push the resulting string to the result vector.
+                    res.push_str(&dat);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 // other functions follow... This function converts the input to a string.
 function_def!(StringFunction, "string", 1);
 
@@ -68,6 +92,10 @@ impl Expression for StringFunction {
         state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
     ) -> Result<ResolveResult<'a>, crate::TransformError> {
         let dat = self.args[0].resolve(state)?;
+        // Strings are returned as is, which avoids copying borrowed input.
+        if dat.is_string() {
+            return Ok(dat);
+        }
         Ok(ResolveResult::Owned(
             dat.try_into_string("string", &self.span)?
                 .into_owned()
@@ -500,6 +528,212 @@ impl Expression for TranslateFunction {
     }
 }
 
+/// Compute the Levenshtein distance between `a` and `b`, counted in characters.
+///
+/// This is quadratic in the input, so each row of the table counts one operation
+/// per character in `b` towards the operation limit.
+fn levenshtein_distance(
+    a: &str,
+    b: &str,
+    state: &mut crate::expressions::ExpressionExecutionState<'_, '_>,
+) -> Result<usize, crate::TransformError> {
+    let b: Vec<char> = b.chars().collect();
+    // Distances from the current prefix of `a` to each prefix of `b`.
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        state.inc_ops(b.len())?;
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    Ok(row[b.len()])
+}
+
+function_def!(LevenshteinFunction, "levenshtein", 2);
+
+impl Expression for LevenshteinFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, crate::TransformError> {
+        let a = self.args[0].resolve(state)?;
+        let a = a.try_as_string("levenshtein", &self.span)?;
+        let b = self.args[1].resolve(state)?;
+        let b = b.try_as_string("levenshtein", &self.span)?;
+
+        Ok(ResolveResult::Owned(Value::from(levenshtein_distance(
+            &a, &b, state,
+        )?)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        for arg in &self.args {
+            let res = arg.resolve_types(state)?;
+            res.assert_assignable_to(&Type::stringifyable(), &self.span)?;
+        }
+        Ok(Type::Integer)
+    }
+}
+
+function_def!(SimilarityFunction, "similarity", 2);
+
+impl Expression for SimilarityFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, crate::TransformError> {
+        let a = self.args[0].resolve(state)?;
+        let a = a.try_as_string("similarity", &self.span)?;
+        let b = self.args[1].resolve(state)?;
+        let b = b.try_as_string("similarity", &self.span)?;
+
+        let max_len = a.chars().count().max(b.chars().count());
+        let similarity = if max_len == 0 {
+            1.0
+        } else {
+            1.0 - levenshtein_distance(&a, &b, state)? as f64 / max_len as f64
+        };
+        Ok(ResolveResult::Owned(Value::from(similarity)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        for arg in &self.args {
+            let res = arg.resolve_types(state)?;
+            res.assert_assignable_to(&Type::stringifyable(), &self.span)?;
+        }
+        Ok(Type::Float)
+    }
+}
+
+function_def!(HexEncodeFunction, "hex_encode", 1);
+
+impl Expression for HexEncodeFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, crate::TransformError> {
+        let inp = self.args[0].resolve(state)?;
+        let inp = inp.try_as_string("hex_encode", &self.span)?;
+
+        let mut res = String::with_capacity(inp.len() * 2);
+        for b in inp.bytes() {
+            res.push_str(&format!("{b:02x}"));
+        }
+        Ok(ResolveResult::Owned(Value::String(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        let input = self.args[0].resolve_types(state)?;
+        input.assert_assignable_to(&Type::stringifyable(), &self.span)?;
+        Ok(Type::String)
+    }
+}
+
+function_def!(HexDecodeFunction, "hex_decode", 1);
+
+impl Expression for HexDecodeFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, crate::TransformError> {
+        let inp = self.args[0].resolve(state)?;
+        let inp = inp.try_as_string("hex_decode", &self.span)?;
+        let hex = inp.strip_prefix("0x").unwrap_or(&inp);
+
+        let nibble = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+        let bytes = hex
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| match pair {
+                [high, low] => Some((nibble(*high)? << 4) | nibble(*low)?),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let Some(bytes) = bytes else {
+            return Err(crate::TransformError::new_conversion_failed(
+                format!("Failed to decode {inp:?} as hex in hex_decode"),
+                &self.span,
+            ));
+        };
+        let res = String::from_utf8(bytes).map_err(|_| {
+            crate::TransformError::new_conversion_failed(
+                format!("Decoded value of {inp:?} is not valid UTF-8 in hex_decode"),
+                &self.span,
+            )
+        })?;
+        Ok(ResolveResult::Owned(Value::String(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        let input = self.args[0].resolve_types(state)?;
+        input.assert_assignable_to(&Type::String, &self.span)?;
+        Ok(Type::String)
+    }
+}
+
+function_def!(RepeatFunction, "repeat", 2);
+
+/// Largest string `repeat` may produce, in bytes.
+const MAX_REPEAT_LENGTH: usize = 16 * 1024 * 1024;
+
+impl Expression for RepeatFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, crate::TransformError> {
+        let inp = self.args[0].resolve(state)?;
+        let inp = inp.try_as_string("repeat", &self.span)?;
+        let count = self.args[1]
+            .resolve(state)?
+            .try_as_number("repeat", &self.span)?
+            .try_as_i64(&self.span)?;
+        if count < 0 {
+            return Err(crate::TransformError::new_invalid_operation(
+                format!("Number of repetitions in repeat must be non-negative, got {count}"),
+                &self.span,
+            ));
+        }
+
+        let count = count as usize;
+        if inp.len().saturating_mul(count) > MAX_REPEAT_LENGTH {
+            return Err(crate::TransformError::new_invalid_operation(
+                format!(
+                    "Result of repeat would be longer than the limit of {MAX_REPEAT_LENGTH} bytes"
+                ),
+                &self.span,
+            ));
+        }
+        Ok(ResolveResult::Owned(Value::String(inp.repeat(count))))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        let input = self.args[0].resolve_types(state)?;
+        input.assert_assignable_to(&Type::stringifyable(), &self.span)?;
+        let count = self.args[1].resolve_types(state)?;
+        count.assert_assignable_to(&Type::Integer, &self.span)?;
+        Ok(Type::String)
+    }
+}
+
 // Once the function is defined it should be added to the main function enum in expressions/base.rs, and to the get_function_expression function.
 // We can just add a test in this file:
 #[cfg(test)]
@@ -528,6 +762,34 @@ mod tests {
         assert_eq!("foo123bar", res.get("concat3").unwrap().as_str().unwrap());
     }
 
+    #[test]
+    pub fn test_nested_concat() {
+        let expr = compile_expression(
+            r#"concat(concat(input.topic, "/", concat(input.id, "")), "/", string(input.n), concat("x", null))"#,
+            &["input"],
+        )
+        .unwrap();
+
+        let inp = json!({
+            "topic": "my/topic",
+            "id": "my-id",
+            "n": 5
+        });
+        let res = expr.run([&inp]).unwrap();
+        assert_eq!("my/topic/my-id/5x", res.as_str().unwrap());
+    }
+
+    #[test]
+    pub fn test_string_borrows_input() {
+        let expr = compile_expression("string(input.id)", &["input"]).unwrap();
+        let inp = json!({ "id": "my-id" });
+        let res = expr.run([&inp]).unwrap();
+        let crate::ResolveResult::Borrowed(res) = res else {
+            panic!("Expected a borrowed result, got {res:?}");
+        };
+        assert!(std::ptr::eq(res, &inp["id"]));
+    }
+
     #[test]
     pub fn test_string_function() {
         let expr = compile_expression(
@@ -943,4 +1205,151 @@ mod tests {
         let ty = expr.run_types([Type::String]).unwrap();
         assert_eq!(ty, Type::String);
     }
+
+    #[test]
+    fn test_levenshtein() {
+        let expr = compile_expression(
+            r#"[
+            levenshtein("kitten", "sitting"),
+            levenshtein("", "abc"),
+            levenshtein("abc", ""),
+            levenshtein("flaw", "lawn"),
+            levenshtein(input, "Pump 1A"),
+            levenshtein("æøå", "aøå"),
+            levenshtein(123, 124),
+        ]"#,
+            &["input"],
+        )
+        .unwrap();
+        let inp = json!("pump 1a");
+        let res = expr.run([&inp]).unwrap();
+        assert_eq!(res.as_ref(), &json!([3, 3, 3, 2, 2, 1, 1]));
+    }
+
+    #[test]
+    fn test_similarity() {
+        let expr = compile_expression(
+            r#"[
+            similarity("kitten", "sitting"),
+            similarity("", ""),
+            similarity("abc", "abc"),
+            similarity("abc", "xyz"),
+            similarity(input, "Pump 1A"),
+        ]"#,
+            &["input"],
+        )
+        .unwrap();
+        let inp = json!("Pump 1B");
+        let res = expr.run([&inp]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([1.0 - 3.0 / 7.0, 1.0, 1.0, 0.0, 1.0 - 1.0 / 7.0])
+        );
+    }
+
+    #[test]
+    fn test_levenshtein_operation_limit() {
+        let expr = compile_expression("levenshtein(input, input)", &["input"]).unwrap();
+        let inp = json!("a".repeat(2000));
+        let err = expr.run_limited([&inp], 1_000_000).unwrap_err();
+        assert!(matches!(err, crate::TransformError::OperationLimitExceeded));
+
+        let expr = compile_expression("similarity(input, input)", &["input"]).unwrap();
+        let err = expr.run_limited([&inp], 1_000_000).unwrap_err();
+        assert!(matches!(err, crate::TransformError::OperationLimitExceeded));
+
+        let inp = json!("a".repeat(500));
+        let res = expr.run_limited([&inp], 1_000_000).unwrap();
+        assert_eq!(res.as_ref(), &json!(1.0));
+    }
+
+    #[test]
+    fn test_similarity_types() {
+        let expr = compile_expression("similarity(input, 'a')", &["input"]).unwrap();
+        assert_eq!(expr.run_types([Type::String]).unwrap(), Type::Float);
+        assert!(expr.run_types([Type::any_object()]).is_err());
+
+        let expr = compile_expression("levenshtein(input, 'a')", &["input"]).unwrap();
+        assert_eq!(expr.run_types([Type::String]).unwrap(), Type::Integer);
+    }
+
+    #[test]
+    fn test_hex_encode_decode() {
+        let expr = compile_expression(
+            r#"[
+            hex_encode("hello"),
+            hex_encode(""),
+            hex_encode(255),
+            hex_decode("68656c6c6f"),
+            hex_decode("0x4142"),
+            hex_decode("C3A6"),
+            hex_decode(hex_encode(input)),
+        ]"#,
+            &["input"],
+        )
+        .unwrap();
+        let inp = json!("æøå");
+        let res = expr.run([&inp]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!(["68656c6c6f", "", "323535", "hello", "AB", "æ", "æøå"])
+        );
+    }
+
+    #[test]
+    fn test_hex_decode_errors() {
+        let expr = compile_expression("hex_decode(input)", &["input"]).unwrap();
+        for (inp, msg) in [
+            ("abc", r#"Failed to decode "abc" as hex in hex_decode"#),
+            ("zz", r#"Failed to decode "zz" as hex in hex_decode"#),
+            ("+1+1", r#"Failed to decode "+1+1" as hex in hex_decode"#),
+            (
+                "ff",
+                r#"Decoded value of "ff" is not valid UTF-8 in hex_decode"#,
+            ),
+        ] {
+            let inp = json!(inp);
+            let err = expr.run([&inp]).unwrap_err();
+            assert_eq!(err.message(), msg);
+        }
+    }
+
+    #[test]
+    fn test_repeat() {
+        let expr = compile_expression(
+            r#"[repeat("ab", 3), repeat("-", 0), repeat("", 5), repeat(12, 2), "x".repeat(2)]"#,
+            &[],
+        )
+        .unwrap();
+        let res = expr.run([]).unwrap();
+        assert_eq!(res.as_ref(), &json!(["ababab", "", "", "1212", "xx"]));
+    }
+
+    #[test]
+    fn test_repeat_errors() {
+        let expr = compile_expression("repeat('ab', input)", &["input"]).unwrap();
+        let inp = json!(-1);
+        let err = expr.run([&inp]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Number of repetitions in repeat must be non-negative, got -1"
+        );
+        let inp = json!(1_000_000_000);
+        let err = expr.run([&inp]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Result of repeat would be longer than the limit of 16777216 bytes"
+        );
+        let inp = json!(8 * 1024 * 1024);
+        assert_eq!(
+            expr.run([&inp]).unwrap().as_str().unwrap().len(),
+            16 * 1024 * 1024
+        );
+
+        assert_eq!(expr.run_types([Type::Integer]).unwrap(), Type::String);
+        expr.run_types([Type::String]).unwrap_err();
+
+        let expr = compile_expression("repeat('', 1000000000000)", &[]).unwrap();
+        assert_eq!(expr.run([]).unwrap().as_ref(), &json!(""));
+    }
 }
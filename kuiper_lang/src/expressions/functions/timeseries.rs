@@ -0,0 +1,244 @@
+use logos::Span;
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    expressions::{numbers::JsonNumber, Expression, ExpressionExecutionState, ResolveResult},
+    types::{Object, Type, TypeError, TypeExecutionState},
+    TransformError,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+enum ResampleMethod {
+    /// Interpolate linearly between the points on either side.
+    Linear,
+    /// Use the value of the closest point at or before the grid point.
+    Previous,
+}
+
+/// Read a datapoint object `{ "timestamp": ..., "value": ... }`.
+fn read_point<'a>(point: &'a Value, span: &Span) -> Result<(i64, &'a Value), TransformError> {
+    let (Some(timestamp), Some(value)) = (point.get("timestamp"), point.get("value")) else {
+        return Err(TransformError::new_invalid_operation(
+            "Points passed to resample must be objects with timestamp and value".to_owned(),
+            span,
+        ));
+    };
+    let timestamp = JsonNumber::try_from(timestamp, "resample", span)?.try_as_i64(span)?;
+    Ok((timestamp, value))
+}
+
+fn interpolate(
+    (t0, v0): (i64, &Value),
+    (t1, v1): (i64, &Value),
+    t: i64,
+    span: &Span,
+) -> Result<Value, TransformError> {
+    let v0 = JsonNumber::try_from(v0, "resample", span)?.as_f64();
+    let v1 = JsonNumber::try_from(v1, "resample", span)?.as_f64();
+    let res = v0 + (v1 - v0) * ((t - t0) as f64 / (t1 - t0) as f64);
+    Number::from_f64(res).map(Value::Number).ok_or_else(|| {
+        TransformError::new_conversion_failed(
+            "Failed to convert result of resample to number",
+            span,
+        )
+    })
+}
+
+function_def!(ResampleFunction, "resample", 2, Some(3));
+
+impl Expression for ResampleFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let source = self.args[0].resolve(state)?;
+        let Value::Array(raw_points) = source.as_ref() else {
+            return Err(TransformError::new_incorrect_type(
+                "Incorrect input to resample",
+                "array",
+                TransformError::value_desc(&source),
+                &self.span,
+            ));
+        };
+        let interval = self.args[1]
+            .resolve(state)?
+            .try_as_number("resample", &self.span)?
+            .try_as_i64(&self.span)?;
+        if interval <= 0 {
+            return Err(TransformError::new_invalid_operation(
+                "Interval in resample must be greater than 0".to_owned(),
+                &self.span,
+            ));
+        }
+        let method = match self.args.get(2) {
+            Some(m) => {
+                let m = m.resolve(state)?;
+                match m.try_as_string("resample", &self.span)?.as_ref() {
+                    "linear" => ResampleMethod::Linear,
+                    "previous" => ResampleMethod::Previous,
+                    other => {
+                        return Err(TransformError::new_invalid_operation(
+                            format!(
+                                "Unknown method {other} in resample, expected linear or previous"
+                            ),
+                            &self.span,
+                        ))
+                    }
+                }
+            }
+            None => ResampleMethod::Linear,
+        };
+
+        let mut points = raw_points
+            .iter()
+            .map(|p| read_point(p, &self.span))
+            .collect::<Result<Vec<_>, _>>()?;
+        points.sort_by_key(|(t, _)| *t);
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return Ok(ResolveResult::Owned(Value::Array(Vec::new())));
+        };
+
+        // The grid starts at the first multiple of the interval at or after the first point.
+        let mut t = first.0.div_euclid(interval) * interval;
+        if t < first.0 {
+            t += interval;
+        }
+        let last = last.0;
+        let mut res = Vec::new();
+        // Index of the last point at or before `t`.
+        let mut idx = 0;
+        while t <= last {
+            // A small interval over a long range produces a lot of output, so count each grid point
+            // towards the operation limit.
+            state.inc_op()?;
+            while idx + 1 < points.len() && points[idx + 1].0 <= t {
+                idx += 1;
+            }
+            let (t0, v0) = points[idx];
+            let value = if t0 == t || method == ResampleMethod::Previous {
+                v0.clone()
+            } else {
+                interpolate(points[idx], points[idx + 1], t, &self.span)?
+            };
+            let mut point = Map::new();
+            point.insert("timestamp".to_owned(), Value::from(t));
+            point.insert("value".to_owned(), value);
+            res.push(Value::Object(point));
+
+            let Some(next) = t.checked_add(interval) else {
+                break;
+            };
+            t = next;
+        }
+        Ok(ResolveResult::Owned(Value::Array(res)))
+    }
+
+    fn resolve_types(&self, state: &mut TypeExecutionState<'_, '_>) -> Result<Type, TypeError> {
+        let source = self.args[0].resolve_types(state)?;
+        source.assert_assignable_to(&Type::any_array(), &self.span)?;
+        self.args[1]
+            .resolve_types(state)?
+            .assert_assignable_to(&Type::Integer, &self.span)?;
+        if let Some(method) = self.args.get(2) {
+            method
+                .resolve_types(state)?
+                .assert_assignable_to(&Type::String, &self.span)?;
+        }
+        Ok(Type::array_of_type(Type::Object(
+            Object::default()
+                .with_field("timestamp", Type::Integer)
+                .with_field("value", Type::Any),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{compile_expression, TransformError};
+
+    #[test]
+    fn test_resample_linear() {
+        let expr = compile_expression("resample(input, 1000)", &["input"]).unwrap();
+        let input = json!([
+            { "timestamp": 3500, "value": 20 },
+            { "timestamp": 500, "value": 5 },
+            { "timestamp": 1500, "value": 10 },
+        ]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([
+                { "timestamp": 1000, "value": 7.5 },
+                { "timestamp": 2000, "value": 12.5 },
+                { "timestamp": 3000, "value": 17.5 },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_resample_previous() {
+        let expr = compile_expression(r#"resample(input, 10, "previous")"#, &["input"]).unwrap();
+        let input = json!([
+            { "timestamp": 0, "value": "off" },
+            { "timestamp": 15, "value": "on" },
+            { "timestamp": 20, "value": "off" },
+            { "timestamp": 41, "value": "on" },
+        ]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!([
+                { "timestamp": 0, "value": "off" },
+                { "timestamp": 10, "value": "off" },
+                { "timestamp": 20, "value": "off" },
+                { "timestamp": 30, "value": "off" },
+                { "timestamp": 40, "value": "off" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_resample_edge_cases() {
+        let expr = compile_expression("resample(input, 10)", &["input"]).unwrap();
+        let input = json!([]);
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!([]));
+        let input = json!([{ "timestamp": -15, "value": 1 }, { "timestamp": -5, "value": 3 }]);
+        assert_eq!(
+            expr.run([&input]).unwrap().as_ref(),
+            &json!([{ "timestamp": -10, "value": 2.0 }])
+        );
+        let input = json!([{ "timestamp": 11, "value": 1 }, { "timestamp": 19, "value": 3 }]);
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!([]));
+    }
+
+    #[test]
+    fn test_resample_errors() {
+        let expr = compile_expression(
+            "resample(input.points, input.interval, input.method)",
+            &["input"],
+        )
+        .unwrap();
+        let input = json!({ "points": [{ "timestamp": 0 }], "interval": 10, "method": "linear" });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Points passed to resample must be objects with timestamp and value"
+        );
+        let input = json!({ "points": [], "interval": 0, "method": "linear" });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(err.message(), "Interval in resample must be greater than 0");
+        let input = json!({ "points": [], "interval": 10, "method": "spline" });
+        let err = expr.run([&input]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Unknown method spline in resample, expected linear or previous"
+        );
+
+        let expr = compile_expression("resample(input, 1)", &["input"]).unwrap();
+        let input = json!([{ "timestamp": 0, "value": 1 }, { "timestamp": 1000000, "value": 2 }]);
+        let err = expr.run_limited([&input], 10_000).unwrap_err();
+        assert!(matches!(err, TransformError::OperationLimitExceeded));
+    }
+}
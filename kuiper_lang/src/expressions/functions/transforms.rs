@@ -86,6 +86,225 @@ impl Expression for PairsFunction {
     }
 }
 
+function_def!(FlattenObjectFunction, "flatten_object", 1, Some(3));
+
+/// Insert `value` into `out`, flattening nested objects, and arrays if `arrays` is set,
+/// into keys joined by `separator`.
+fn flatten_into(
+    key: String,
+    value: Value,
+    separator: &str,
+    arrays: bool,
+    out: &mut Map<String, Value>,
+) {
+    match value {
+        Value::Object(o) if !o.is_empty() => {
+            for (k, v) in o {
+                flatten_into(format!("{key}{separator}{k}"), v, separator, arrays, out);
+            }
+        }
+        Value::Array(a) if arrays && !a.is_empty() => {
+            for (idx, v) in a.into_iter().enumerate() {
+                flatten_into(format!("{key}{separator}{idx}"), v, separator, arrays, out);
+            }
+        }
+        v => {
+            out.insert(key, v);
+        }
+    }
+}
+
+/// Get the separator and array handling arguments shared by `flatten_object` and `unflatten_object`.
+fn get_flatten_options<'a>(
+    args: &'a [crate::ExpressionType],
+    state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    name: &str,
+    span: &logos::Span,
+) -> Result<(String, bool), TransformError> {
+    let separator = match args.get(1) {
+        Some(s) => s.resolve(state)?.try_into_string(name, span)?.into_owned(),
+        None => ".".to_owned(),
+    };
+    if separator.is_empty() {
+        return Err(TransformError::new_invalid_operation(
+            format!("Separator in {name} cannot be empty"),
+            span,
+        ));
+    }
+    let arrays = match args.get(2) {
+        Some(a) => a.resolve(state)?.as_bool(),
+        None => false,
+    };
+    Ok((separator, arrays))
+}
+
+fn flatten_options_types(
+    args: &[crate::ExpressionType],
+    state: &mut crate::types::TypeExecutionState<'_, '_>,
+    span: &logos::Span,
+) -> Result<(), crate::types::TypeError> {
+    let input = args[0].resolve_types(state)?;
+    input.assert_assignable_to(&Type::any_object(), span)?;
+    if let Some(separator) = args.get(1) {
+        let separator = separator.resolve_types(state)?;
+        separator.assert_assignable_to(&Type::stringifyable(), span)?;
+    }
+    if let Some(arrays) = args.get(2) {
+        arrays.resolve_types(state)?;
+    }
+    Ok(())
+}
+
+impl Expression for FlattenObjectFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let inp = self.args[0].resolve(state)?;
+        let obj = match inp.into_owned() {
+            Value::Object(o) => o,
+            x => {
+                return Err(TransformError::new_incorrect_type(
+                    "Incorrect input to flatten_object",
+                    "object",
+                    TransformError::value_desc(&x),
+                    &self.span,
+                ));
+            }
+        };
+        let (separator, arrays) =
+            get_flatten_options(&self.args, state, "flatten_object", &self.span)?;
+
+        let mut res = Map::new();
+        for (key, val) in obj {
+            flatten_into(key, val, &separator, arrays, &mut res);
+        }
+        Ok(ResolveResult::Owned(Value::Object(res)))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<crate::types::Type, crate::types::TypeError> {
+        flatten_options_types(&self.args, state, &self.span)?;
+        Ok(Type::any_object())
+    }
+}
+
+function_def!(UnflattenObjectFunction, "unflatten_object", 1, Some(3));
+
+/// A partially built object in `unflatten_object`. Only branches are converted to arrays,
+/// so that values in the input are never modified.
+enum UnflattenNode {
+    Leaf(Value),
+    Branch(BTreeMap<String, UnflattenNode>),
+}
+
+impl UnflattenNode {
+    fn into_value(self, arrays: bool) -> Value {
+        let children = match self {
+            UnflattenNode::Leaf(v) => return v,
+            UnflattenNode::Branch(children) => children,
+        };
+
+        if arrays {
+            // Indices must be written without leading zeros, and cover 0..len exactly.
+            let mut indices: Vec<_> = children
+                .keys()
+                .filter_map(|k| k.parse::<usize>().ok().filter(|i| i.to_string() == *k))
+                .collect();
+            indices.sort_unstable();
+            if indices.len() == children.len() && indices.iter().enumerate().all(|(i, v)| i == *v) {
+                let mut items: Vec<_> = children
+                    .into_iter()
+                    .map(|(k, v)| (k.parse::<usize>().unwrap_or_default(), v))
+                    .collect();
+                items.sort_unstable_by_key(|(i, _)| *i);
+                return Value::Array(
+                    items
+                        .into_iter()
+                        .map(|(_, v)| v.into_value(arrays))
+                        .collect(),
+                );
+            }
+        }
+
+        Value::Object(
+            children
+                .into_iter()
+                .map(|(k, v)| (k, v.into_value(arrays)))
+                .collect(),
+        )
+    }
+}
+
+impl Expression for UnflattenObjectFunction {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut crate::expressions::ExpressionExecutionState<'a, '_>,
+    ) -> Result<ResolveResult<'a>, TransformError> {
+        let inp = self.args[0].resolve(state)?;
+        let obj = match inp.into_owned() {
+            Value::Object(o) => o,
+            x => {
+                return Err(TransformError::new_incorrect_type(
+                    "Incorrect input to unflatten_object",
+                    "object",
+                    TransformError::value_desc(&x),
+                    &self.span,
+                ));
+            }
+        };
+        let (separator, arrays) =
+            get_flatten_options(&self.args, state, "unflatten_object", &self.span)?;
+
+        let conflict = |key: &str| {
+            TransformError::new_invalid_operation(
+                format!(
+                    "Conflicting keys in unflatten_object: {key:?} is both a value and an object"
+                ),
+                &self.span,
+            )
+        };
+
+        let mut root = BTreeMap::new();
+        for (key, val) in obj {
+            let mut parts = key.split(separator.as_str()).peekable();
+            let mut current = &mut root;
+            while let Some(part) = parts.next() {
+                if parts.peek().is_none() {
+                    if current.contains_key(part) {
+                        return Err(conflict(&key));
+                    }
+                    current.insert(part.to_owned(), UnflattenNode::Leaf(val));
+                    break;
+                }
+                let node = current
+                    .entry(part.to_owned())
+                    .or_insert_with(|| UnflattenNode::Branch(BTreeMap::new()));
+                current = match node {
+                    UnflattenNode::Branch(children) => children,
+                    UnflattenNode::Leaf(_) => return Err(conflict(&key)),
+                };
+            }
+        }
+        // The root is always an object, only nested objects are converted to arrays.
+        Ok(ResolveResult::Owned(Value::Object(
+            root.into_iter()
+                .map(|(k, v)| (k, v.into_value(arrays)))
+                .collect(),
+        )))
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<crate::types::Type, crate::types::TypeError> {
+        flatten_options_types(&self.args, state, &self.span)?;
+        Ok(Type::any_object())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -190,4 +409,119 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn test_flatten_object() {
+        let expr = compile_expression(
+            r#"{
+            "default": flatten_object(input),
+            "separator": flatten_object(input, "/"),
+            "arrays": flatten_object(input, "_", true),
+        }"#,
+            &["input"],
+        )
+        .unwrap();
+        let inp = json!({
+            "a": { "b": { "c": 1 }, "d": [1, { "e": 2 }] },
+            "f": "g",
+            "empty": {},
+            "empty_list": []
+        });
+        let res = expr.run([&inp]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!({
+                "default": {
+                    "a.b.c": 1,
+                    "a.d": [1, { "e": 2 }],
+                    "f": "g",
+                    "empty": {},
+                    "empty_list": []
+                },
+                "separator": {
+                    "a/b/c": 1,
+                    "a/d": [1, { "e": 2 }],
+                    "f": "g",
+                    "empty": {},
+                    "empty_list": []
+                },
+                "arrays": {
+                    "a_b_c": 1,
+                    "a_d_0": 1,
+                    "a_d_1_e": 2,
+                    "f": "g",
+                    "empty": {},
+                    "empty_list": []
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_unflatten_object() {
+        let expr = compile_expression(
+            r#"{
+            "default": unflatten_object(input),
+            "arrays": unflatten_object(input, ".", true),
+            "roundtrip": unflatten_object(flatten_object(nested, "/", true), "/", true),
+        }"#,
+            &["input", "nested"],
+        )
+        .unwrap();
+        let inp = json!({
+            "a.b.c": 1,
+            "a.d.0": 1,
+            "a.d.1.e": 2,
+            "a.d.10": 3,
+            "f.0": { "0": 1 },
+            "f.1": null,
+            "g": "h"
+        });
+        let nested = json!({
+            "a": { "b": [1, [2, { "c": 3 }]], "d": {} },
+            "e": []
+        });
+        let res = expr.run([&inp, &nested]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!({
+                "default": {
+                    "a": { "b": { "c": 1 }, "d": { "0": 1, "1": { "e": 2 }, "10": 3 } },
+                    "f": { "0": { "0": 1 }, "1": null },
+                    "g": "h"
+                },
+                "arrays": {
+                    // Not converted to an array, since index 2 to 9 are missing.
+                    "a": { "b": { "c": 1 }, "d": { "0": 1, "1": { "e": 2 }, "10": 3 } },
+                    // Values in the input are not converted.
+                    "f": [{ "0": 1 }, null],
+                    "g": "h"
+                },
+                "roundtrip": nested
+            })
+        );
+
+        let expr = compile_expression(r#"unflatten_object(input, ".", true)"#, &["input"]).unwrap();
+        let inp = json!({ "0": 1, "1.0": 2 });
+        let res = expr.run([&inp]).unwrap();
+        assert_eq!(res.as_ref(), &json!({ "0": 1, "1": [2] }));
+    }
+
+    #[test]
+    fn test_unflatten_object_errors() {
+        let expr = compile_expression("unflatten_object(input)", &["input"]).unwrap();
+        let inp = json!({ "a": 1, "a.b": 2 });
+        let err = expr.run([&inp]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            r#"Conflicting keys in unflatten_object: "a.b" is both a value and an object"#
+        );
+
+        let expr = compile_expression("unflatten_object(input, '')", &["input"]).unwrap();
+        let err = expr.run([&inp]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Separator in unflatten_object cannot be empty"
+        );
+    }
 }
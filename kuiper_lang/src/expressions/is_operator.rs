@@ -3,7 +3,7 @@ use std::fmt::Display;
 use serde_json::Value;
 
 use crate::{
-    types::{Truthy, Type},
+    types::{Array, Truthy, Type},
     BuildError, ExpressionType, TransformError,
 };
 
@@ -45,10 +45,128 @@ impl Display for TypeLiteral {
     }
 }
 
+impl TypeLiteral {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            TypeLiteral::Null => value.is_null(),
+            TypeLiteral::Int => value.is_i64() || value.is_u64(),
+            TypeLiteral::Bool => value.is_boolean(),
+            TypeLiteral::Float => value.is_f64(),
+            TypeLiteral::String => value.is_string(),
+            TypeLiteral::Array => value.is_array(),
+            TypeLiteral::Object => value.is_object(),
+            TypeLiteral::Number => value.is_number(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+/// The right hand side of an "is" expression, either a plain type literal, or
+/// a shape describing the contents of an array or object.
+pub enum TypePattern {
+    /// A plain type literal, like `int`.
+    Literal(TypeLiteral),
+    /// An array where every element matches the inner pattern, like `[number]`.
+    Array(Box<TypePattern>),
+    /// An object where each listed field matches its pattern, like `{ "value": number }`.
+    /// Other fields are ignored, and a missing field is treated as `null`.
+    Object(Vec<(String, TypePattern)>),
+}
+
+impl From<TypeLiteral> for TypePattern {
+    fn from(value: TypeLiteral) -> Self {
+        TypePattern::Literal(value)
+    }
+}
+
+impl Display for TypePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypePattern::Literal(l) => write!(f, "{l}"),
+            TypePattern::Array(inner) => write!(f, "[{inner}]"),
+            TypePattern::Object(fields) if fields.is_empty() => write!(f, "{{}}"),
+            TypePattern::Object(fields) => {
+                write!(f, "{{ ")?;
+                for (i, (key, pattern)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {pattern}", Value::String(key.clone()))?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
+}
+
+impl TypePattern {
+    fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (TypePattern::Literal(l), v) => l.matches(v),
+            (TypePattern::Array(inner), Value::Array(a)) => a.iter().all(|v| inner.matches(v)),
+            (TypePattern::Object(fields), Value::Object(o)) => fields
+                .iter()
+                .all(|(key, pattern)| pattern.matches(o.get(key).unwrap_or(&Value::Null))),
+            _ => false,
+        }
+    }
+
+    fn matches_type(&self, ty: &Type) -> Truthy {
+        match (self, ty) {
+            (TypePattern::Literal(l), ty) => IsExpression::matches_type(*l, ty),
+            (p, Type::Union(r)) => r
+                .iter()
+                .map(|v| p.matches_type(v))
+                .reduce(|i1, i2| i1.combine(i2))
+                .unwrap_or(Truthy::Never),
+            (_, Type::Any) => Truthy::Maybe,
+            (p, Type::Constant(v)) => {
+                if p.matches(v) {
+                    Truthy::Always
+                } else {
+                    Truthy::Never
+                }
+            }
+            (TypePattern::Array(inner), Type::Array(arr)) => Self::matches_array(inner, arr),
+            (TypePattern::Object(fields), Type::Object(obj)) => {
+                all_of(fields.iter().map(|(key, pattern)| {
+                    pattern.matches_type(&obj.index_into(key).unwrap_or_else(Type::null))
+                }))
+            }
+            _ => Truthy::Never,
+        }
+    }
+
+    fn matches_array(inner: &TypePattern, arr: &Array) -> Truthy {
+        let known = all_of(arr.elements.iter().map(|e| inner.matches_type(e)));
+        match &arr.end_dynamic {
+            // There may be no elements past the known ones, so a mismatch is not certain.
+            Some(end) => match inner.matches_type(end) {
+                Truthy::Always => known,
+                _ => all_of([known, Truthy::Maybe].into_iter()),
+            },
+            None => known,
+        }
+    }
+}
+
+/// Combine the results of several checks that must all pass.
+fn all_of(checks: impl Iterator<Item = Truthy>) -> Truthy {
+    let mut res = Truthy::Always;
+    for check in checks {
+        match check {
+            Truthy::Never => return Truthy::Never,
+            Truthy::Maybe => res = Truthy::Maybe,
+            Truthy::Always => (),
+        }
+    }
+    res
+}
+
 #[derive(Debug)]
 pub struct IsExpression {
     lhs: Box<ExpressionType>,
-    rhs: TypeLiteral,
+    rhs: TypePattern,
     not: bool,
 }
 
@@ -69,16 +187,7 @@ impl Expression for IsExpression {
     ) -> Result<ResolveResult<'a>, TransformError> {
         state.inc_op()?;
         let lhs = self.lhs.resolve(state)?;
-        let res = match self.rhs {
-            TypeLiteral::Null => lhs.is_null(),
-            TypeLiteral::Int => lhs.is_i64() || lhs.is_u64(),
-            TypeLiteral::Bool => lhs.is_boolean(),
-            TypeLiteral::Float => lhs.is_f64(),
-            TypeLiteral::String => lhs.is_string(),
-            TypeLiteral::Array => lhs.is_array(),
-            TypeLiteral::Object => lhs.is_object(),
-            TypeLiteral::Number => lhs.is_number(),
-        };
+        let res = self.rhs.matches(&lhs);
         if self.not {
             Ok(ResolveResult::Owned(Value::Bool(!res)))
         } else {
@@ -91,7 +200,7 @@ impl Expression for IsExpression {
         state: &mut crate::types::TypeExecutionState<'_, '_>,
     ) -> Result<Type, crate::types::TypeError> {
         let lhs = self.lhs.resolve_types(state)?;
-        match Self::matches_type(self.rhs, &lhs) {
+        match self.rhs.matches_type(&lhs) {
             Truthy::Always => Ok(Type::from_const(!self.not)),
             Truthy::Maybe => Ok(Type::Boolean),
             Truthy::Never => Ok(Type::from_const(self.not)),
@@ -100,7 +209,7 @@ impl Expression for IsExpression {
 }
 
 impl IsExpression {
-    pub fn new(lhs: ExpressionType, rhs: TypeLiteral, not: bool) -> Result<Self, BuildError> {
+    pub fn new(lhs: ExpressionType, rhs: TypePattern, not: bool) -> Result<Self, BuildError> {
         lhs.fail_if_lambda()?;
         Ok(Self {
             lhs: Box::new(lhs),
@@ -175,4 +284,83 @@ mod tests {
         let ty = expr.run_types([Type::Any]).unwrap();
         assert_eq!(Type::Boolean, ty);
     }
+
+    #[test]
+    fn test_is_shape() {
+        let expr = crate::compile_expression(
+            r#"[
+                input is { "value": number, "timestamp": int },
+                input is { "value": string },
+                input.xs is [number],
+                input.ys is [number],
+                input is { "missing": null },
+                input is { "xs": [int] },
+                input is not { "nested": { "a": [bool] } },
+                input.xs is { },
+            ]"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = serde_json::json!({
+            "value": 1.5,
+            "timestamp": 10,
+            "xs": [1, 2.5],
+            "ys": [1, "a"],
+            "nested": { "a": [true, false] },
+        });
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &serde_json::json!([true, false, true, false, true, false, false, false])
+        );
+
+        let expr = crate::compile_expression(r#"[] is [int]"#, &[]).unwrap();
+        assert_eq!(expr.run([]).unwrap().as_ref(), &serde_json::json!(true));
+    }
+
+    #[test]
+    fn test_is_shape_types() {
+        use crate::types::{Array, Object};
+
+        let expr = crate::compile_expression(
+            r#"input is { "value": number, "tags": [string] }"#,
+            &["input"],
+        )
+        .unwrap();
+        let obj = |value: Type, tags: Type| {
+            Type::Object(
+                Object::default()
+                    .with_field("value", value)
+                    .with_field("tags", tags),
+            )
+        };
+        let tags = Type::array_of_type(Type::String);
+        let ty = expr.run_types([obj(Type::Float, tags.clone())]).unwrap();
+        assert_eq!(Type::from_const(true), ty);
+        let ty = expr.run_types([obj(Type::String, tags.clone())]).unwrap();
+        assert_eq!(Type::from_const(false), ty);
+        let ty = expr
+            .run_types([obj(Type::Float.union_with(Type::null()), tags)])
+            .unwrap();
+        assert_eq!(Type::Boolean, ty);
+        // An array with unknown length may be empty, so the element type cannot rule out a match.
+        let ty = expr
+            .run_types([obj(Type::Integer, Type::array_of_type(Type::Integer))])
+            .unwrap();
+        assert_eq!(Type::Boolean, ty);
+        let ty = expr
+            .run_types([obj(
+                Type::Integer,
+                Type::Array(Array {
+                    elements: vec![Type::Integer],
+                    end_dynamic: None,
+                }),
+            )])
+            .unwrap();
+        assert_eq!(Type::from_const(false), ty);
+        let ty = expr.run_types([Type::Integer]).unwrap();
+        assert_eq!(Type::from_const(false), ty);
+        let ty = expr.run_types([Type::Any]).unwrap();
+        assert_eq!(Type::Boolean, ty);
+    }
 }
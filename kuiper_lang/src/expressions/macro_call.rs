@@ -144,7 +144,7 @@ mod tests {
         let expr = compile_expression(r#"#m := () => input / 0; m()"#, &["input"]).unwrap();
         let err = expr.run(&[Value::from(10)]).unwrap_err();
         match err {
-            TransformError::InvalidOperation(d) => {
+            TransformError::DivideByZero(d) => {
                 assert_eq!(d.desc, "Divide by zero");
                 assert_eq!(d.span, Span { start: 18, end: 19 });
             }
@@ -15,6 +15,7 @@ mod selector;
 mod source;
 mod template_string;
 mod transform_error;
+mod try_expr;
 
 pub use array::{ArrayElement, ArrayExpression};
 #[cfg(feature = "completions")]
@@ -27,7 +28,7 @@ pub use defines::DefineExpression;
 pub use functions::dynamic::{DynamicFunction, DynamicFunctionBuilder};
 pub use functions::{function_def, FunctionExpression, FunctionInfo, LambdaAcceptFunction};
 pub use if_expr::IfExpression;
-pub use is_operator::{IsExpression, TypeLiteral};
+pub use is_operator::{IsExpression, TypeLiteral, TypePattern};
 pub use lambda::LambdaExpression;
 pub use macro_call::MacroCallExpression;
 pub use numbers::JsonNumber;
@@ -38,7 +39,8 @@ pub use run_builder::ExpressionRunBuilder;
 pub use selector::{SelectorElement, SelectorExpression, SourceElement};
 pub use source::{LazySourceData, LazySourceDataJson, SourceData};
 pub use template_string::{TemplateStringExpression, TemplateStringSegment};
-pub use transform_error::{TransformError, TransformErrorData};
+pub use transform_error::{TransformError, TransformErrorData, TransformErrorKind};
+pub use try_expr::TryExpression;
 
 pub(crate) use base::FunctionType;
 pub(crate) use functions::dynamic::DynamicFunctionSource;
@@ -247,10 +247,7 @@ impl JsonNumber {
     /// Try to divide self by a number, result is floating point.
     pub fn try_div(self, rhs: JsonNumber, span: &Span) -> Result<JsonNumber, TransformError> {
         if rhs.as_f64() == 0.0f64 {
-            return Err(TransformError::new_invalid_operation(
-                "Divide by zero".to_string(),
-                span,
-            ));
+            return Err(TransformError::new_divide_by_zero(span));
         }
         Ok(JsonNumber::Float(self.as_f64() / rhs.as_f64()))
     }
@@ -358,10 +355,7 @@ impl JsonNumber {
     /// Try to compute the modulus of self by rhs, this will fail if rhs is zero, or if the result cannot be represented as a JsonNumber.
     pub fn try_mod(self, rhs: JsonNumber, span: &Span) -> Result<JsonNumber, TransformError> {
         if rhs.as_f64() == 0.0f64 {
-            return Err(TransformError::new_invalid_operation(
-                "Divide by zero".to_string(),
-                span,
-            ));
+            return Err(TransformError::new_divide_by_zero(span));
         }
         match (self, rhs) {
             (JsonNumber::PosInteger(x), JsonNumber::PosInteger(y)) => {
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use crate::{
-    expressions::{Expression, ExpressionExecutionState},
+    expressions::{base::SourceValues, Expression, ExpressionExecutionState},
     source::SourceData,
     ExpressionType, ResolveResult, TransformError,
 };
@@ -72,7 +72,7 @@ where
     /// Run the expression, returning the result.
     pub fn run(self) -> Result<ResolveResult<'c>, TransformError> {
         let mut opcount = 0;
-        let data = self.items.map(Some).collect();
+        let data: SourceValues = self.items.map(Some).collect();
         let mut state =
             ExpressionExecutionState::new(&data, &mut opcount, self.max_operation_count);
         self.expression.resolve(&mut state)
@@ -81,7 +81,7 @@ where
     /// Run the expression, returning the result along with the number of operations performed.
     pub fn run_get_opcount(self) -> Result<(ResolveResult<'c>, i64), TransformError> {
         let mut opcount = 0;
-        let data = self.items.map(Some).collect();
+        let data: SourceValues = self.items.map(Some).collect();
         let mut state =
             ExpressionExecutionState::new(&data, &mut opcount, self.max_operation_count);
         let result = self.expression.resolve(&mut state)?;
@@ -97,7 +97,7 @@ where
         use std::collections::HashMap;
 
         let mut opcount = 0;
-        let data = self.items.map(Some).collect();
+        let data: SourceValues = self.items.map(Some).collect();
         let mut state =
             ExpressionExecutionState::new(&data, &mut opcount, self.max_operation_count);
         let mut completions = HashMap::new();
@@ -652,6 +652,8 @@ mod tests {
         assert_eq!(r, Type::null());
     }
 
+    // Uses `now()` as a key that is unknown at compile time.
+    #[cfg(feature = "time")]
     #[test]
     fn test_selector_types_array_dynamic() {
         let expr = crate::compile_expression(
@@ -743,6 +745,8 @@ mod tests {
         assert_eq!(r, Type::Float);
     }
 
+    // Uses `now()` as a key that is unknown at compile time.
+    #[cfg(feature = "time")]
     #[test]
     fn test_selector_types_object_dynamic() {
         let expr = crate::compile_expression(
@@ -770,4 +774,22 @@ mod tests {
                 .union_with(Type::null())
         );
     }
+
+    #[test]
+    fn test_selector_borrows_input() {
+        let expr = compile_expression("input.a.b[1].c", &["input"]).unwrap();
+        let input = serde_json::json!({ "a": { "b": [{ "c": 1 }, { "c": { "d": 2 } }] } });
+        let r = expr.run([&input]).unwrap();
+        let crate::ResolveResult::Borrowed(r) = r else {
+            panic!("Expected a borrowed result, got {r:?}");
+        };
+        assert!(std::ptr::eq(r, &input["a"]["b"][1]["c"]));
+
+        let expr = compile_expression("input['a'].b[0]", &["input"]).unwrap();
+        let r = expr.run([&input]).unwrap();
+        let crate::ResolveResult::Borrowed(r) = r else {
+            panic!("Expected a borrowed result, got {r:?}");
+        };
+        assert!(std::ptr::eq(r, &input["a"]["b"][0]));
+    }
 }
@@ -23,7 +23,10 @@ impl Display for TransformErrorData {
 /// or by the optimizer.
 ///
 /// These are typically runtime type errors, or other invalid operations.
+///
+/// New variants may be added in minor releases, so matches on this enum need a wildcard arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum TransformError {
     /// The source value does not exist.
     #[error("Source does not exist: {0}")]
@@ -37,6 +40,9 @@ pub enum TransformError {
     /// An invalid operation was performed.
     #[error("{0}")]
     InvalidOperation(TransformErrorData),
+    /// A number was divided by zero, either by division or modulo.
+    #[error("{0}")]
+    DivideByZero(TransformErrorData),
     /// The operation limit was exceeded.
     #[error("Too many operations: the transform expression was terminated because it exceeded the operation limit")]
     OperationLimitExceeded,
@@ -79,6 +85,13 @@ impl TransformError {
         })
     }
 
+    pub(crate) fn new_divide_by_zero(span: &Span) -> Self {
+        Self::DivideByZero(TransformErrorData {
+            desc: "Divide by zero".to_owned(),
+            span: span.clone(),
+        })
+    }
+
     pub(crate) fn new_arith_overflow(span: &Span) -> Self {
         Self::InvalidOperation(TransformErrorData {
             desc: "Arithmetic overflow".to_owned(),
@@ -105,10 +118,23 @@ impl TransformError {
             TransformError::IncorrectTypeInField(x) => Some(x.span.clone()),
             TransformError::ConversionFailed(x) => Some(x.span.clone()),
             TransformError::InvalidOperation(x) => Some(x.span.clone()),
+            TransformError::DivideByZero(x) => Some(x.span.clone()),
             TransformError::OperationLimitExceeded => None,
         }
     }
 
+    /// Get the kind of error, for reporting errors in a structured way.
+    pub fn kind(&self) -> TransformErrorKind {
+        match self {
+            TransformError::SourceMissingError(_) => TransformErrorKind::SourceMissing,
+            TransformError::IncorrectTypeInField(_) => TransformErrorKind::IncorrectType,
+            TransformError::ConversionFailed(_) => TransformErrorKind::ConversionFailed,
+            TransformError::InvalidOperation(_) => TransformErrorKind::InvalidOperation,
+            TransformError::DivideByZero(_) => TransformErrorKind::DivideByZero,
+            TransformError::OperationLimitExceeded => TransformErrorKind::OperationLimitExceeded,
+        }
+    }
+
     /// Get a human-readable message describing the error.
     pub fn message(&self) -> String {
         match self {
@@ -124,9 +150,44 @@ impl TransformError {
             TransformError::InvalidOperation(transform_error_data) => {
                 transform_error_data.desc.clone()
             }
+            TransformError::DivideByZero(transform_error_data) => transform_error_data.desc.clone(),
             TransformError::OperationLimitExceeded => {
                 "Too many operations: the transform expression was terminated because it exceeded the operation limit".to_string()
             }
         }
     }
 }
+
+/// The kind of a [TransformError], without any associated data.
+///
+/// New kinds may be added in minor releases, so matches on this enum need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TransformErrorKind {
+    /// The source value does not exist.
+    SourceMissing,
+    /// A field had an incorrect type.
+    IncorrectType,
+    /// A conversion between types failed.
+    ConversionFailed,
+    /// An invalid operation was performed.
+    InvalidOperation,
+    /// A number was divided by zero.
+    DivideByZero,
+    /// The operation limit was exceeded.
+    OperationLimitExceeded,
+}
+
+impl TransformErrorKind {
+    /// Get a stable, `snake_case` name for this kind of error.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransformErrorKind::SourceMissing => "source_missing",
+            TransformErrorKind::IncorrectType => "incorrect_type",
+            TransformErrorKind::ConversionFailed => "conversion_failed",
+            TransformErrorKind::InvalidOperation => "invalid_operation",
+            TransformErrorKind::DivideByZero => "divide_by_zero",
+            TransformErrorKind::OperationLimitExceeded => "operation_limit_exceeded",
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt::Display;
+
+use logos::Span;
+use serde_json::{json, Value};
+
+use crate::{
+    compiler::BuildError,
+    types::{Object, Type},
+    ExpressionType, TransformError,
+};
+
+use super::{Expression, ExpressionMeta};
+
+/// An expression that evaluates to a fallback if its inner expression fails,
+/// `try inner catch err => fallback`.
+///
+/// If the fallback is a lambda, it is called with an object describing the error,
+/// `{ "message": ..., "span": { "start": ..., "end": ... } }`.
+/// Exceeding the operation limit is not caught.
+#[derive(Debug)]
+pub struct TryExpression {
+    inner: Box<ExpressionType>,
+    handler: Box<ExpressionType>,
+    #[allow(unused)]
+    span: Span,
+}
+
+impl Display for TryExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "try {} catch {}", self.inner, self.handler)
+    }
+}
+
+fn error_object(err: &TransformError) -> Value {
+    let span = err
+        .span()
+        .map(|s| json!({ "start": s.start, "end": s.end }))
+        .unwrap_or_default();
+    json!({ "message": err.message(), "span": span })
+}
+
+fn error_object_type() -> Type {
+    Type::Object(
+        Object::default()
+            .with_field("message", Type::String)
+            .with_field(
+                "span",
+                Type::Object(
+                    Object::default()
+                        .with_field("start", Type::Integer)
+                        .with_field("end", Type::Integer),
+                ),
+            ),
+    )
+}
+
+impl Expression for TryExpression {
+    fn resolve<'a>(
+        &'a self,
+        state: &mut super::ExpressionExecutionState<'a, '_>,
+    ) -> Result<super::ResolveResult<'a>, TransformError> {
+        state.inc_op()?;
+        match self.inner.resolve(state) {
+            Ok(r) => Ok(r),
+            // Missing sources only happen during optimization, and mean that the
+            // expression cannot be evaluated yet, not that it failed.
+            Err(e @ TransformError::OperationLimitExceeded)
+            | Err(e @ TransformError::SourceMissingError(_)) => Err(e),
+            Err(e) => self.handler.call(state, &[&error_object(&e)]),
+        }
+    }
+
+    fn resolve_types(
+        &self,
+        state: &mut crate::types::TypeExecutionState<'_, '_>,
+    ) -> Result<Type, crate::types::TypeError> {
+        let handler = self.handler.call_types(state, &[&error_object_type()])?;
+        // If the inner expression is known to fail, the result is always the fallback.
+        match self.inner.resolve_types(state) {
+            Ok(inner) => Ok(inner.union_with(handler)),
+            Err(_) => Ok(handler),
+        }
+    }
+}
+
+impl TryExpression {
+    pub fn new(
+        inner: ExpressionType,
+        handler: ExpressionType,
+        span: Span,
+    ) -> Result<Self, BuildError> {
+        inner.fail_if_lambda()?;
+        if let ExpressionType::Lambda(lambda) = &handler {
+            if lambda.input_names.len() != 1 {
+                return Err(BuildError::n_function_args(
+                    lambda.span.clone(),
+                    "The fallback of a try expression takes a function with one argument",
+                ));
+            }
+        }
+        Ok(Self {
+            inner: Box::new(inner),
+            handler: Box::new(handler),
+            span,
+        })
+    }
+}
+
+impl ExpressionMeta for TryExpression {
+    fn iter_children_mut(&mut self) -> Box<dyn Iterator<Item = &mut ExpressionType> + '_> {
+        Box::new([self.inner.as_mut(), self.handler.as_mut()].into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{compile_expression, types::Type, TransformError};
+
+    #[test]
+    fn test_try_catch() {
+        let expr = compile_expression("input.map(x => try float(x) catch err => null)", &["input"])
+            .unwrap();
+        let input = json!(["1.5", "garbage", 3]);
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(res.as_ref(), &json!([1.5, null, 3.0]));
+
+        // The fallback may also be a plain expression.
+        let expr = compile_expression("try int(input) catch -1", &["input"]).unwrap();
+        let input = json!("abc");
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!(-1));
+        let input = json!("12");
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!(12));
+    }
+
+    #[test]
+    fn test_try_error_object() {
+        let expr = compile_expression("try float(input) catch err => err", &["input"]).unwrap();
+        let input = json!("garbage");
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!({
+                "message": "Failed to convert string garbage to float: invalid float literal",
+                "span": { "start": 4, "end": 16 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_constant() {
+        // Constant expressions that fail are caught when the optimizer folds them.
+        let expr = compile_expression(r#"try float("abc") catch err => err.message"#, &[]).unwrap();
+        assert_eq!(
+            expr.to_string(),
+            r#""Failed to convert string abc to float: invalid float literal""#
+        );
+
+        let expr = compile_expression(r#"try float("abc") catch input"#, &["input"]).unwrap();
+        let input = json!(5);
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!(5));
+    }
+
+    #[test]
+    fn test_try_errors() {
+        // Errors in the fallback are not caught.
+        let expr =
+            compile_expression("try float(input) catch err => int(input)", &["input"]).unwrap();
+        let input = json!("abc");
+        expr.run([&input]).unwrap_err();
+
+        // Nor is the operation limit.
+        let expr = compile_expression("try input.map(x => x + 1) catch 0", &["input"]).unwrap();
+        let input = json!([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        let err = expr.run_limited([&input], 5).unwrap_err();
+        assert!(matches!(err, TransformError::OperationLimitExceeded));
+
+        assert!(compile_expression("try 1 catch (a, b) => a", &[]).is_err());
+    }
+
+    #[test]
+    fn test_try_catch_as_names() {
+        // `try` and `catch` are only keywords where they can be part of a try expression.
+        let expr = compile_expression("[input.try, input.catch]", &["input"]).unwrap();
+        let input = json!({ "try": 1, "catch": 2 });
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!([1, 2]));
+
+        let expr = compile_expression("input.map(x => x.catch)", &["input"]).unwrap();
+        let input = json!([{ "catch": 1 }, { "catch": 2 }]);
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!([1, 2]));
+
+        let expr = compile_expression("catch + 1", &["catch"]).unwrap();
+        assert_eq!(expr.run([&json!(1)]).unwrap().as_ref(), &json!(2));
+
+        let expr = compile_expression("input.map(catch => try int(catch.try) catch 0)", &["input"])
+            .unwrap();
+        let input = json!([{ "try": "1" }, { "try": "a" }]);
+        assert_eq!(expr.run([&input]).unwrap().as_ref(), &json!([1, 0]));
+    }
+
+    #[test]
+    fn test_try_types() {
+        let expr =
+            compile_expression("try int(input) catch err => err.message", &["input"]).unwrap();
+        let res = expr.run_types([Type::String]).unwrap();
+        assert_eq!(res, Type::Integer.union_with(Type::String));
+    }
+}
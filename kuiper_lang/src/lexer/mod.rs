@@ -55,6 +55,7 @@ impl Display for LexerError {
 pub struct Lexer<T: Iterator<Item = (Result<Token, LexerError>, Span)>> {
     token_stream: Peekable<T>,
     inner: Vec<TemplateExpansionState>,
+    last_token: Option<Token>,
 }
 
 enum TemplateIterState {
@@ -236,6 +237,7 @@ impl<T: Iterator<Item = (Result<Token, LexerError>, Span)>> Lexer<T> {
         Self {
             token_stream: stream.peekable(),
             inner: Vec::new(),
+            last_token: None,
         }
     }
 
@@ -277,6 +279,17 @@ impl<T: Iterator<Item = (Result<Token, LexerError>, Span)>> Iterator for Lexer<T
     type Item = Spanned<Token, usize, LexerError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let tok = self.next_combined()?;
+        Some(tok.map(|(start, token, end)| {
+            let token = token.contextual(self.last_token.as_ref());
+            self.last_token = Some(token.clone());
+            (start, token, end)
+        }))
+    }
+}
+
+impl<T: Iterator<Item = (Result<Token, LexerError>, Span)>> Lexer<T> {
+    fn next_combined(&mut self) -> Option<Spanned<Token, usize, LexerError>> {
         // Unpleasant hack to get around LR(1) and a bug in Logos.
         // Keep a token stored, and if we encounter ) =>, combine the two tokens.
 
@@ -143,6 +143,14 @@ pub enum Token {
     #[token("else")]
     Else,
 
+    /// The `try` keyword for expressions that recover from errors.
+    #[token("try")]
+    Try,
+
+    /// The `catch` keyword, followed by the fallback of a `try` expression.
+    #[token("catch")]
+    Catch,
+
     /// A bare string, which is either part of a selector, or a function call.
     #[regex(r#"\p{XID_Start}\p{XID_Continue}*"#, |s| s.slice().to_string(), priority = 2)]
     #[regex(r#"[$@_a-zA-Z][_0-9a-zA-Z]*"#, |s| s.slice().to_string(), priority = 3)]
@@ -186,6 +194,10 @@ pub enum Token {
     #[token("#")]
     DefineSym,
 
+    /// Question mark used in conditional expressions, `cond ? a : b`.
+    #[token("?")]
+    QuestionMark,
+
     /// Combined arrow used in lambda expressions with multiple arguments.
     CombinedArrow,
 
@@ -202,6 +214,44 @@ pub enum Token {
     TemplateStringEnd,
 }
 
+impl Token {
+    /// Whether this token may be the last token of an expression.
+    fn ends_expression(&self) -> bool {
+        matches!(
+            self,
+            Token::Identifier(_)
+                | Token::Float(_)
+                | Token::Integer(_)
+                | Token::Boolean(_)
+                | Token::String(_)
+                | Token::TypeLiteral(_)
+                | Token::RawTemplateString(_)
+                | Token::TemplateStringEnd
+                | Token::CloseParenthesis
+                | Token::CloseBracket
+                | Token::CloseBrace
+                | Token::Comment
+        )
+    }
+
+    /// Turn the `try` and `catch` keywords into plain identifiers where they cannot start
+    /// or continue a try expression, given the token before them.
+    ///
+    /// This lets them be used as selectors, like `input.try`, and lets `catch` be used as
+    /// a variable. `catch` is only a keyword directly after an expression.
+    pub fn contextual(self, previous: Option<&Token>) -> Token {
+        match self {
+            Token::Try if matches!(previous, Some(Token::Period)) => {
+                Token::Identifier("try".to_owned())
+            }
+            Token::Catch if !previous.is_some_and(|p| p.ends_expression()) => {
+                Token::Identifier("catch".to_owned())
+            }
+            t => t,
+        }
+    }
+}
+
 impl Display for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -229,9 +279,12 @@ impl Display for Token {
             Token::Not => write!(f, "not"),
             Token::If => write!(f, "if"),
             Token::Else => write!(f, "else"),
+            Token::Try => write!(f, "try"),
+            Token::Catch => write!(f, "catch"),
             Token::SemiColon => write!(f, ";"),
             Token::DefineEqual => write!(f, ":="),
             Token::DefineSym => write!(f, "#"),
+            Token::QuestionMark => write!(f, "?"),
             Token::RawTemplateString(v) => write!(f, "$\"{v}\""),
             Token::TemplateStringStart => write!(f, "$\"{{"),
             Token::TemplateStringSegment(v) => write!(f, "}}{v}{{"),
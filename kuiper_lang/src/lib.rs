@@ -32,13 +32,19 @@
 #![warn(missing_docs)]
 
 mod compiler;
+/// Documentation for the built-in functions, generated from `kuiper_documentation/functions.yaml`.
+#[cfg(feature = "docs")]
+pub mod docs;
 mod expressions;
 mod lexer;
 mod parse;
 mod pretty;
 pub mod types;
 
-pub use pretty::{format_expression, PrettyError};
+pub use pretty::{
+    format_expression, format_expression_range, format_expression_range_with_options,
+    format_expression_with_options, FormatOptions, PrettyError,
+};
 
 /// A constant null value, which can be handy when implementing SourceData, as a fallback
 /// if a key is not found.
@@ -137,14 +143,14 @@ impl CompileError {
 
 pub use compiler::{
     compile_expression, compile_expression_with_config, BuildError, CompilerConfig, DebugInfo,
-    ExpressionDebugInfo,
+    ExpressionDebugInfo, TypeCheckerMode,
 };
 #[cfg(feature = "completions")]
 pub use expressions::Completions;
 pub use expressions::{
     DynamicFunctionBuilder, Expression, ExpressionExecutionState, ExpressionMeta,
     ExpressionRunBuilder, ExpressionType, JsonNumber, ResolveResult, TransformError,
-    TransformErrorData,
+    TransformErrorData, TransformErrorKind,
 };
 pub use lexer::ParseError;
 pub use logos::Span;
@@ -233,7 +239,7 @@ pub(crate) mod tests {
     use crate::{
         compile_expression, compile_expression_with_config, compiler::BuildError,
         format_expression, lex::Token, CompileError, CompilerConfig, ExpressionDebugInfo,
-        TransformError,
+        TransformError, TransformErrorKind,
     };
 
     pub(crate) fn compile_err(data: &str, inputs: &[&str]) -> CompileError {
@@ -288,8 +294,10 @@ pub(crate) mod tests {
     pub fn test_divide_by_zero() {
         let expr = compile_expression("10 / input.val", &["input"]).unwrap();
         let res = expr.run([&json!({ "val": 0 })]).unwrap_err();
+        assert_eq!(res.kind(), TransformErrorKind::DivideByZero);
+        assert_eq!(res.kind().name(), "divide_by_zero");
         match res {
-            TransformError::InvalidOperation(d) => {
+            TransformError::DivideByZero(d) => {
                 assert_eq!(d.desc, "Divide by zero");
                 assert_eq!(d.span, Span { start: 3, end: 4 });
             }
@@ -638,6 +646,40 @@ pub(crate) mod tests {
         assert!(res_obj.get("v7").unwrap().as_bool().unwrap());
     }
 
+    #[test]
+    pub fn test_conditional_operator() {
+        let expr = compile_expression(
+            r#"{
+            "v1": input.a > 3 ? "big" : "small",
+            "v2": input.missing ? 1 : 2,
+            "v3": false || input.a ? 1 : 2,
+            "v4": input.a > 10 ? 1 : input.a > 4 ? 2 : 3,
+            "v5": input.a > 3 ? input.a > 4 ? "a" : "b" : "c",
+            "v6": [1, 2].map(x => x == 1 ? "one" : "other"),
+            "v7": { input.a == 5 ? "k1" : "k2": 1 }
+        }"#,
+            &["input"],
+        )
+        .unwrap();
+        let input = json!({ "a": 5 });
+        let res = expr.run([&input]).unwrap();
+        assert_eq!(
+            res.as_ref(),
+            &json!({
+                "v1": "big",
+                "v2": 2,
+                "v3": 1,
+                "v4": 2,
+                "v5": "a",
+                "v6": ["one", "other"],
+                "v7": { "k1": 1 }
+            })
+        );
+
+        compile_err("1 ? 2", &[]);
+        compile_err("1 ? 2 : ", &[]);
+    }
+
     #[cfg(feature = "completions")]
     #[test]
     pub fn test_completions() {
@@ -821,6 +863,23 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn test_deny_function() {
+        let config = CompilerConfig::new().deny_function("now");
+        let err = compile_expression_with_config("[1, now()]", &[], &config).unwrap_err();
+
+        match err {
+            CompileError::Build(BuildError::Other(d)) => {
+                assert_eq!(d.detail, "Function now is not allowed");
+                assert_eq!(d.position, Span { start: 4, end: 9 });
+            }
+            _ => panic!("Wrong type of error {err:?}"),
+        }
+
+        // Macros with the same name as a denied function are still allowed.
+        compile_expression_with_config("#now := () => 1; now()", &[], &config).unwrap();
+    }
+
     #[test]
     fn test_compile_from_tokens() {
         use crate::lex::compile_from_tokens;
@@ -4,7 +4,7 @@ use logos::Span;
 use serde_json::{Number, Value};
 
 use crate::{
-    expressions::{Operator, TypeLiteral, UnaryOperator},
+    expressions::{Operator, TypePattern, UnaryOperator},
     write_list,
 };
 
@@ -100,7 +100,7 @@ pub struct OpExpression {
 #[derive(Debug, Clone)]
 pub struct IsExpression {
     pub lhs: Box<Expression>,
-    pub rhs: TypeLiteral,
+    pub rhs: TypePattern,
     pub not: bool,
 }
 
@@ -189,6 +189,11 @@ pub enum Expression {
         loc: Span,
     },
     TemplateString(TemplateString),
+    Try {
+        inner: Box<Expression>,
+        handler: Box<FunctionParameter>,
+        loc: Span,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -292,6 +297,11 @@ impl Display for Expression {
                 }
                 write!(f, "\"")
             }
+            Expression::Try {
+                inner,
+                handler,
+                loc: _,
+            } => write!(f, "try {inner} catch {handler}"),
         }
     }
 }
@@ -31,13 +31,13 @@ pub(super) struct Formatter<'a, T: Iterator<Item = (usize, Span)>> {
     tokens_on_line: usize,
     /// An iterator over the spans of lines in the input.
     lines: Peekable<T>,
+    /// The number of spaces added for each level of indentation.
+    indent_size: usize,
 }
 
-const INDENT_SIZE: usize = 4;
-
 impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
     /// Create a new formatter.
-    pub(super) fn new(input: &'a str, lines: Peekable<T>) -> Self {
+    pub(super) fn new(input: &'a str, lines: Peekable<T>, indent_size: usize) -> Self {
         Self {
             input,
             stack: vec![IndentNode {
@@ -45,6 +45,7 @@ impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
                 line: 0,
                 caused_indent: false,
                 has_postfix_chain: false,
+                open_conditionals: 0,
             }],
             output: String::new(),
             indent: 0,
@@ -54,6 +55,7 @@ impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
             last_token: None,
             tokens_on_line: 0,
             lines,
+            indent_size,
         }
     }
 
@@ -82,15 +84,22 @@ impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
     }
 
     fn process_token(&mut self, token: Token, token_span: Span) -> Result<(), PrettyError> {
+        let token = token.contextual(self.last_token.as_ref());
         let current_line = self.advance_to_line_for_token(&token_span)?;
         self.update_indent_from_token(&token, &token_span, current_line)?;
 
         // Push any whitespace between the last token and the current one.
-        self.output.push_str(&trim_inter_token_whitespace(
-            &self.input[self.last_end..token_span.start],
-            self.last_token.as_ref(),
-            Some(&token),
-        ));
+        let whitespace = &self.input[self.last_end..token_span.start];
+        if self.is_conditional_colon(&token) && !whitespace.contains('\n') {
+            // Unlike colons in objects, the colon in `cond ? a : b` is preceded by a space.
+            self.output.push(' ');
+        } else {
+            self.output.push_str(&trim_inter_token_whitespace(
+                whitespace,
+                self.last_token.as_ref(),
+                Some(&token),
+            ));
+        }
 
         // Check if we need to indent the output for a postfix chain.
         self.update_postfix_indent(&token, self.tokens_on_line == 1);
@@ -121,6 +130,27 @@ impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
         Ok(())
     }
 
+    /// Keep track of conditional expressions, returning `true` if `token` is the colon of one.
+    ///
+    /// A colon belongs to a conditional expression if there is a question mark without a matching
+    /// colon at the same nesting level, otherwise it separates a key and value in an object.
+    fn is_conditional_colon(&mut self, token: &Token) -> bool {
+        let Some(node) = self.stack.last_mut() else {
+            return false;
+        };
+        match token {
+            Token::QuestionMark => {
+                node.open_conditionals += 1;
+                false
+            }
+            Token::Colon if node.open_conditionals > 0 => {
+                node.open_conditionals -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Advance the formatter to the line which contains the token given by `token_span`.
     fn advance_to_line_for_token(&mut self, token_span: &Span) -> Result<usize, PrettyError> {
         loop {
@@ -144,7 +174,7 @@ impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
             self.lines.next();
             self.tokens_on_line = 0;
             if self.indent_on_line > 0 {
-                self.indent += INDENT_SIZE;
+                self.indent += self.indent_size;
                 self.indent_on_line = 0;
             }
         }
@@ -169,6 +199,7 @@ impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
                 line: current_line,
                 caused_indent: true,
                 has_postfix_chain: false,
+                open_conditionals: 0,
             });
             self.indent_on_line += 1;
         }
@@ -180,10 +211,10 @@ impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
             } else {
                 // Else, we need to reduce the indent level, if the original node caused an indent.
                 if node.caused_indent {
-                    self.indent -= INDENT_SIZE;
+                    self.indent -= self.indent_size;
                 }
                 if node.has_postfix_chain {
-                    self.postfix_indent -= INDENT_SIZE;
+                    self.postfix_indent -= self.indent_size;
                 }
             }
         }
@@ -215,15 +246,20 @@ impl<'a, T: Iterator<Item = (usize, Span)>> Formatter<'a, T> {
                 if let Some(n) = self.stack.last_mut() {
                     if !n.has_postfix_chain {
                         n.has_postfix_chain = true;
-                        self.postfix_indent += INDENT_SIZE;
+                        self.postfix_indent += self.indent_size;
                     }
                 }
             }
-            Token::Operator(_) | Token::Colon | Token::SemiColon | Token::Comma => {
+            Token::Operator(_)
+            | Token::Colon
+            | Token::SemiColon
+            | Token::Comma
+            | Token::QuestionMark
+            | Token::Catch => {
                 if let Some(n) = self.stack.last_mut() {
                     if n.has_postfix_chain {
                         n.has_postfix_chain = false;
-                        self.postfix_indent -= INDENT_SIZE;
+                        self.postfix_indent -= self.indent_size;
                     }
                 }
             }
@@ -1,4 +1,7 @@
+use std::ops::Range;
+
 use formatter::Formatter;
+use logos::Span;
 use utils::iter_line_spans;
 
 pub use utils::PrettyError;
@@ -14,6 +17,30 @@ mod utils;
 /// Note that this is just a best-effort formatter, designed to be quite conservative, but
 /// apply proper indentation and spacing to the expression.
 pub fn format_expression(input: &str) -> Result<String, PrettyError> {
+    format_expression_with_options(input, &FormatOptions::default())
+}
+
+/// Options for the formatter.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// The number of spaces used for each level of indentation. Defaults to 4.
+    pub indent_size: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { indent_size: 4 }
+    }
+}
+
+/// Format a kuiper expression into a pretty printed string, using the given options.
+/// Returns an error if the input is not a valid kuiper expression.
+///
+/// See [format_expression] for details.
+pub fn format_expression_with_options(
+    input: &str,
+    options: &FormatOptions,
+) -> Result<String, PrettyError> {
     // This is probably not the perfect way to do this, but it works well enough.
     // We do a few separate things in a single pass:
     // 1. Re-indent based on certain tokens. Parentheses, brackets, braces, and postfix selector chains.
@@ -24,7 +51,12 @@ pub fn format_expression(input: &str) -> Result<String, PrettyError> {
     // or produce terrible results.
     let parse_res = crate::parse::ProgramParser::new().parse(crate::lexer::Lexer::new(input))?;
 
-    let res = Formatter::new(input, iter_line_spans(input).enumerate().peekable()).run()?;
+    let res = Formatter::new(
+        input,
+        iter_line_spans(input).enumerate().peekable(),
+        options.indent_size,
+    )
+    .run()?;
 
     let after_format_parse = crate::parse::ProgramParser::new()
         .parse(crate::lexer::Lexer::new(&res))
@@ -37,6 +69,56 @@ pub fn format_expression(input: &str) -> Result<String, PrettyError> {
     Ok(res)
 }
 
+/// Format only the lines of a kuiper expression that overlap `range`, which is a byte range into `input`.
+/// Returns an error if the input is not a valid kuiper expression.
+///
+/// Returns the span of `input` that should be replaced, which covers all the lines overlapping `range`,
+/// along with the formatted replacement for those lines. The entire expression is still used to
+/// determine indentation, so the result is the same as formatting the whole expression,
+/// and keeping only the selected lines.
+pub fn format_expression_range(
+    input: &str,
+    range: Range<usize>,
+) -> Result<(Span, String), PrettyError> {
+    format_expression_range_with_options(input, range, &FormatOptions::default())
+}
+
+/// Format only the lines of a kuiper expression that overlap `range`, using the given options.
+/// Returns an error if the input is not a valid kuiper expression.
+///
+/// See [format_expression_range] for details.
+pub fn format_expression_range_with_options(
+    input: &str,
+    range: Range<usize>,
+    options: &FormatOptions,
+) -> Result<(Span, String), PrettyError> {
+    let formatted = format_expression_with_options(input, options)?;
+
+    let input_lines: Vec<_> = iter_line_spans(input).collect();
+    let formatted_lines: Vec<_> = iter_line_spans(&formatted).collect();
+    // The formatter never adds or removes newlines, so lines in the input map directly to lines in the output.
+    if input_lines.len() != formatted_lines.len() {
+        return Err(PrettyError::Pretty(
+            "Formatting changed the number of lines in the expression".to_string(),
+            range,
+        ));
+    }
+
+    let overlaps = |line: &Span| {
+        line.start <= range.start && line.end > range.start
+            || line.start >= range.start && line.start < range.end
+    };
+    let Some(first) = input_lines.iter().position(overlaps) else {
+        return Ok((input.len()..input.len(), String::new()));
+    };
+    let last = input_lines.iter().rposition(overlaps).unwrap_or(first);
+
+    Ok((
+        input_lines[first].start..input_lines[last].end,
+        formatted[formatted_lines[first].start..formatted_lines[last].end].to_string(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     fn test_pretty_print(input: &str, expected: &str) {
@@ -444,6 +526,38 @@ input.MessagePayload.SourceType, ":", input.MessagePayload.SourceName, ":", inpu
         );
     }
 
+    #[test]
+    fn test_pretty_printing_type_patterns() {
+        test_pretty_print(
+            r#"input is {"a":[ int ],"b" : {"c":string}}"#,
+            r#"input is { "a": [int], "b": { "c": string } }"#,
+        );
+        test_pretty_print(
+            r#"if input is {"a":int}{1}else{2}"#,
+            r#"if input is { "a": int } { 1 } else { 2 }"#,
+        );
+    }
+
+    #[test]
+    fn test_pretty_printing_conditional() {
+        test_pretty_print(
+            r#"{"a":input?1:2,"b":[x?{"c":1}:null],"c":x?y?1:2:3}"#,
+            r#"{ "a": input ? 1 : 2, "b": [x ? { "c": 1 } : null], "c": x ? y ? 1 : 2 : 3 }"#,
+        );
+    }
+
+    #[test]
+    fn test_pretty_printing_try() {
+        test_pretty_print(
+            r#"input.map(x=>try   float(x)catch err=>{"error":err.message})"#,
+            r#"input.map(x => try float(x) catch err => { "error": err.message })"#,
+        );
+        test_pretty_print(
+            r#"[input.try,input.catch.x,input.map((catch)=>catch+1)]"#,
+            r#"[input.try, input.catch.x, input.map((catch) => catch + 1)]"#,
+        );
+    }
+
     #[test]
     fn test_pretty_printing_defines() {
         test_pretty_print(
@@ -461,4 +575,66 @@ foo+ 1
 "#,
         );
     }
+
+    #[test]
+    fn test_format_indent_size() {
+        let input = r#"{
+"a": [
+1,
+input.map(x=>x)
+.filter(x=>x)
+]
+}"#;
+        let options = super::FormatOptions { indent_size: 2 };
+        let result = super::format_expression_with_options(input, &options).unwrap();
+        assert_eq!(
+            result,
+            r#"{
+  "a": [
+    1,
+    input.map(x => x)
+      .filter(x => x)
+  ]
+}"#
+        );
+    }
+
+    #[test]
+    fn test_format_range() {
+        let input = r#"{
+"a": 1+1,
+"b":   [1,2],
+"c": input.map(x=>x*2)
+}"#;
+        // Select part of the second line.
+        let (span, formatted) = super::format_expression_range(input, 3..6).unwrap();
+        assert_eq!(&input[span.clone()], "\"a\": 1+1,\n");
+        assert_eq!(formatted, "    \"a\": 1 + 1,\n");
+
+        // Select across the third and fourth lines.
+        let start = input.find("[1").unwrap();
+        let end = input.find("map").unwrap();
+        let (span, formatted) = super::format_expression_range(input, start..end).unwrap();
+        assert_eq!(
+            &input[span.clone()],
+            "\"b\":   [1,2],\n\"c\": input.map(x=>x*2)\n"
+        );
+        assert_eq!(
+            formatted,
+            "    \"b\": [1, 2],\n    \"c\": input.map(x => x * 2)\n"
+        );
+
+        // Empty range at the start of the last line, without a trailing newline.
+        let (span, formatted) =
+            super::format_expression_range(input, input.len() - 1..input.len() - 1).unwrap();
+        assert_eq!(span, input.len() - 1..input.len());
+        assert_eq!(formatted, "}");
+
+        // Custom indentation is applied to the selected lines.
+        let options = super::FormatOptions { indent_size: 2 };
+        let (span, formatted) =
+            super::format_expression_range_with_options(input, 3..6, &options).unwrap();
+        assert_eq!(&input[span.clone()], "\"a\": 1+1,\n");
+        assert_eq!(formatted, "  \"a\": 1 + 1,\n");
+    }
 }
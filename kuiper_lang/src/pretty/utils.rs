@@ -30,6 +30,8 @@ pub(super) struct IndentNode {
     pub(super) line: usize,
     pub(super) caused_indent: bool,
     pub(super) has_postfix_chain: bool,
+    /// The number of conditional expressions, `cond ? a : b`, in this node that have not yet seen their colon.
+    pub(super) open_conditionals: usize,
 }
 
 pub(super) fn raw_token(input: &str, span: Span) -> &str {
@@ -117,8 +119,11 @@ pub(super) fn trim_inter_token_whitespace(
         // Some special tokens are always followed by a space.
         (Some(Token::DefineEqual), _) | (_, Some(Token::DefineEqual)) => 1, // Define equal is always followed by and preceeded by a space.
         (Some(Token::Arrow), _) | (_, Some(Token::Arrow)) => 1, // Arrow is always followed by and preceeded by a space.
-        (Some(Token::If), _) => 1,                              // If is always followed by a space.
+        (Some(Token::QuestionMark), _) | (_, Some(Token::QuestionMark)) => 1, // Question mark is always followed by and preceeded by a space.
+        (Some(Token::If), _) => 1, // If is always followed by a space.
         (Some(Token::Else), _) | (_, Some(Token::Else)) => 1, // Else is always followed by and preceeded by a space.
+        (Some(Token::Try), _) => 1,                           // Try is always followed by a space.
+        (Some(Token::Catch), _) | (_, Some(Token::Catch)) => 1, // Catch is always followed by and preceeded by a space.
         (_, Some(Token::Comment)) | (Some(Token::Comment), _) => 1, // Comments are always preceded by a space.
         (Some(Token::Colon), _) => 1, // Colon is always followed by a space.
         (Some(Token::Not), _) => 1, // Not is always followed by a space. Since the only valid token before this is `is`, it will
@@ -134,7 +139,8 @@ pub(super) fn trim_inter_token_whitespace(
                 | Token::Identifier(_)
                 | Token::String(_)
                 | Token::TypeLiteral(_)
-                | Token::RawTemplateString(_),
+                | Token::RawTemplateString(_)
+                | Token::CloseBrace,
             ),
             Some(Token::OpenBrace),
         ) => 1,
@@ -183,7 +189,7 @@ pub(super) fn prettify_comment(comment: &str) -> String {
 }
 
 pub(super) fn prettify_template_string(input: &str) -> Result<String, PrettyError> {
-    let formatter = Formatter::new(input, iter_line_spans(input).enumerate().peekable());
+    let formatter = Formatter::new(input, iter_line_spans(input).enumerate().peekable(), 4);
     let inner = &input[2..(input.len() - 1)];
     let inner_parser = TemplateExpansionState::parse(inner, 2)?;
     let r = formatter.run_with_tokens(inner_parser.map(|v| match v {
@@ -288,6 +294,7 @@ b - 1
                     line: 0,
                     caused_indent: false,
                     has_postfix_chain: false,
+                    open_conditionals: 0,
                 });
             } else if let Some(node) = check_closing_token(&mut stack, &tok, &span).unwrap() {
                 removed.push(node);
@@ -12,6 +12,8 @@ use thiserror::Error;
 
 mod array;
 mod object;
+mod schema;
+mod typescript;
 
 pub use array::Array;
 pub use object::{Object, ObjectField};
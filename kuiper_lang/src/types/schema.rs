@@ -0,0 +1,208 @@
+use serde_json::{Map, Value};
+
+use crate::types::{Array, Object, ObjectField, Type};
+
+impl Type {
+    /// Create a type from a JSON schema.
+    ///
+    /// This supports a subset of JSON schema: `type`, `const`, `enum`, `anyOf`, `oneOf`,
+    /// `properties`, `required`, `additionalProperties`, `items`, `prefixItems`, and the
+    /// OpenAPI `nullable` keyword. Anything else is treated as `Any`.
+    ///
+    /// Since missing fields evaluate to `null` in Kuiper, properties that are not
+    /// required are nullable.
+    pub fn from_json_schema(schema: &Value) -> Type {
+        let schema = match schema {
+            Value::Bool(false) => return Type::never(),
+            Value::Object(schema) => schema,
+            _ => return Type::Any,
+        };
+
+        if let Some(value) = schema.get("const") {
+            return Type::Constant(value.clone());
+        }
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            return values.iter().fold(Type::never(), |res, value| {
+                res.union_with(Type::Constant(value.clone()))
+            });
+        }
+        for key in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(options)) = schema.get(key) {
+                return options.iter().fold(Type::never(), |res, option| {
+                    res.union_with(Type::from_json_schema(option))
+                });
+            }
+        }
+
+        let res = match schema.get("type") {
+            Some(Value::String(name)) => Self::from_json_schema_type(name, schema),
+            Some(Value::Array(names)) => names
+                .iter()
+                .filter_map(|name| name.as_str())
+                .fold(Type::never(), |res, name| {
+                    res.union_with(Self::from_json_schema_type(name, schema))
+                }),
+            _ => Type::Any,
+        };
+
+        if schema.get("nullable") == Some(&Value::Bool(true)) {
+            res.nullable()
+        } else {
+            res
+        }
+    }
+
+    fn from_json_schema_type(name: &str, schema: &Map<String, Value>) -> Type {
+        match name {
+            "string" => Type::String,
+            "integer" => Type::Integer,
+            "number" => Type::number(),
+            "boolean" => Type::Boolean,
+            "null" => Type::null(),
+            "array" => {
+                // Older drafts use `items` as an array for tuples, with `additionalItems` for the rest.
+                let (prefix, rest) = match schema.get("items") {
+                    Some(Value::Array(items)) => (Some(items), schema.get("additionalItems")),
+                    items => (schema.get("prefixItems").and_then(|p| p.as_array()), items),
+                };
+                let elements = prefix
+                    .map(|items| items.iter().map(Type::from_json_schema).collect())
+                    .unwrap_or_default();
+                let end_dynamic = match rest {
+                    Some(Value::Bool(false)) => None,
+                    Some(rest) => Some(Box::new(Type::from_json_schema(rest))),
+                    None => Some(Box::new(Type::Any)),
+                };
+                Type::Array(Array {
+                    elements,
+                    end_dynamic,
+                })
+            }
+            "object" => {
+                let required: Vec<&str> = schema
+                    .get("required")
+                    .and_then(|r| r.as_array())
+                    .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+                    .unwrap_or_default();
+
+                let mut object = Object::default();
+                if let Some(Value::Object(properties)) = schema.get("properties") {
+                    for (key, property) in properties {
+                        let ty = Type::from_json_schema(property);
+                        let ty = if required.contains(&key.as_str()) {
+                            ty
+                        } else {
+                            ty.nullable()
+                        };
+                        object.push_field(ObjectField::Constant(key.clone()), ty);
+                    }
+                }
+                match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => (),
+                    Some(additional) => {
+                        object.push_field(ObjectField::Generic, Type::from_json_schema(additional))
+                    }
+                    None => object.push_field(ObjectField::Generic, Type::Any),
+                }
+                Type::Object(object)
+            }
+            _ => Type::Any,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::types::{Array, Object, Type};
+
+    #[test]
+    fn test_primitive_schemas() {
+        assert_eq!(
+            Type::from_json_schema(&json!({ "type": "string" })),
+            Type::String
+        );
+        assert_eq!(
+            Type::from_json_schema(&json!({ "type": "number" })),
+            Type::number()
+        );
+        assert_eq!(
+            Type::from_json_schema(&json!({ "type": ["integer", "null"] })),
+            Type::Integer.nullable()
+        );
+        assert_eq!(
+            Type::from_json_schema(&json!({ "type": "boolean", "nullable": true })),
+            Type::Boolean.nullable()
+        );
+        assert_eq!(Type::from_json_schema(&json!({})), Type::Any);
+        assert_eq!(Type::from_json_schema(&json!(true)), Type::Any);
+        assert_eq!(Type::from_json_schema(&json!(false)), Type::never());
+    }
+
+    #[test]
+    fn test_const_and_enum_schemas() {
+        assert_eq!(
+            Type::from_json_schema(&json!({ "const": "foo" })),
+            Type::from_const("foo")
+        );
+        assert_eq!(
+            Type::from_json_schema(&json!({ "enum": ["a", 1] })),
+            Type::from_const("a").union_with(Type::from_const(1))
+        );
+        assert_eq!(
+            Type::from_json_schema(&json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })),
+            Type::String.nullable()
+        );
+    }
+
+    #[test]
+    fn test_object_schema() {
+        let ty = Type::from_json_schema(&json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "integer" },
+                "name": { "type": "string" }
+            },
+            "required": ["id"],
+            "additionalProperties": false
+        }));
+        assert_eq!(
+            ty,
+            Type::Object(
+                Object::default()
+                    .with_field("id", Type::Integer)
+                    .with_field("name", Type::String.nullable())
+            )
+        );
+
+        let ty = Type::from_json_schema(&json!({
+            "type": "object",
+            "additionalProperties": { "type": "string" }
+        }));
+        assert_eq!(ty, Type::object_of_type(Type::String));
+    }
+
+    #[test]
+    fn test_array_schema() {
+        assert_eq!(
+            Type::from_json_schema(&json!({ "type": "array", "items": { "type": "string" } })),
+            Type::array_of_type(Type::String)
+        );
+        assert_eq!(
+            Type::from_json_schema(&json!({
+                "type": "array",
+                "prefixItems": [{ "type": "string" }, { "type": "integer" }],
+                "items": false
+            })),
+            Type::Array(Array {
+                elements: vec![Type::String, Type::Integer],
+                end_dynamic: None,
+            })
+        );
+        assert_eq!(
+            Type::from_json_schema(&json!({ "type": "array" })),
+            Type::any_array()
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use itertools::Itertools;
+
+use crate::types::{Array, Object, ObjectField, Type};
+
+impl Type {
+    /// Produce a TypeScript type expression for this type.
+    ///
+    /// Integers and floats are both represented as `number`, and `Any` is represented as `unknown`.
+    pub fn to_typescript(&self) -> String {
+        match self {
+            // JSON literals are also valid TypeScript literal types.
+            Type::Constant(value) => value.to_string(),
+            Type::Object(o) => o.to_typescript(),
+            Type::Array(a) => a.to_typescript(),
+            Type::String => "string".to_string(),
+            Type::Integer | Type::Float => "number".to_string(),
+            Type::Boolean => "boolean".to_string(),
+            Type::Union(types) if types.is_empty() => "never".to_string(),
+            Type::Union(types) => types.iter().map(|t| t.to_typescript()).unique().join(" | "),
+            Type::Any => "unknown".to_string(),
+        }
+    }
+
+    /// Produce a TypeScript type, wrapped in parentheses if it is a union.
+    fn to_typescript_element(&self) -> String {
+        let res = self.to_typescript();
+        if res.contains(" | ") {
+            format!("({res})")
+        } else {
+            res
+        }
+    }
+}
+
+impl Array {
+    /// Produce a TypeScript type expression for this array type.
+    pub fn to_typescript(&self) -> String {
+        match (self.elements.is_empty(), &self.end_dynamic) {
+            (true, Some(end)) => format!("{}[]", end.to_typescript_element()),
+            (_, end) => {
+                let elements = self
+                    .elements
+                    .iter()
+                    .map(|e| e.to_typescript())
+                    .chain(
+                        end.iter()
+                            .map(|e| format!("...{}[]", e.to_typescript_element())),
+                    )
+                    .join(", ");
+                format!("[{elements}]")
+            }
+        }
+    }
+}
+
+impl Object {
+    /// Produce a TypeScript type expression for this object type.
+    pub fn to_typescript(&self) -> String {
+        if self.fields.is_empty() {
+            return "Record<string, never>".to_string();
+        }
+
+        let mut fields = Vec::new();
+        for (key, value) in &self.fields {
+            match key {
+                ObjectField::Constant(name) => fields.push(format!(
+                    "{}: {}",
+                    serde_json::Value::String(name.clone()),
+                    value.to_typescript()
+                )),
+                // In TypeScript the index signature must include the types of all known fields.
+                ObjectField::Generic => fields.push(format!(
+                    "[key: string]: {}",
+                    self.element_union().to_typescript()
+                )),
+            }
+        }
+        format!("{{ {} }}", fields.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::types::{Array, Object, Type};
+
+    #[test]
+    fn test_primitive_typescript() {
+        assert_eq!(Type::String.to_typescript(), "string");
+        assert_eq!(Type::number().to_typescript(), "number");
+        assert_eq!(Type::Integer.nullable().to_typescript(), "number | null");
+        assert_eq!(Type::from_const("foo").to_typescript(), "\"foo\"");
+        assert_eq!(Type::never().to_typescript(), "never");
+        assert_eq!(Type::Any.to_typescript(), "unknown");
+        assert_eq!(
+            Type::from_const(json!({ "a": [1, true] })).to_typescript(),
+            "{\"a\":[1,true]}"
+        );
+    }
+
+    #[test]
+    fn test_array_typescript() {
+        assert_eq!(
+            Type::array_of_type(Type::String.nullable()).to_typescript(),
+            "(string | null)[]"
+        );
+        assert_eq!(
+            Type::Array(Array {
+                elements: vec![Type::String, Type::Boolean],
+                end_dynamic: None,
+            })
+            .to_typescript(),
+            "[string, boolean]"
+        );
+        assert_eq!(
+            Type::Array(Array {
+                elements: vec![Type::String],
+                end_dynamic: Some(Box::new(Type::Integer)),
+            })
+            .to_typescript(),
+            "[string, ...number[]]"
+        );
+    }
+
+    #[test]
+    fn test_object_typescript() {
+        assert_eq!(
+            Type::Object(
+                Object::default()
+                    .with_field("id", Type::Integer)
+                    .with_field("name", Type::String.nullable())
+            )
+            .to_typescript(),
+            "{ \"id\": number; \"name\": string | null }"
+        );
+        assert_eq!(
+            Type::Object(
+                Object::default()
+                    .with_field("id", Type::Integer)
+                    .with_generic_field(Type::Boolean)
+            )
+            .to_typescript(),
+            "{ \"id\": number; [key: string]: number | boolean }"
+        );
+        assert_eq!(
+            Type::Object(Object::default()).to_typescript(),
+            "Record<string, never>"
+        );
+    }
+}
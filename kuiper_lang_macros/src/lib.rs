@@ -84,8 +84,10 @@ pub fn pass_through_derive(d: TokenStream) -> TokenStream {
                 }
                 let itemident = Ident::new("a", Span::call_site());
                 let path = &variant.ident;
+                // Variants may be feature gated, so the match arms must be as well.
+                let cfgs = variant.attrs.iter().filter(|a| a.path().is_ident("cfg"));
                 arms.extend(
-                    quote! { #name::#path(a) => #itemident.#funcname(#(#mapped_funcargs),*), },
+                    quote! { #(#cfgs)* #name::#path(a) => #itemident.#funcname(#(#mapped_funcargs),*), },
                 );
             }
 
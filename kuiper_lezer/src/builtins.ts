@@ -14,6 +14,9 @@ export const builtIns: KuiperInput[] = [
     { label: "asin", description: "`asin(x)`: Return the inverse sine of `x` in radians between -pi/2 and pi/2." },
     { label: "atan", description: "`atan(x)`: Return the inverse tangent of `x` in radians between -pi/2 and pi/2." },
     { label: "atan2", description: "`atan2(x, y)`: Return the inverse tangent of `x`/`y` in radians between -pi and pi." },
+    { label: "avg", description: "`avg(x)`: Return the mean of the numbers in the array `x`, as a float. Returns `null` if `x` is empty." },
+    { label: "binary_search", description: "`binary_search(x, a)`: Return the index of `a` in the sorted array `x`, or -1 if `x` does not contain `a`. This is faster than" },
+    { label: "cartesian_product", description: "`cartesian_product(x, y)`: Return every combination of an element from the list `x` with an element from the list `y`," },
     { label: "case", description: "`case(x, c1, r1, c2, r2, ..., (default))`: Compare `x` to each of `c1`, `c2`, etc. and return the matching `r1`, `r2` of the first match. If no entry matches, a final optional expression can be returned as default." },
     { label: "ceil", description: "`ceil(x)`: Return `x` rounded up to the nearest integer." },
     { label: "chars", description: "`chars(x)`: Create an array of characters from a string." },
@@ -22,29 +25,53 @@ export const builtIns: KuiperInput[] = [
     { label: "concat", description: "`concat(x, y, ...)`: Concatenate any number of strings." },
     { label: "contains", description: "`contains(x, a)`: Return `true` if the array or string `x` contains item `a`." },
     { label: "cos", description: "`cos(x)`: Return the cosine of `x`, where `x` is in radians." },
+    { label: "crc32", description: "`crc32(x)`: Compute the CRC-32 checksum of `x`, as an integer." },
     { label: "digest", description: "`digest(a, b, ...)`: Compute the SHA256 hash of the list of values." },
+    { label: "distinct", description: "`distinct(x)`: Return the list `x` with duplicate elements removed, keeping the first occurrence of each. Elements are compared by value, so objects with the same fields are equal regardless of field order." },
     { label: "distinct_by", description: "`distinct_by(x, (a(, b)) => ...)`: Return a list or object where the elements are distinct by the returned value of the given lambda function. The lambda function either takes list values, or object (value, key) pairs." },
     { label: "ends_with", description: "`ends_with(item, substring)`: Return `true` if `item` ends with `substring`." },
     { label: "except", description: "`except(x, (v(, k)) => ...)` or `except(x, l)`: Return a list or object where keys or entries matching the predicate have been removed." },
     { label: "exp", description: "`exp(x)`: Return e to the power of `x`." },
-    { label: "filter", description: "`filter(x, it => ...)`: Remove any item from the list `x` where the lambda function returns `false` or `null`." },
-    { label: "flatmap", description: "`flatmap(x, it => ...)`: Apply the lambda function to every item in the list `x` and flatten the result." },
+    { label: "filter", description: "`filter(x, (it(, index)) => ...)`: Remove any item from the list `x` where the lambda function returns `false` or `null`. The lambda takes an optional second input which is the index of the item in the list." },
+    { label: "flatmap", description: "`flatmap(x, (it(, index)) => ...)`: Apply the lambda function to every item in the list `x` and flatten the result. The lambda takes an optional second input which is the index of the item in the list." },
+    { label: "flatten_object", description: "`flatten_object(x(, separator(, arrays)))`: Flatten the nested object `x` into an object with a single level, where each key is the path to a value joined by `separator`, which defaults to `.`." },
+    { label: "fletcher16", description: "`fletcher16(x)`: Compute the Fletcher-16 checksum of `x`, as an integer." },
     { label: "float", description: "`float(x)`: Convert `x` into a floating point number if possible. If the conversion fails, the whole mapping will fail." },
     { label: "floor", description: "`floor(x)`: Return `x` rounded down to the nearest integer." },
     { label: "format_timestamp", description: "`format_timestamp(x, f)`: Convert the Unix timestamp `x` into a string representation based on the format `f`." },
+    { label: "geo_distance", description: "`geo_distance(lat1, lon1, lat2, lon2)`: Return the great-circle distance in meters between the points (`lat1`, `lon1`) and (`lat2`, `lon2`)," },
+    { label: "get", description: "`get(x, path(, default))`: Get the value at `path` in `x`, where `path` is a string like `a.b[0].c`. Use this when the path is not known until runtime, for example when it comes from the input data. Keys containing `.` or `[` can be quoted, as in `a['b.c']`, and negative indices count from the end of an array." },
+    { label: "group_by", description: "`group_by(x, (it(, index)) => ...)`: Group the elements of the list `x` into an object, where the keys are the values returned by the lambda function, and the values are lists of the elements with that key, in their original order." },
+    { label: "hex_decode", description: "`hex_decode(x)`: Decode the hexadecimal string `x`, with an optional `0x` prefix, into a string." },
+    { label: "hex_encode", description: "`hex_encode(x)`: Encode the UTF-8 bytes of the string `x` as lowercase hexadecimal." },
     { label: "if", description: "`if(x, y, (z))`: Return `y` if `x` evaluates to `true`, otherwise return `z`, or `null` if `z` is omitted." },
     { label: "if_value", description: "`if_value(item, item => ...)`: Map a value using a lambda if the value is not null. This is useful if you need to combine parts of some complex object or result of a longer calculation." },
+    { label: "index_of", description: "`index_of(x, a)`: Return the index of the first occurrence of `a` in the array `x`, or -1 if `x` does not contain `a`." },
     { label: "int", description: "`int(x)`: Convert `x` into an integer if possible. If the conversion fails, the whole mapping will fail." },
+    { label: "int_from_hex", description: "`int_from_hex(x)`: Parse the hexadecimal string `x`, with an optional `0x` prefix and sign, as an integer." },
+    { label: "ip_in_cidr", description: "`ip_in_cidr(ip, cidr)`: Return `true` if the IP address `ip` is in the CIDR range `cidr`, for example `10.0.0.0/8`." },
+    { label: "ip_to_int", description: "`ip_to_int(ip)`: Convert the IPv4 address `ip` to an integer. IPv6 addresses are only supported if they are IPv4-mapped," },
     { label: "join", description: "`join(a, b, ...)`: Return the union of the given objects or arrays. If a key is present in multiple objects, each instance of the key is overwritten by later objects. Arrays are simply merged." },
+    { label: "json_diff", description: "`json_diff(a, b)`: Compare `a` and `b`, and return a JSON patch, as defined in RFC 6902, that transforms `a` into `b`. The patch is a list of `add`, `remove`, and `replace` operations, and is empty if `a` and `b` are equal." },
+    { label: "json_patch", description: "`json_patch(x, patch)`: Apply the JSON patch `patch`, as defined in RFC 6902, to `x`. `patch` is a list of operations, which may be `add`, `remove`, `replace`, `move`, `copy`, or `test`." },
+    { label: "json_pointer", description: "`json_pointer(x, pointer(, default))`: Get the value in `x` referenced by the JSON pointer `pointer`, as defined in RFC 6901. A pointer is either empty, referencing `x` itself, or a list of keys and array indices each prefixed by `/`, where `~1` is an escaped `/` and `~0` is an escaped `~`." },
     { label: "length", description: "`length(x)`: Return the length of the list, string, or object `x`." },
+    { label: "levenshtein", description: "`levenshtein(a, b)`: Return the Levenshtein distance between the strings `a` and `b`, which is the number of single character insertions, deletions, or substitutions needed to turn `a` into `b`. This takes time proportional to the product of the string lengths, and each pair of characters compared counts towards the operation limit." },
     { label: "log", description: "`log(x, y)`: Return the base `y` logarithm of `x`." },
     { label: "lower", description: "`lower(x)`: Convert all characters in the string `x` to lowercase. If `x` is a boolean or number, it will be converted to a string." },
     { label: "map", description: "`map(x, (it(, index)) => ...)`: Apply the lambda function to every item in the list `x`. The lambda takes an optional second input which is the index of the item in the list." },
+    { label: "mask", description: "`mask(x, paths, (replacement))`: Replace the fields in `x` matching any of `paths` with `replacement`, which defaults to `***`. `paths` is a single path or a list of paths," },
     { label: "max", description: "`max(a, b, ...)`: Return the larger of the given numbers. Can also be used on an array." },
+    { label: "max_by", description: "`max_by(x, (it(, index)) => ...)`: Return the element of the list `x` for which the lambda function returns the largest value, or `null` if the list is empty." },
+    { label: "median", description: "`median(x)`: Return the median of the numbers in the array `x`. If `x` has an odd number of elements, the middle element is returned as is," },
     { label: "min", description: "`min(a, b, ...)`: Return the smaller of the given numbers. Can also be used on an array." },
+    { label: "min_by", description: "`min_by(x, (it(, index)) => ...)`: Return the element of the list `x` for which the lambda function returns the smallest value, or `null` if the list is empty." },
     { label: "now", description: "`now()`: Return the current time as a millisecond Unix timestamp, that is, the number of milliseconds since midnight 1/1/1970 UTC." },
     { label: "pairs", description: "`pairs(x)`: Convert the object `x` into a list of key/value pairs." },
+    { label: "parse_ip", description: "`parse_ip(x)`: Parse the IPv4 or IPv6 address `x`. Returns an object with the fields `address`, the normalized address," },
     { label: "parse_json", description: "`parse_json(string)`: Parse a string as a JSON object, which can be used in further transformations. If the passed value isn't a string, it's returned as-is." },
+    { label: "parse_number", description: "`parse_number(x, (options))`: Parse the string `x` as a number, using the separators given in `options`. Fails if `x` cannot be parsed." },
+    { label: "percentile", description: "`percentile(x, p)`: Return the `p`th percentile of the numbers in the array `x`, where `p` is between 0 and 100." },
     { label: "pow", description: "`pow(x, y)`: Return `x` to the power of `y`." },
     { label: "random", description: "`random()`: Return a random floating-point number between 0.0 (inclusive) and 1.0 (exclusive)." },
     { label: "reduce", description: "`reduce(x, (acc, val) => ..., init)`: Return the value obtained by reducing the list `x`. The lambda function is called once for each element in the list `val`, and the returned value is passed as `acc` in the next iteration. The `init` will be given as the initial `acc` for the first call to the lambda function." },
@@ -55,28 +82,44 @@ export const builtIns: KuiperInput[] = [
     { label: "regex_is_match", description: "`regex_is_match(haystack, regex)`: Return `true` if the haystack matches the regex. Prefer this over the other regex methods if you only need to check for the presence of a match." },
     { label: "regex_replace", description: "`regex_replace(haystack, regex, replace)`: Replace the first occurrence of the regex in the haystack. The replace object supports referencing capture groups using either the index (`$1`) or the name (`$group`). Use `$$` if you need a literal `$` symbol. `${group}` is equivalent to `$group` but lets you specify the group name exactly." },
     { label: "regex_replace_all", description: "`regex_replace_all(haystack, regex, replace)`: Replace each occurrence of the regex in the haystack. See [regex_replace](#regex_replace) for details." },
+    { label: "regex_split", description: "`regex_split(haystack, regex)`: Split the haystack on each match of the regex, returning an array of strings. Use [split](#split) to split on a plain string." },
+    { label: "repeat", description: "`repeat(x, n)`: Return the string `x` repeated `n` times. Fails if the result would be longer than 16 MiB." },
     { label: "replace", description: "`replace(a, b, c)`: Replace occurrences of `b` in string `a` with `c`." },
+    { label: "resample", description: "`resample(x, interval, (method))`: Resample the datapoints in `x`, a list of objects with `timestamp` and `value` fields, to a regular grid with" },
     { label: "round", description: "`round(x)`: Return `x` rounded to the nearest integer." },
     { label: "select", description: "`select(x, (v(, k)) => ...)` or `select(x, [1, 2, 3])`: Return a list or object where the lambda returns true. If the second argument is a list, the list values or object keys found in that list are used to select from the source." },
+    { label: "semver_compare", description: "`semver_compare(a, b)`: Compare the semantic versions `a` and `b`, returning -1 if `a` is lower than `b`, 0 if they are equal, and 1 if `a` is greater." },
+    { label: "semver_satisfies", description: "`semver_satisfies(version, requirement)`: Return `true` if the semantic version `version` matches `requirement`, which is a comma separated list" },
+    { label: "similarity", description: "`similarity(a, b)`: Return how similar the strings `a` and `b` are, as a number between `0.0` and `1.0`, where `1.0` means they are equal. This is the Levenshtein distance between `a` and `b` divided by the length of the longest string, subtracted from `1.0`." },
     { label: "sin", description: "`sin(x)`: Return the sine of `x`, where `x` is in radians." },
     { label: "slice", description: "`slice(x, start(, end))`: Create a sub-array from an array `x` from `start` to `end`. If `end` is not specified, go from `start` to the end of the array. If `start` or `end` are negative, count from the end of the array." },
-    { label: "split", description: "`split(a, b)`: Split string `a` on any occurrences of `b`. If `b` is an empty string, this will split on each character, including before the first and after the last." },
+    { label: "sort", description: "`sort(x(, descending))`: Sort the list `x` in ascending order, or descending order if `descending` is `true`." },
+    { label: "sort_by", description: "`sort_by(x, (it(, index)) => ...(, descending))`: Sort the list `x` by the value returned by the lambda function for each element, in ascending order, or descending order if `descending` is `true`." },
+    { label: "sort_with", description: "`sort_with(x, (a, b) => ...)`: Sort the list `x` using a comparator function. The function is called with two elements `a` and `b`, and should return" },
+    { label: "split", description: "`split(a, b)`: Split string `a` on any occurrences of `b`. If `b` is an empty string, this will split on each character, including before the first and after the last. Use [regex_split](#regex_split) to split on a regex." },
     { label: "sqrt", description: "`sqrt(x)`: Return the square root of `x`." },
     { label: "starts_with", description: "`starts_with(item, substring)`: Return `true` if `item` starts with `substring`." },
+    { label: "stddev", description: "`stddev(x)`: Return the population standard deviation of the numbers in the array `x`. Returns `null` if `x` is empty." },
     { label: "string", description: "`string(x)`: Convert `x` into a string." },
     { label: "string_join", description: "`string_join(x(, a))`: Return a string with all the elements of `x`, separated by `a`. If `a` is omitted, the strings will be joined without any separator." },
     { label: "substring", description: "`substring(x, start(, end))`: Create a substring of an input string `x` from `start` to `end`. If `end` is not specified, go from `start` to end of string. If `start` or `end` are negative, count from the end of the string." },
     { label: "sum", description: "`sum(x)`: Sum the numbers in the array `x`." },
     { label: "tail", description: "`tail(x(, n))`: Take the last element of the list `x`. If `n` is given, takes the last `n` elements, and returns a list if `n` > 1." },
     { label: "tan", description: "`tan(x)`: Return the tangent of `x`, where `x` is in radians." },
+    { label: "to_json_pointer", description: "`to_json_pointer(x)`: Build a JSON pointer from the list of keys and indices `x`, escaping `~` and `/` in each key." },
     { label: "to_object", description: "`to_object(x, val => ...(, val => ...))`: Convert the array `x` into an object by producing the key and value from two lambdas." },
     { label: "to_unix_timestamp", description: "`to_unix_timestamp(x, f)`: Convert the string `x` into a millisecond Unix timestamp using the format string `f`." },
     { label: "translate", description: "`translate(x, from, to)`: Replace characters in the string `x` found in the string `from` with the corresponding character in the string `to`. If `to` and `from` are of different lengths, the expression will fail." },
+    { label: "transpose", description: "`transpose(x)`: Transpose the list of lists `x`, turning rows into columns. If the lists have different lengths," },
     { label: "trim_whitespace", description: "`trim_whitespace(x)`: Remove any whitespace from the start and end of `x`." },
     { label: "try_bool", description: "`try_bool(a, b)`: Try to convert `a` to a boolean; if it fails, return `b`." },
     { label: "try_float", description: "`try_float(a, b)`: Try to convert `a` to a float; if it fails, return `b`." },
     { label: "try_int", description: "`try_int(a, b)`: Try to convert `a` to an int; if it fails, return `b`." },
+    { label: "try_parse_number", description: "`try_parse_number(x, default, (options))`: Try to parse `x` as a number, like [parse_number](#parse_number); if it fails, return `default`." },
+    { label: "unflatten_object", description: "`unflatten_object(x(, separator(, arrays)))`: Convert the flat object `x` into a nested object by splitting each key on `separator`, which defaults to `.`. This is the inverse of `flatten_object`." },
     { label: "upper", description: "`upper(x)`: Convert all characters in the string `x` to uppercase. If `x` is a boolean or number, it will be converted to a string first." },
     { label: "uuid4", description: "`uuid4()`: Generate a random UUID (version 4) and return it as a string." },
+    { label: "variance", description: "`variance(x)`: Return the population variance of the numbers in the array `x`. Returns `null` if `x` is empty." },
+    { label: "window", description: "`window(x, n)`: Return every overlapping window of `n` consecutive elements in the list `x`. If `x` has fewer than `n` elements, the result is empty." },
     { label: "zip", description: "`zip(x, y, ..., (i1, i2, ...) => ...)`: Take a number of arrays, call the given lambda function on each entry, and return a single array from the result of each call. The returned array will be as long as the longest argument, null will be given for the shorter input arrays when they run out." },
 ];
@@ -30,10 +30,12 @@ export const kuiperLanguage = LRLanguage.define({
             "NotModifier": tags.compareOperator,
             "ArithOp/...": tags.arithmeticOperator,
             "LogicOp/...": tags.logicOperator,
+            "CondOp/...": tags.controlOperator,
             "Arrow": tags.function(tags.punctuation),
             "Type/...": tags.typeName,
             "If": tags.keyword,
             "Else": tags.keyword,
+            "Try Catch": tags.controlKeyword,
         }), indentNodeProp.add({
             Object: delimitedIndent({ closing: "}" }),
             Array: delimitedIndent({ closing: "]" }),
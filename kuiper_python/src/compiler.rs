@@ -1,39 +1,115 @@
 use crate::{
-    exceptions::raise_kuiper_error,
+    exceptions::compile_error,
     expressions::KuiperExpression,
-    python_json::{ConversionError, PythonJson},
+    python_json::{python_to_value, value_to_python, ConversionError},
 };
 use kuiper_lang::{
     compile_expression_with_config, functions::DynamicFunction, CompilerConfig,
     DynamicFunctionBuilder, Expression, ExpressionExecutionState, ExpressionMeta, ExpressionType,
-    ResolveResult, Span, TransformError,
+    ResolveResult, Span, TransformError, TypeCheckerMode,
+};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyValueError},
+    pyclass, pyfunction, pymethods,
+    types::PyTuple,
+    Py, PyAny, PyResult, Python,
+};
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
 };
-use pyo3::{pyclass, pyfunction, pymethods, types::PyTuple, Py, PyAny, PyResult, Python};
-use std::{fmt::Display, sync::Arc};
 
 /// A custom function that can be used in Kuiper expressions.
 ///
 /// Custom functions allows you to extend the Kuiper language with your own
 /// functions.
 ///
+/// Custom functions are never evaluated by the optimizer, so expressions calling
+/// them are not constant folded, even if all the arguments are constant.
+///
 /// Args:
-///     name:    Name of the function as it will appear in Kuiper
-///     target:  Python callable that implements the function
+///     name:      Name of the function as it will appear in Kuiper
+///     target:    Python callable that implements the function
+///     min_args:  Minimum number of arguments the function accepts
+///     max_args:  Maximum number of arguments the function accepts, or None for no limit
 #[derive(Debug)]
 #[pyclass(module = "kuiper", frozen)]
 pub struct CustomFunction {
     name: String,
     target: Py<PyAny>,
+    min_args: usize,
+    max_args: Option<usize>,
 }
 
 #[pymethods]
 impl CustomFunction {
     #[new]
-    fn new(name: String, target: Py<PyAny>) -> Self {
-        CustomFunction { name, target }
+    #[pyo3(signature = (name, target, min_args=0, max_args=None))]
+    fn new(name: String, target: Py<PyAny>, min_args: usize, max_args: Option<usize>) -> Self {
+        CustomFunction {
+            name,
+            target,
+            min_args,
+            max_args,
+        }
     }
 }
 
+impl CustomFunction {
+    /// Get a human-readable description of the number of arguments this function takes,
+    /// matching the error messages for built-in functions.
+    fn num_args_desc(&self) -> String {
+        match self.max_args {
+            Some(x) if x == self.min_args => {
+                format!("function {} takes {} arguments", self.name, x)
+            }
+            Some(x) => format!(
+                "function {} takes {} to {} arguments",
+                self.name, self.min_args, x
+            ),
+            None => format!(
+                "function {} takes at least {} arguments",
+                self.name, self.min_args
+            ),
+        }
+    }
+}
+
+/// Functions registered with `register_function`, which are available in all expressions
+/// compiled afterwards.
+static REGISTERED_FUNCTIONS: Mutex<Vec<Py<CustomFunction>>> = Mutex::new(Vec::new());
+
+/// Register a Python callable as a function available in all expressions compiled
+/// after this call.
+///
+/// Registered functions are never evaluated by the optimizer, so expressions calling
+/// them are not constant folded, even if all the arguments are constant. Functions
+/// passed to `compile_expression` through `custom_functions` take precedence over
+/// registered functions with the same name.
+///
+/// Args:
+///     name:      Name of the function as it will appear in Kuiper
+///     target:    Python callable that implements the function
+///     min_args:  Minimum number of arguments the function accepts
+///     max_args:  Maximum number of arguments the function accepts, or None for no limit
+#[pyfunction]
+#[pyo3(signature = (name, target, min_args=0, max_args=None))]
+pub fn register_function(
+    py: Python<'_>,
+    name: String,
+    target: Py<PyAny>,
+    min_args: usize,
+    max_args: Option<usize>,
+) -> PyResult<()> {
+    let function = Py::new(py, CustomFunction::new(name, target, min_args, max_args))?;
+    let mut registered = REGISTERED_FUNCTIONS
+        .lock()
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    registered.retain(|f| f.get().name != function.get().name);
+    registered.push(function);
+    Ok(())
+}
+
 fn build_custom_functions(
     mut config: CompilerConfig,
     custom_functions: Vec<Py<CustomFunction>>,
@@ -64,31 +140,19 @@ fn build_custom_functions(
                     .collect::<Result<Vec<_>, _>>()?;
 
                 let result = Python::attach(|py| {
-                    let args =
-                        PyTuple::new(
-                            py,
-                            args.into_iter()
-                                .map(|arg| {
-                                    Ok(PythonJson::from_value(arg.into_owned().take())?
-                                        .into_python(py))
-                                })
-                                .collect::<Result<Vec<_>, ConversionError>>()?,
-                        )?;
-
-                    Ok(self
-                        .function
-                        .get()
-                        .target
-                        .call(py, args, None)
-                        .and_then(|res| res.extract::<PythonJson>(py))?)
+                    let args = PyTuple::new(
+                        py,
+                        args.iter()
+                            .map(|arg| value_to_python(py, arg))
+                            .collect::<Result<Vec<_>, ConversionError>>()?,
+                    )?;
+
+                    let result = self.function.get().target.call(py, args, None)?;
+                    python_to_value(result.bind(py))
                 })
                 .map_err(|err: ConversionError| err.into_transform_error(&self.span))?;
 
-                Ok(ResolveResult::Owned(
-                    result
-                        .into_value()
-                        .map_err(|e| e.into_transform_error(&self.span))?,
-                ))
+                Ok(ResolveResult::Owned(result))
             }
 
             fn is_deterministic(&self) -> bool {
@@ -112,6 +176,15 @@ fn build_custom_functions(
                 args: Vec<ExpressionType>,
                 span: Span,
             ) -> Result<Box<dyn DynamicFunction>, kuiper_lang::BuildError> {
+                let function = self.function.get();
+                if args.len() < function.min_args
+                    || function.max_args.is_some_and(|max| args.len() > max)
+                {
+                    return Err(kuiper_lang::BuildError::n_function_args(
+                        span,
+                        &function.num_args_desc(),
+                    ));
+                }
                 Ok(Box::new(Custom {
                     function: self.function.clone(),
                     args,
@@ -143,25 +216,59 @@ fn build_custom_functions(
 ///                                 optimization.
 ///     max_macro_expansions:       Maximum number of macro expansions allowed.
 ///     custom_functions:           Optional list of custom functions to include.
+///     type_checker:               When to run the type checker, one of "early"
+///                                 (before optimization), "late" (after
+///                                 optimization), or "off".
+///     denied_functions:           Optional list of functions that expressions
+///                                 are not allowed to call.
 ///
 /// Returns:
 ///     A `KuiperExpression` object representing the compiled expression.
 ///
 /// Raises:
 ///     KuiperCompileError: If the compilation encounters an error.
+///     ValueError:         If `type_checker` is not a valid mode.
 #[pyfunction]
 #[pyo3(name = "compile_expression")]
-#[pyo3(signature = (expression, inputs, optimizer_operation_limit=100_000, max_macro_expansions=20, custom_functions=None))]
+#[pyo3(signature = (expression, inputs, optimizer_operation_limit=100_000, max_macro_expansions=20, custom_functions=None, type_checker="off", denied_functions=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn compile_expression_py(
+    py: Python<'_>,
     expression: String,
     inputs: Vec<String>,
     optimizer_operation_limit: i64,
     max_macro_expansions: i32,
     custom_functions: Option<Vec<Py<CustomFunction>>>,
+    type_checker: &str,
+    denied_functions: Option<Vec<String>>,
 ) -> PyResult<KuiperExpression> {
+    let type_checker = match type_checker {
+        "early" => TypeCheckerMode::Early,
+        "late" => TypeCheckerMode::Late,
+        "off" => TypeCheckerMode::Off,
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "Invalid type checker mode {other}, expected one of \"early\", \"late\", or \"off\""
+            )))
+        }
+    };
+
     let mut config = CompilerConfig::new()
         .optimizer_operation_limit(optimizer_operation_limit)
-        .max_macro_expansions(max_macro_expansions);
+        .max_macro_expansions(max_macro_expansions)
+        .type_checker_mode(type_checker);
+
+    let registered_functions = REGISTERED_FUNCTIONS
+        .lock()
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?
+        .iter()
+        .map(|f| f.clone_ref(py))
+        .collect();
+    config = build_custom_functions(config, registered_functions);
+
+    for function in denied_functions.unwrap_or_default() {
+        config = config.deny_function(function);
+    }
 
     if let Some(custom_functions) = custom_functions {
         config = build_custom_functions(config, custom_functions);
@@ -173,11 +280,6 @@ pub fn compile_expression_py(
         &config,
     ) {
         Ok(expression) => Ok(KuiperExpression::new(expression)),
-        Err(compile_error) => Err(raise_kuiper_error(
-            "KuiperCompileError",
-            compile_error.to_string(),
-            compile_error.span().map(|s| s.start),
-            compile_error.span().map(|s| s.end),
-        )),
+        Err(error) => Err(compile_error(error)),
     }
 }
@@ -0,0 +1,63 @@
+use kuiper_lang::{docs::FUNCTIONS, FormatOptions};
+use pyo3::{pyfunction, Bound, PyAny, PyResult, Python};
+
+use crate::{
+    exceptions::format_error,
+    python_json::{value_to_python, ConversionError},
+};
+
+/// Format a Kuiper expression.
+///
+/// This uses the same formatter as the Kuiper CLI. It fixes indentation and spacing,
+/// but never adds or removes line breaks.
+///
+/// Args:
+///     expression:   The Kuiper expression to format.
+///     indent_size:  Number of spaces used for each level of indentation.
+///
+/// Returns:
+///     The formatted expression.
+///
+/// Raises:
+///     KuiperCompileError: If the expression could not be parsed.
+#[pyfunction]
+#[pyo3(signature = (expression, indent_size=4))]
+pub fn format_expression(expression: &str, indent_size: usize) -> PyResult<String> {
+    kuiper_lang::format_expression_with_options(expression, &FormatOptions { indent_size })
+        .map_err(format_error)
+}
+
+/// Get the documentation for a built-in function.
+///
+/// Args:
+///     name:  Name of the function.
+///
+/// Returns:
+///     A dictionary with the `name`, `signature`, `description`, and `examples` of the
+///     function, or None if there is no built-in function with the given name.
+#[pyfunction]
+pub fn get_method_docs<'py>(py: Python<'py>, name: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+    FUNCTIONS
+        .iter()
+        .find(|f| f.name == name)
+        .map(|def| to_python(py, def))
+        .transpose()
+}
+
+/// List the documentation for all built-in functions, sorted by name.
+///
+/// Returns:
+///     A list of dictionaries, in the same format as returned by `get_method_docs`.
+#[pyfunction]
+pub fn list_functions(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+    to_python(py, FUNCTIONS)
+}
+
+fn to_python<'py>(
+    py: Python<'py>,
+    value: &(impl serde::Serialize + ?Sized),
+) -> PyResult<Bound<'py, PyAny>> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| ConversionError::PlainMessage(e.to_string()).into_python_error())?;
+    value_to_python(py, &value).map_err(ConversionError::into_python_error)
+}
@@ -1,14 +1,144 @@
-use pyo3::{types::PyAnyMethods, PyErr, Python};
+use kuiper_lang::{CompileError, PrettyError, TransformError};
+use pyo3::{
+    exceptions::PyException, pyclass, pymethods, types::PyAnyMethods, PyClassInitializer, PyErr,
+    PyTypeInfo, Python,
+};
 
-pub fn raise_kuiper_error(
-    error: &str,
+/// Base class for all errors raised by Kuiper.
+///
+/// Attributes:
+///     message:  Description of the error.
+///     start:    Start of the span in the expression where the error occurred, if known.
+///     end:      End of the span in the expression where the error occurred, if known.
+///     kind:     The kind of error, for example "parser", "type", or "divide_by_zero".
+#[pyclass(module = "kuiper", extends = PyException, subclass, frozen)]
+pub struct KuiperError {
+    #[pyo3(get)]
     message: String,
+    #[pyo3(get)]
     start: Option<usize>,
+    #[pyo3(get)]
     end: Option<usize>,
+    #[pyo3(get)]
+    kind: Option<String>,
+}
+
+#[pymethods]
+impl KuiperError {
+    #[new]
+    #[pyo3(signature = (message, start=None, end=None, kind=None))]
+    fn new(
+        message: String,
+        start: Option<usize>,
+        end: Option<usize>,
+        kind: Option<String>,
+    ) -> Self {
+        KuiperError {
+            message,
+            start,
+            end,
+            kind,
+        }
+    }
+
+    fn __str__(&self) -> String {
+        self.message.clone()
+    }
+}
+
+/// Error raised when an expression fails to compile.
+#[pyclass(module = "kuiper", extends = KuiperError, frozen)]
+pub struct KuiperCompileError;
+
+#[pymethods]
+impl KuiperCompileError {
+    #[new]
+    #[pyo3(signature = (message, start=None, end=None, kind=None))]
+    fn new(
+        message: String,
+        start: Option<usize>,
+        end: Option<usize>,
+        kind: Option<String>,
+    ) -> PyClassInitializer<Self> {
+        PyClassInitializer::from(KuiperError::new(message, start, end, kind)).add_subclass(Self)
+    }
+}
+
+/// Error raised when running an expression fails.
+#[pyclass(module = "kuiper", extends = KuiperError, frozen)]
+pub struct KuiperRuntimeError;
+
+#[pymethods]
+impl KuiperRuntimeError {
+    #[new]
+    #[pyo3(signature = (message, start=None, end=None, kind=None))]
+    fn new(
+        message: String,
+        start: Option<usize>,
+        end: Option<usize>,
+        kind: Option<String>,
+    ) -> PyClassInitializer<Self> {
+        PyClassInitializer::from(KuiperError::new(message, start, end, kind)).add_subclass(Self)
+    }
+}
+
+/// Create an exception of type `T` by calling the type object, so that `args` is set like for
+/// exceptions raised from Python.
+fn raise<T: PyTypeInfo>(
+    message: String,
+    start: Option<usize>,
+    end: Option<usize>,
+    kind: Option<String>,
 ) -> PyErr {
-    Python::attach(|py| {
-        let errors = py.import("kuiper").unwrap();
-        let exception = errors.getattr(error).unwrap();
-        PyErr::from_value(exception.call1((message, start, end)).unwrap())
-    })
+    Python::attach(
+        |py| match py.get_type::<T>().call1((message, start, end, kind)) {
+            Ok(exception) => PyErr::from_value(exception),
+            Err(e) => e,
+        },
+    )
+}
+
+/// Create a `KuiperCompileError` from a compile error.
+pub fn compile_error(error: CompileError) -> PyErr {
+    let kind = match &error {
+        CompileError::Build(_) => "build",
+        CompileError::Parser(_) => "parser",
+        CompileError::Optimizer(_) => "optimizer",
+        CompileError::TypeChecker(_) => "type",
+    };
+    raise::<KuiperCompileError>(
+        error.to_string(),
+        error.span().map(|s| s.start),
+        error.span().map(|s| s.end),
+        Some(kind.to_string()),
+    )
+}
+
+/// Create a `KuiperRuntimeError` from a transform error.
+pub fn runtime_error(error: TransformError) -> PyErr {
+    raise::<KuiperRuntimeError>(
+        error.to_string(),
+        error.span().map(|s| s.start),
+        error.span().map(|s| s.end),
+        Some(error.kind().name().to_string()),
+    )
+}
+
+/// Create a `KuiperRuntimeError` from an error parsing JSON input.
+pub fn json_error(error: serde_json::Error) -> PyErr {
+    raise::<KuiperRuntimeError>(
+        error.to_string(),
+        Some(error.column()),
+        Some(error.column()),
+        Some("invalid_json".to_string()),
+    )
+}
+
+/// Create an error from a failure to format an expression. Parser errors are raised as
+/// `KuiperCompileError`, other errors as `KuiperError`.
+pub fn format_error(error: PrettyError) -> PyErr {
+    match error {
+        PrettyError::Parser(e) => compile_error(CompileError::Parser(e)),
+        e => raise::<KuiperError>(e.to_string(), None, None, Some("format".to_string())),
+    }
 }
@@ -1,12 +1,12 @@
 use crate::{
-    exceptions::raise_kuiper_error,
-    python_json::{ConversionError, PythonJson},
+    exceptions::{json_error, runtime_error},
+    python_json::{python_to_value, value_to_python, ConversionError},
 };
 use kuiper_lang::ExpressionType;
 use pyo3::{
     pyclass, pymethods,
-    types::{PyAnyMethods, PyTuple, PyTupleMethods},
-    Py, PyAny, PyResult, Python,
+    types::{PyTuple, PyTupleMethods},
+    Bound, Py, PyAny, PyResult, Python,
 };
 use serde_json::{from_str, Value};
 
@@ -31,18 +31,7 @@ impl KuiperExpression {
             .into_iter()
             .map(from_str)
             .collect::<Result<Vec<_>, _>>();
-        let json = match json {
-            Ok(values) => values,
-            Err(json_error) => {
-                return Err(raise_kuiper_error(
-                    "KuiperRuntimeError",
-                    json_error.to_string(),
-                    Some(json_error.column()),
-                    Some(json_error.column()),
-                ))
-            }
-        };
-        Ok(json)
+        json.map_err(json_error)
     }
 }
 
@@ -73,12 +62,9 @@ impl KuiperExpression {
             inputs
                 .bind(py)
                 .iter()
-                .map(|item| item.extract())
-                .collect::<PyResult<Vec<PythonJson>>>()
-        })?
-        .into_iter()
-        .map(|item| item.into_value())
-        .collect::<Result<Vec<_>, _>>()
+                .map(|item| python_to_value(&item))
+                .collect::<Result<Vec<_>, _>>()
+        })
         .map_err(ConversionError::into_python_error)?;
 
         let run_result = if let Some(op_limit) = max_operations {
@@ -87,21 +73,12 @@ impl KuiperExpression {
             self.expression.run(inputs.iter())
         };
 
-        let run_result = run_result
-            .map_err(|transform_error| {
-                raise_kuiper_error(
-                    "KuiperRuntimeError",
-                    transform_error.to_string(),
-                    transform_error.span().map(|s| s.start),
-                    transform_error.span().map(|s| s.end),
-                )
-            })?
-            .into_owned();
+        let run_result = run_result.map_err(runtime_error)?;
 
         Python::attach(|py| {
-            Ok(PythonJson::from_value(run_result)
+            Ok(value_to_python(py, &run_result)
                 .map_err(ConversionError::into_python_error)?
-                .into_python(py))
+                .unbind())
         })
     }
 
@@ -134,15 +111,97 @@ impl KuiperExpression {
 
         match run_result {
             Ok(result) => Ok(result.to_string()),
-            Err(transform_error) => Err(raise_kuiper_error(
-                "KuiperRuntimeError",
-                transform_error.to_string(),
-                transform_error.span().map(|s| s.start),
-                transform_error.span().map(|s| s.end),
-            )),
+            Err(transform_error) => Err(runtime_error(transform_error)),
         }
     }
 
+    /// Run the expression on many inputs, releasing the GIL while the expression runs.
+    ///
+    /// This is considerably faster than calling `run` in a loop when transforming large
+    /// amounts of data. Each element of `inputs` is the input for one run. If the expression
+    /// takes multiple inputs, each element should be a tuple with one item per input.
+    ///
+    /// Args:
+    ///     inputs:          List of inputs to the expression.
+    ///     parallel:        Whether to run the expression on multiple threads.
+    ///     max_operations:  Maximum number of operations allowed for each run, useful for
+    ///                      limiting the computational resources used by an expression. If
+    ///                      a computation exceeds this limit, a KuiperRuntimeError is raised.
+    ///
+    /// Returns:
+    ///     A list with the result of evaluating the expression on each input.
+    ///
+    /// Raises:
+    ///     KuiperRuntimeError: If the expression evaluation encounters an error for any input.
+    #[pyo3(signature = (inputs, parallel=true, max_operations=None))]
+    fn run_many(
+        &self,
+        py: Python<'_>,
+        inputs: Vec<Bound<'_, PyAny>>,
+        parallel: bool,
+        max_operations: Option<i64>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let rows = inputs
+            .iter()
+            .map(|row| match row.cast::<PyTuple>() {
+                Ok(tuple) => tuple
+                    .iter()
+                    .map(|item| python_to_value(&item))
+                    .collect::<Result<Vec<_>, _>>(),
+                Err(_) => Ok(vec![python_to_value(row)?]),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ConversionError::into_python_error)?;
+
+        let op_limit = max_operations.unwrap_or(-1);
+        let run_chunk = |chunk: &[Vec<Value>]| {
+            chunk
+                .iter()
+                .map(|row| {
+                    self.expression
+                        .run_limited(row.iter(), op_limit)
+                        .map(|r| r.into_owned())
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let results = py.detach(|| {
+            let threads = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            if !parallel || threads <= 1 || rows.len() <= 1 {
+                return run_chunk(&rows);
+            }
+
+            let chunk_size = rows.len().div_ceil(threads);
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = rows
+                    .chunks(chunk_size)
+                    .map(|chunk| scope.spawn(move || run_chunk(chunk)))
+                    .collect();
+                let mut results = Vec::with_capacity(rows.len());
+                for handle in handles {
+                    match handle.join() {
+                        Ok(chunk) => results.extend(chunk?),
+                        Err(panic) => std::panic::resume_unwind(panic),
+                    }
+                }
+                Ok(results)
+            })
+        });
+
+        let results = results.map_err(runtime_error)?;
+
+        results
+            .iter()
+            .map(|result| {
+                value_to_python(py, result)
+                    .map(Bound::unbind)
+                    .map_err(ConversionError::into_python_error)
+            })
+            .collect()
+    }
+
     fn __str__(&self) -> String {
         self.expression.to_string()
     }
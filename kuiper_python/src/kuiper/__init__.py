@@ -11,33 +11,26 @@ Kuiper is a JSON to JSON transform and templating language from Cognite.
 
 The ``compile_expression`` function might raise a ``KuiperCompileError``, and otherwise returns a ``KuiperExpression``
 object. The ``KuiperExpression.run(...)`` method might raise a ``KuiperRuntimeError``. Both of these exceptions are
-subclasses of the ``KuiperError`` base class.
+subclasses of the ``KuiperError`` base class, which has ``start`` and ``end`` attributes with the location of the error
+in the expression, if known, and a ``kind`` attribute describing the type of error.
 """
 
 from ._core import (
     CustomFunction,
+    KuiperCompileError,
+    KuiperError,
     KuiperExpression,
+    KuiperRuntimeError,
     compile_expression,
+    format_expression,
+    get_method_docs,
+    list_functions,
+    register_function,
 )
 
 type JsonType = str | int | float | bool | None | list["JsonType"] | dict[str, "JsonType"]
 
 
-class KuiperError(Exception):
-    def __init__(self, message: str, start: int | None, end: int | None):
-        super().__init__(message)
-        self.start = start
-        self.end = end
-
-
-class KuiperCompileError(KuiperError):
-    pass
-
-
-class KuiperRuntimeError(KuiperError):
-    pass
-
-
 __all__ = [
     "KuiperCompileError",
     "KuiperError",
@@ -46,4 +39,8 @@ __all__ = [
     "compile_expression",
     "CustomFunction",
     "JsonType",
+    "format_expression",
+    "get_method_docs",
+    "list_functions",
+    "register_function",
 ]
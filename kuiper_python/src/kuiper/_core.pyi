@@ -1,14 +1,55 @@
 from collections.abc import Callable
-from typing import Any
+from typing import Any, Literal, TypedDict
 
 type JsonType = str | int | float | bool | None | list["JsonType"] | dict[str, "JsonType"]
 
+class FunctionExample(TypedDict):
+    input: str
+    output: str | None
+
+class FunctionDocs(TypedDict):
+    name: str
+    signature: str
+    description: str
+    examples: list[FunctionExample]
+
+class KuiperError(Exception):
+    message: str
+    start: int | None
+    end: int | None
+    kind: str | None
+    def __init__(
+        self, message: str, start: int | None = None, end: int | None = None, kind: str | None = None
+    ) -> None: ...
+
+class KuiperCompileError(KuiperError):
+    def __init__(
+        self, message: str, start: int | None = None, end: int | None = None, kind: str | None = None
+    ) -> None: ...
+
+class KuiperRuntimeError(KuiperError):
+    def __init__(
+        self, message: str, start: int | None = None, end: int | None = None, kind: str | None = None
+    ) -> None: ...
+
 class KuiperExpression:
     def run(self, *inputs: JsonType, max_operations: int | None = None) -> JsonType: ...
     def run_json(self, *inputs: str, max_operations: int | None = None) -> str: ...
+    def run_many(
+        self,
+        inputs: list[JsonType | tuple[JsonType, ...]],
+        parallel: bool = True,
+        max_operations: int | None = None,
+    ) -> list[JsonType]: ...
 
 class CustomFunction:
-    def __init__(self, name: str, target: Callable[..., Any]) -> None: ...
+    def __init__(
+        self, name: str, target: Callable[..., Any], min_args: int = 0, max_args: int | None = None
+    ) -> None: ...
+
+def register_function(
+    name: str, target: Callable[..., Any], min_args: int = 0, max_args: int | None = None
+) -> None: ...
 
 def compile_expression(
     expression: str,
@@ -16,4 +57,10 @@ def compile_expression(
     optimizer_operation_limit: int = 100000,
     max_macro_expansions: int = 20,
     custom_functions: list[CustomFunction] | None = None,
+    type_checker: Literal["early", "late", "off"] = "off",
+    denied_functions: list[str] | None = None,
 ) -> KuiperExpression: ...
+
+def format_expression(expression: str, indent_size: int = 4) -> str: ...
+def get_method_docs(name: str) -> FunctionDocs | None: ...
+def list_functions() -> list[FunctionDocs]: ...
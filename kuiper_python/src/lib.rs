@@ -1,9 +1,12 @@
 mod compiler;
+mod docs;
 mod exceptions;
 mod expressions;
 pub(crate) mod python_json;
 
-use crate::compiler::{compile_expression_py, CustomFunction};
+use crate::compiler::{compile_expression_py, register_function, CustomFunction};
+use crate::docs::{format_expression, get_method_docs, list_functions};
+use crate::exceptions::{KuiperCompileError, KuiperError, KuiperRuntimeError};
 use crate::expressions::KuiperExpression;
 use pyo3::prelude::PyModule;
 use pyo3::types::PyModuleMethods;
@@ -12,7 +15,14 @@ use pyo3::{pymodule, wrap_pyfunction, Bound, PyResult, Python};
 #[pymodule]
 fn _core(py: Python<'_>, module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_function(wrap_pyfunction!(compile_expression_py, py)?)?;
+    module.add_function(wrap_pyfunction!(register_function, py)?)?;
+    module.add_function(wrap_pyfunction!(format_expression, py)?)?;
+    module.add_function(wrap_pyfunction!(get_method_docs, py)?)?;
+    module.add_function(wrap_pyfunction!(list_functions, py)?)?;
     module.add_class::<KuiperExpression>()?;
     module.add_class::<CustomFunction>()?;
+    module.add_class::<KuiperError>()?;
+    module.add_class::<KuiperCompileError>()?;
+    module.add_class::<KuiperRuntimeError>()?;
     Ok(())
 }
@@ -1,29 +1,14 @@
-use std::collections::HashMap;
-
 use kuiper_lang::{Span, TransformError};
 use pyo3::{
     exceptions::PyTypeError,
-    types::{PyAnyMethods, PyBool, PyDict, PyFloat, PyInt, PyList, PyListMethods, PyString},
-    FromPyObject, Py, PyAny, PyErr, PyResult, Python,
+    types::{
+        PyAnyMethods, PyBool, PyBoolMethods, PyDict, PyDictMethods, PyFloat, PyFloatMethods, PyInt,
+        PyList, PyListMethods, PySequence, PyString, PyStringMethods, PyTypeMethods,
+    },
+    Bound, PyAny, PyErr, Python,
 };
 use serde_json::{Map, Number, Value};
 
-pub(crate) struct PythonNone {}
-
-impl<'a, 'py> FromPyObject<'a, 'py> for PythonNone {
-    type Error = PyErr;
-
-    fn extract(obj: pyo3::Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
-        if obj.is_none() {
-            Ok(PythonNone {})
-        } else {
-            Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-                "Expected None",
-            ))
-        }
-    }
-}
-
 pub enum ConversionError {
     PythonError(PyErr),
     PlainMessage(String),
@@ -53,113 +38,116 @@ impl ConversionError {
     }
 }
 
-#[derive(FromPyObject)]
-pub(crate) enum PythonJson {
-    // This order is important, as it is the order PyO3 will try to convert the type to. That's why e.g. bool is
-    // before float - since a bool can also be coerced into 0.0 and 1.0.
-    #[pyo3(transparent, annotation = "bool")]
-    Bool(bool),
-    #[pyo3(transparent, annotation = "int")]
-    Int(i64),
-    #[pyo3(transparent, annotation = "float")]
-    Float(f64),
-    #[pyo3(transparent, annotation = "str")]
-    Str(String),
-    #[pyo3(transparent, annotation = "None")]
-    None(PythonNone),
-    #[pyo3(transparent, annotation = "list")]
-    List(Vec<PythonJson>),
-    #[pyo3(transparent, annotation = "dict")]
-    Dict(HashMap<String, PythonJson>),
-}
-
-impl PythonJson {
-    pub fn into_value(self) -> Result<Value, ConversionError> {
-        match self {
-            PythonJson::Bool(b) => Ok(Value::Bool(b)),
-
-            PythonJson::Int(i) => Ok(Value::Number(Number::from(i))),
-
-            PythonJson::Float(f) => Ok(Value::Number(Number::from_f64(f).ok_or(
-                ConversionError::PlainMessage("Invalid float value".to_string()),
-            )?)),
-
-            PythonJson::Str(s) => Ok(Value::String(s)),
-
-            PythonJson::None(_none) => Ok(Value::Null),
-
-            PythonJson::List(list) => {
-                let values = list
-                    .into_iter()
-                    .map(|item| item.into_value())
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(Value::Array(values))
-            }
-
-            PythonJson::Dict(dict) => {
-                let values = dict
-                    .into_iter()
-                    .map(|(k, v)| Ok((k, v.into_value()?)))
-                    .collect::<Result<Map<_, _>, ConversionError>>()?;
-                Ok(Value::Object(values))
-            }
+/// Convert a native python object into a JSON value.
+///
+/// The object must be a string, integer, float, boolean, None, sequence, or dictionary with string keys.
+/// Other objects are accepted if they can be converted to an integer through `__index__`, or to a
+/// float through `__float__`, such as numpy scalars and `Decimal`.
+pub(crate) fn python_to_value(obj: &Bound<'_, PyAny>) -> Result<Value, ConversionError> {
+    if obj.is_none() {
+        return Ok(Value::Null);
+    }
+    // This must come before int, since bool is a subclass of int in python.
+    if let Ok(b) = obj.cast::<PyBool>() {
+        return Ok(Value::Bool(b.is_true()));
+    }
+    if let Ok(i) = obj.cast::<PyInt>() {
+        if let Ok(i) = i.extract::<i64>() {
+            return Ok(Value::Number(Number::from(i)));
+        }
+        if let Ok(u) = i.extract::<u64>() {
+            return Ok(Value::Number(Number::from(u)));
         }
+        return Err(ConversionError::PlainMessage(
+            "Integer value out of range".to_string(),
+        ));
     }
-
-    pub fn into_python(self, py: Python) -> Py<PyAny> {
-        match self {
-            PythonJson::Bool(b) => PyBool::new(py, b).to_owned().into(),
-            PythonJson::Int(i) => PyInt::new(py, i).into(),
-            PythonJson::Float(f) => PyFloat::new(py, f).into(),
-            PythonJson::Str(s) => PyString::new(py, &s).into(),
-            PythonJson::None(_) => py.None(),
-            PythonJson::List(list) => {
-                let py_list = PyList::empty(py);
-                for item in list {
-                    py_list.append(item.into_python(py)).unwrap();
-                }
-                py_list.into()
-            }
-            PythonJson::Dict(dict) => {
-                let py_dict = PyDict::new(py);
-                for (k, v) in dict {
-                    py_dict.set_item(k, v.into_python(py)).unwrap();
-                }
-                py_dict.into()
-            }
+    if let Ok(f) = obj.cast::<PyFloat>() {
+        return Ok(Value::Number(Number::from_f64(f.value()).ok_or(
+            ConversionError::PlainMessage("Invalid float value".to_string()),
+        )?));
+    }
+    if let Ok(s) = obj.cast::<PyString>() {
+        return Ok(Value::String(s.to_str()?.to_owned()));
+    }
+    if let Ok(list) = obj.cast::<PyList>() {
+        return Ok(Value::Array(
+            list.iter()
+                .map(|item| python_to_value(&item))
+                .collect::<Result<Vec<_>, _>>()?,
+        ));
+    }
+    if let Ok(dict) = obj.cast::<PyDict>() {
+        let mut map = Map::with_capacity(dict.len());
+        for (key, value) in dict.iter() {
+            let key = key.cast::<PyString>().map_err(|_| {
+                ConversionError::PlainMessage("Dictionary keys must be strings".to_string())
+            })?;
+            map.insert(key.to_str()?.to_owned(), python_to_value(&value)?);
         }
+        return Ok(Value::Object(map));
+    }
+    // Tuples and other sequences are converted the same way as lists.
+    if let Ok(seq) = obj.cast::<PySequence>() {
+        return Ok(Value::Array(
+            seq.try_iter()?
+                .map(|item| python_to_value(&item?))
+                .collect::<Result<Vec<_>, _>>()?,
+        ));
+    }
+    if let Ok(i) = obj.extract::<i64>() {
+        return Ok(Value::Number(Number::from(i)));
+    }
+    if let Ok(u) = obj.extract::<u64>() {
+        return Ok(Value::Number(Number::from(u)));
+    }
+    if let Ok(f) = obj.extract::<f64>() {
+        return Ok(Value::Number(Number::from_f64(f).ok_or(
+            ConversionError::PlainMessage("Invalid float value".to_string()),
+        )?));
     }
 
-    pub fn from_value(value: Value) -> Result<Self, ConversionError> {
-        match value {
-            Value::Null => Ok(PythonJson::None(PythonNone {})),
-            Value::Bool(b) => Ok(PythonJson::Bool(b)),
-            Value::Number(number) => {
-                if let Some(i) = number.as_i64() {
-                    Ok(PythonJson::Int(i))
-                } else if let Some(f) = number.as_f64() {
-                    Ok(PythonJson::Float(f))
-                } else {
-                    Err(ConversionError::PlainMessage(
-                        "Invalid number value".to_string(),
-                    ))
-                }
+    Err(ConversionError::PlainMessage(format!(
+        "Object of type {} can not be converted to JSON",
+        obj.get_type().name()?
+    )))
+}
+
+/// Convert a JSON value into a native python object.
+pub(crate) fn value_to_python<'py>(
+    py: Python<'py>,
+    value: &Value,
+) -> Result<Bound<'py, PyAny>, ConversionError> {
+    Ok(match value {
+        Value::Null => py.None().into_bound(py),
+        Value::Bool(b) => PyBool::new(py, *b).to_owned().into_any(),
+        Value::Number(number) => {
+            if let Some(i) = number.as_i64() {
+                PyInt::new(py, i).into_any()
+            } else if let Some(u) = number.as_u64() {
+                PyInt::new(py, u).into_any()
+            } else if let Some(f) = number.as_f64() {
+                PyFloat::new(py, f).into_any()
+            } else {
+                return Err(ConversionError::PlainMessage(
+                    "Invalid number value".to_string(),
+                ));
             }
-            Value::String(s) => Ok(PythonJson::Str(s)),
-            Value::Array(values) => {
-                let python_values = values
-                    .into_iter()
-                    .map(PythonJson::from_value)
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(PythonJson::List(python_values))
+        }
+        Value::String(s) => PyString::new(py, s).into_any(),
+        Value::Array(values) => {
+            let list = PyList::empty(py);
+            for item in values {
+                list.append(value_to_python(py, item)?)?;
             }
-            Value::Object(map) => {
-                let python_map = map
-                    .into_iter()
-                    .map(|(k, v)| Ok((k, PythonJson::from_value(v)?)))
-                    .collect::<Result<HashMap<_, _>, ConversionError>>()?;
-                Ok(PythonJson::Dict(python_map))
+            list.into_any()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (k, v) in map {
+                dict.set_item(k, value_to_python(py, v)?)?;
             }
+            dict.into_any()
         }
-    }
+    })
 }
@@ -1,8 +1,20 @@
 import json
+from decimal import Decimal
 
 import pytest
 
-from kuiper import JsonType, KuiperCompileError, compile_expression, CustomFunction
+from kuiper import (
+    JsonType,
+    KuiperCompileError,
+    KuiperError,
+    KuiperRuntimeError,
+    compile_expression,
+    CustomFunction,
+    format_expression,
+    get_method_docs,
+    list_functions,
+    register_function,
+)
 
 
 @pytest.mark.parametrize(
@@ -64,3 +76,117 @@ def test_custom_function() -> None:
 
     result = exp.run({"num": 5})
     assert result == {"simple": 42, "with_args": [5, 1, 6, {"a": 1, "b": {"c": [1, 2, 3]}}]}
+
+
+def test_native_types_round_trip() -> None:
+    value = {"bool": True, "int": 2**64 - 1, "float": 1.5, "none": None, "list": [False, "a", {"b": []}]}
+    assert compile_expression("input", ["input"]).run(value) == value
+
+
+class Index:
+    def __index__(self) -> int:
+        return 7
+
+
+def test_sequence_and_number_like_inputs() -> None:
+    exp = compile_expression("input", ["input"])
+    assert exp.run({"a": (1, "b", (None,))}) == {"a": [1, "b", [None]]}
+    assert exp.run([Index()]) == [7]
+    assert exp.run(Decimal("1.5")) == 1.5
+
+
+def test_unsupported_input_type() -> None:
+    with pytest.raises(TypeError):
+        compile_expression("input", ["input"]).run({"a": object()})
+
+
+def test_denied_functions() -> None:
+    compile_expression("now()", [])
+    with pytest.raises(KuiperCompileError):
+        compile_expression("now()", [], denied_functions=["now"])
+
+
+def test_type_checker_mode() -> None:
+    with pytest.raises(KuiperCompileError):
+        compile_expression("input.a + 'test'", ["input"], type_checker="early")
+    with pytest.raises(ValueError):
+        compile_expression("input", ["input"], type_checker="sometimes")
+
+
+@pytest.mark.parametrize("parallel", [True, False])
+def test_run_many(parallel: bool) -> None:
+    exp = compile_expression("input.x * 2", ["input"])
+    inputs = [{"x": i} for i in range(1000)]
+    assert exp.run_many(inputs, parallel=parallel) == [i * 2 for i in range(1000)]
+
+
+def test_run_many_multiple_inputs() -> None:
+    exp = compile_expression("a + b", ["a", "b"])
+    assert exp.run_many([(1, 2), (3, 4)]) == [3, 7]
+
+
+def test_run_many_error() -> None:
+    exp = compile_expression("input.x / input.y", ["input"])
+    with pytest.raises(KuiperRuntimeError):
+        exp.run_many([{"x": 1, "y": 1}, {"x": 1, "y": 0}])
+
+
+def test_register_function() -> None:
+    register_function("lookup_test", lambda key, default=None: {"a": 1}.get(key, default), min_args=1, max_args=2)
+
+    exp = compile_expression('[lookup_test(input), lookup_test("b", 5)]', ["input"])
+    assert exp.run("a") == [1, 5]
+
+    with pytest.raises(KuiperCompileError):
+        compile_expression("lookup_test()", [])
+    with pytest.raises(KuiperCompileError):
+        compile_expression("lookup_test(1, 2, 3)", [])
+
+    # Explicit custom functions take precedence over registered ones.
+    exp = compile_expression(
+        "lookup_test(1)", [], custom_functions=[CustomFunction("lookup_test", lambda x: x * 2)]
+    )
+    assert exp.run() == 2
+
+
+def test_compile_error_details() -> None:
+    with pytest.raises(KuiperCompileError) as exc_info:
+        compile_expression("1 + foo()", [])
+    error = exc_info.value
+    assert isinstance(error, KuiperError)
+    assert error.kind == "build"
+    assert (error.start, error.end) == (4, 9)
+    assert str(error) == error.message
+    assert error.args[0] == str(error)
+
+
+def test_runtime_error_details() -> None:
+    exp = compile_expression("input.x / input.y", ["input"])
+    with pytest.raises(KuiperRuntimeError) as exc_info:
+        exp.run({"x": 1, "y": 0})
+    assert exc_info.value.kind == "divide_by_zero"
+    assert exc_info.value.args[0] == str(exc_info.value)
+    assert exc_info.value.start is not None
+
+    with pytest.raises(KuiperRuntimeError) as exc_info:
+        exp.run({"x": 1, "y": 1}, max_operations=1)
+    assert exc_info.value.kind == "operation_limit_exceeded"
+
+
+def test_format_expression() -> None:
+    assert format_expression("{\n\"a\":1+1\n}") == '{\n    "a": 1 + 1\n}'
+    assert format_expression("{\n\"a\":1+1\n}", indent_size=2) == '{\n  "a": 1 + 1\n}'
+    with pytest.raises(KuiperCompileError):
+        format_expression("1 +")
+
+
+def test_function_docs() -> None:
+    docs = get_method_docs("pow")
+    assert docs is not None
+    assert docs["signature"] == "pow(x, y)"
+    assert len(docs["examples"]) > 0
+    assert get_method_docs("does_not_exist") is None
+
+    names = [f["name"] for f in list_functions()]
+    assert "map" in names
+    assert names == sorted(names)
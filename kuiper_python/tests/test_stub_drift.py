@@ -0,0 +1,74 @@
+"""
+Stub drift check. The type stubs in _core.pyi are written by hand, this checks that they match the signatures of the
+compiled module, so that they don't drift apart when the Rust code changes.
+"""
+
+import ast
+import inspect
+from pathlib import Path
+from typing import Any
+
+import pytest
+
+import kuiper._core as core
+
+STUB_PATH = Path(__file__).parent.parent / "src" / "kuiper" / "_core.pyi"
+
+
+def _stub_definitions() -> dict[str, ast.FunctionDef | ast.ClassDef]:
+    module = ast.parse(STUB_PATH.read_text())
+    return {node.name: node for node in module.body if isinstance(node, ast.FunctionDef | ast.ClassDef)}
+
+
+def _stub_parameters(function: ast.FunctionDef) -> list[tuple[str, Any]]:
+    args = function.args
+    positional = args.posonlyargs + args.args
+    defaults: list[Any] = [inspect.Parameter.empty] * (len(positional) - len(args.defaults))
+    defaults += [ast.literal_eval(d) for d in args.defaults]
+    params = [(a.arg, d) for a, d in zip(positional, defaults, strict=True) if a.arg != "self"]
+    if args.vararg is not None:
+        params.append(("*" + args.vararg.arg, inspect.Parameter.empty))
+    for a, d in zip(args.kwonlyargs, args.kw_defaults, strict=True):
+        params.append((a.arg, inspect.Parameter.empty if d is None else ast.literal_eval(d)))
+    return params
+
+
+def _runtime_parameters(function: Any) -> list[tuple[str, Any]]:
+    return [
+        ("*" + p.name if p.kind == inspect.Parameter.VAR_POSITIONAL else p.name, p.default)
+        for p in inspect.signature(function).parameters.values()
+        if p.name != "self"
+    ]
+
+
+def _runtime_functions() -> list[tuple[str, str | None, Any]]:
+    functions: list[tuple[str, str | None, Any]] = []
+    for name in dir(core):
+        item = getattr(core, name)
+        if name.startswith("_"):
+            continue
+        if isinstance(item, type):
+            if item.__text_signature__ is not None:
+                functions.append((name, "__init__", item))
+            for method_name, method in vars(item).items():
+                if not method_name.startswith("_") and callable(method):
+                    functions.append((name, method_name, method))
+        elif callable(item):
+            functions.append((name, None, item))
+    return functions
+
+
+@pytest.mark.parametrize("name,method,function", _runtime_functions())
+def test_stub_matches_runtime(name: str, method: str | None, function: Any) -> None:
+    definitions = _stub_definitions()
+    assert name in definitions, f"{name} is missing from _core.pyi"
+
+    stub = definitions[name]
+    if method is not None:
+        assert isinstance(stub, ast.ClassDef)
+        methods = {node.name: node for node in stub.body if isinstance(node, ast.FunctionDef)}
+        assert method in methods, f"{name}.{method} is missing from _core.pyi"
+        stub = methods[method]
+
+    assert isinstance(stub, ast.FunctionDef)
+    assert _stub_parameters(stub) == _runtime_parameters(function)